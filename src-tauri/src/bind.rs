@@ -0,0 +1,177 @@
+//! sidecar 监听地址限制。
+//!
+//! rclone rc 与 OpenList 只允许监听回环地址，启动前由后端改写启动参数（rclone）或配置文件（OpenList），
+//! 额外启动参数中的 `--rc-addr` 同样会被改写。Linux 下 rclone rc 默认改用数据目录中的 Unix 套接字
+//! （目录 0700、套接字 0600），后端 rc 客户端与请求中转都通过该套接字访问。
+
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde_json::Value;
+
+/// 回环地址（未指定或指定了非回环地址时使用）
+const LOOPBACK: &str = "127.0.0.1";
+/// Unix 套接字所在目录（位于数据目录下）
+const SOCKET_DIR: &str = "run";
+const RC_SOCKET_NAME: &str = "rclone-rc.sock";
+/// `sun_path` 长度上限为 108 字节，超出时回退到回环地址
+const SOCKET_PATH_MAX: usize = 100;
+/// 等待 rclone 创建套接字的最长时间
+const SOCKET_WAIT: Duration = Duration::from_secs(15);
+
+/// 是否为回环地址（`localhost`、`127.0.0.0/8`、`::1`）
+pub fn is_loopback(host: &str) -> bool {
+    host.eq_ignore_ascii_case("localhost")
+        || host
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse::<IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback())
+}
+
+/// 设置中是否启用 rclone rc 的 Unix 套接字（`settings.rcUnixSocket`，默认开启，仅 Linux 生效）
+pub fn rc_unix_socket_enabled(settings: &Value) -> bool {
+    cfg!(target_os = "linux") && settings["rcUnixSocket"].as_bool().unwrap_or(true)
+}
+
+/// 取出参数中所有的 `--rc-addr`（`--rc-addr=x` 与 `--rc-addr x` 两种形式）
+fn take_rc_addrs(args: &mut Vec<String>) -> Vec<String> {
+    let mut addrs = Vec::new();
+    let mut rest = Vec::with_capacity(args.len());
+    let mut iter = std::mem::take(args).into_iter();
+    while let Some(arg) = iter.next() {
+        if let Some(addr) = arg.strip_prefix("--rc-addr=") {
+            addrs.push(addr.to_string());
+        } else if arg == "--rc-addr" {
+            addrs.extend(iter.next());
+        } else {
+            rest.push(arg);
+        }
+    }
+    *args = rest;
+    addrs
+}
+
+/// 把地址限制为回环地址，保留端口；Unix 套接字地址原样保留
+fn loopback_addr(addr: &str) -> String {
+    if addr.starts_with("unix://") || addr.starts_with('/') {
+        return addr.to_string();
+    }
+    match addr.rsplit_once(':') {
+        Some((host, _)) if is_loopback(host) => addr.to_string(),
+        Some((_, port)) => format!("{}:{}", LOOPBACK, port),
+        None => format!("{}:{}", LOOPBACK, addr),
+    }
+}
+
+/// 改写 `rclone rcd` 的监听地址：指定了套接字时监听该套接字，否则只监听回环地址。
+/// 多个 `--rc-addr` 只保留第一个（rclone 会同时监听所有地址）
+pub fn secure_rclone_args(mut args: Vec<String>, socket: Option<&Path>) -> Vec<String> {
+    let addrs = take_rc_addrs(&mut args);
+    let addr = match socket {
+        Some(socket) => Some(format!("unix://{}", socket.display())),
+        None => addrs.first().map(|addr| loopback_addr(addr)),
+    };
+    // 未指定时 rclone 默认监听 localhost:5572
+    if let Some(addr) = addr {
+        let pos = args
+            .iter()
+            .position(|arg| arg == "rcd")
+            .map_or(0, |i| i + 1);
+        args.insert(pos, format!("--rc-addr={}", addr));
+    }
+    args
+}
+
+/// 准备 rclone rc 套接字：创建仅当前用户可访问的目录并删除残留的套接字文件。
+/// 路径过长或创建失败时返回 None（回退到回环地址）
+#[cfg(unix)]
+pub fn prepare_rc_socket(data_dir: &Path) -> Option<PathBuf> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = data_dir.join(SOCKET_DIR);
+    let socket = dir.join(RC_SOCKET_NAME);
+    if socket.as_os_str().len() > SOCKET_PATH_MAX {
+        eprintln!(
+            "rclone rc socket path is too long, falling back to loopback: {}",
+            socket.display()
+        );
+        return None;
+    }
+    let prepared = std::fs::create_dir_all(&dir)
+        .and_then(|_| std::fs::set_permissions(&dir, std::fs::Permissions::from_mode(0o700)));
+    if let Err(e) = prepared {
+        eprintln!("Failed to prepare rclone rc socket dir: {}", e);
+        return None;
+    }
+    let _ = std::fs::remove_file(&socket);
+    Some(socket)
+}
+
+#[cfg(not(unix))]
+pub fn prepare_rc_socket(_data_dir: &Path) -> Option<PathBuf> {
+    None
+}
+
+/// 等待 rclone 创建套接字后把权限收紧为 0600（rclone 按 umask 创建）
+#[cfg(unix)]
+pub fn restrict_socket(socket: PathBuf) {
+    use std::os::unix::fs::PermissionsExt;
+
+    tauri::async_runtime::spawn(async move {
+        let deadline = tokio::time::Instant::now() + SOCKET_WAIT;
+        while tokio::time::Instant::now() < deadline {
+            if socket.exists() {
+                if let Err(e) =
+                    std::fs::set_permissions(&socket, std::fs::Permissions::from_mode(0o600))
+                {
+                    eprintln!("Failed to restrict rclone rc socket: {}", e);
+                }
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        eprintln!("rclone rc socket was not created: {}", socket.display());
+    });
+}
+
+#[cfg(not(unix))]
+pub fn restrict_socket(_socket: PathBuf) {}
+
+/// 启动前把 OpenList 配置中的 `scheme.address` 限制为回环地址（OpenList 默认监听 0.0.0.0）。
+/// 配置文件不存在时只写入监听地址，其余字段由 OpenList 按默认值补全
+pub fn secure_openlist_config(data_dir: &Path) -> anyhow::Result<()> {
+    let path = data_dir.join("config.json");
+    let mut config: Value = match std::fs::read_to_string(&path) {
+        Ok(content) => serde_json::from_str(&content)?,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => serde_json::json!({}),
+        Err(e) => return Err(e.into()),
+    };
+    let address = config
+        .pointer("/scheme/address")
+        .and_then(Value::as_str)
+        .unwrap_or("");
+    if is_loopback(address) {
+        return Ok(());
+    }
+    let Some(root) = config.as_object_mut() else {
+        return Err(anyhow::anyhow!(
+            "Invalid OpenList config: {}",
+            path.display()
+        ));
+    };
+    let scheme = root
+        .entry("scheme")
+        .or_insert_with(|| serde_json::json!({}));
+    let Some(scheme) = scheme.as_object_mut() else {
+        return Err(anyhow::anyhow!(
+            "Invalid OpenList config: {}",
+            path.display()
+        ));
+    };
+    scheme.insert("address".to_string(), Value::from(LOOPBACK));
+    std::fs::create_dir_all(data_dir)?;
+    std::fs::write(&path, serde_json::to_string_pretty(&config)?)?;
+    Ok(())
+}
@@ -1,7 +1,21 @@
+use base64::Engine as _;
 use rand::distr::SampleString as _;
+use serde_json::Value;
 
 use crate::State;
 
+/// 由后端保管、不返回给 webview 的框架凭据（JSON Pointer）
+const FRAMEWORK_SECRETS: [&str; 2] = ["/framework/rclone/password", "/framework/openlist/password"];
+
+/// 旧版前端写入的密码编码前缀（XOR + base64，见 `passwordEncoding.ts`）
+const LEGACY_ENCODING_PREFIX: &str = "nmenc:";
+const LEGACY_ENCODING_KEY: &str = "NetMount2024!";
+
+/// 默认凭据长度（rclone 用户名、rclone 密码、OpenList 密码）
+const RCLONE_USER_LEN: usize = 32;
+const RCLONE_PASSWORD_LEN: usize = 128;
+const OPENLIST_PASSWORD_LEN: usize = 16;
+
 fn random_str(len: usize) -> String {
     rand::distr::Alphanumeric.sample_string(&mut rand::rng(), len)
 }
@@ -9,6 +23,13 @@ fn random_str(len: usize) -> String {
 #[derive(Clone)]
 pub struct Config(pub serde_json::Value);
 
+/// 框架的登录凭据
+#[derive(Clone, Debug, Default)]
+pub struct Credentials {
+    pub user: String,
+    pub password: String,
+}
+
 impl State for Config {}
 
 impl Default for Config {
@@ -19,9 +40,133 @@ impl Default for Config {
             "api": { "url": "https://api.hotpe.top/API/NetMount" },
             "settings": { "themeMode": "auto", "startHide": false, "autoRecoverComponents": true },
             "framework": {
-                "rclone": { "user": random_str(32), "password":  random_str(128) },
-                "openlist": { "user": "admin", "password":  random_str(16) }
+                "rclone": { "user": random_str(RCLONE_USER_LEN), "password":  random_str(RCLONE_PASSWORD_LEN) },
+                "openlist": { "user": "admin", "password":  random_str(OPENLIST_PASSWORD_LEN) }
             }
         }))
     }
 }
+
+impl Config {
+    /// `framework` 为 `rclone` 或 `openlist`
+    pub fn credentials(&self, framework: &str) -> Credentials {
+        let field = |key: &str| {
+            self.0
+                .pointer(&format!("/framework/{}/{}", framework, key))
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string()
+        };
+        Credentials {
+            user: field("user"),
+            password: decode_legacy_password(&field("password")),
+        }
+    }
+
+    /// 设置中是否每次启动时轮换框架凭据（`settings.rotateCredentials`，默认关闭）
+    pub fn rotate_credentials(&self) -> bool {
+        self.0["settings"]["rotateCredentials"]
+            .as_bool()
+            .unwrap_or(false)
+    }
+
+    /// 生成新的随机凭据：rclone 用户名与密码都重新生成，OpenList 保留用户名
+    pub fn rotated_credentials(&self, framework: &str) -> Credentials {
+        match framework {
+            "rclone" => Credentials {
+                user: random_str(RCLONE_USER_LEN),
+                password: random_str(RCLONE_PASSWORD_LEN),
+            },
+            _ => Credentials {
+                user: self.credentials(framework).user,
+                password: random_str(OPENLIST_PASSWORD_LEN),
+            },
+        }
+    }
+
+    /// 更新框架密码（OpenList 轮换成功后写回配置）
+    pub fn set_password(&mut self, framework: &str, password: String) {
+        let ptr = format!("/framework/{}", framework);
+        if let Some(obj) = self.0.pointer_mut(&ptr).and_then(Value::as_object_mut) {
+            obj.insert("password".to_string(), Value::from(password));
+        }
+    }
+
+    /// 返回给 webview 的配置，不包含框架密码
+    pub fn redacted(&self) -> Value {
+        let mut data = self.0.clone();
+        for ptr in FRAMEWORK_SECRETS {
+            let (parent, key) = ptr.rsplit_once('/').unwrap();
+            if let Some(obj) = data.pointer_mut(parent).and_then(Value::as_object_mut) {
+                obj.remove(key);
+            }
+        }
+        data
+    }
+
+    /// 前端提交的配置中不含框架密码，保存前填回当前值
+    pub fn restore_secrets(&self, data: &mut Value) {
+        for ptr in FRAMEWORK_SECRETS {
+            let Some(current) = self.0.pointer(ptr).cloned() else {
+                continue;
+            };
+            let (parent, key) = ptr.rsplit_once('/').unwrap();
+            if let Some(obj) = data.pointer_mut(parent).and_then(Value::as_object_mut) {
+                obj.insert(key.to_string(), current);
+            }
+        }
+    }
+
+    /// 补全缺失的框架凭据（原先由前端默认配置生成），返回是否有修改
+    pub fn ensure_credentials(&mut self) -> bool {
+        let defaults = Self::default();
+        let mut changed = false;
+        for framework in ["rclone", "openlist"] {
+            let ptr = format!("/framework/{}", framework);
+            if self.0.pointer(&ptr).is_none() {
+                if !self.0.get("framework").is_some_and(Value::is_object) {
+                    self.0["framework"] = serde_json::json!({});
+                }
+                self.0["framework"][framework] = serde_json::json!({});
+            }
+            let Some(obj) = self.0.pointer_mut(&ptr).and_then(Value::as_object_mut) else {
+                continue;
+            };
+            for (key, value) in defaults
+                .0
+                .pointer(&ptr)
+                .and_then(Value::as_object)
+                .into_iter()
+                .flatten()
+            {
+                if !obj.get(key).is_some_and(Value::is_string) {
+                    obj.insert(key.clone(), value.clone());
+                    changed = true;
+                }
+            }
+        }
+        changed
+    }
+}
+
+/// 解码旧版前端保存的 `nmenc:` 密码，其它值原样返回
+fn decode_legacy_password(value: &str) -> String {
+    let Some(encoded) = value.strip_prefix(LEGACY_ENCODING_PREFIX) else {
+        return value.to_string();
+    };
+    let Some(decoded) = base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .ok()
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+    else {
+        return value.to_string();
+    };
+    // 与前端一致：按 UTF-16 码元与密钥循环异或
+    let key: Vec<u16> = LEGACY_ENCODING_KEY.encode_utf16().collect();
+    let units: Vec<u16> = decoded
+        .encode_utf16()
+        .enumerate()
+        .map(|(i, c)| c ^ key[i % key.len()])
+        .collect();
+    String::from_utf16_lossy(&units)
+}
@@ -225,6 +225,44 @@ fn maybe_add_mount_snapshot<W: Write + Seek>(
     Ok(())
 }
 
+/// rclone remote 概要（仅名称、类型与选项名）；加密配置用内存中的密码解密
+fn maybe_add_rclone_remotes<W: Write + Seek>(
+    zip: &mut zip::ZipWriter<W>,
+    entry_name: &str,
+) -> anyhow::Result<()> {
+    let encryption = crate::rclone::configpass::status();
+    let remotes: Vec<serde_json::Value> = if encryption.encrypted && !encryption.unlocked {
+        Vec::new()
+    } else {
+        crate::rclone::conffile::parse(&crate::rclone::configpass::read_config()?)
+            .iter()
+            .map(|remote| {
+                serde_json::json!({
+                    "name": remote.name,
+                    "type": remote.remote_type(),
+                    "keys": remote
+                        .options
+                        .iter()
+                        .map(|(k, _)| k.as_str())
+                        .filter(|k| *k != "type")
+                        .collect::<Vec<_>>(),
+                })
+            })
+            .collect()
+    };
+
+    let snapshot = serde_json::json!({
+        "encrypted": encryption.encrypted,
+        "unlocked": encryption.unlocked,
+        "count": remotes.len(),
+        "remotes": remotes
+    });
+
+    let pretty = serde_json::to_string_pretty(&snapshot)?;
+    zip_add_string(zip, entry_name, &pretty)?;
+    Ok(())
+}
+
 #[tauri::command]
 pub fn export_diagnostics(
     app: tauri::AppHandle<Runtime>,
@@ -287,6 +325,9 @@ pub fn export_diagnostics(
         ) {
             warnings.push(format!("mount snapshot: {}", e));
         }
+        if let Err(e) = maybe_add_rclone_remotes(&mut zip, "rclone/remotes.json") {
+            warnings.push(format!("rclone remotes: {}", e));
+        }
 
         // log tails
         if let Err(e) = maybe_add_tail_file(
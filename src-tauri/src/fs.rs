@@ -105,7 +105,7 @@ fn fix_openlist_config_paths(config_path: &Path) -> anyhow::Result<()> {
     Ok(())
 }
 
-fn resolve_path(app: &tauri::AppHandle<Runtime>, path: &str) -> anyhow::Result<PathBuf> {
+pub(crate) fn resolve_path(app: &tauri::AppHandle<Runtime>, path: &str) -> anyhow::Result<PathBuf> {
     if path.starts_with("~") {
         let home = app.path().home_dir()
             .map_err(|e| anyhow::anyhow!("Failed to get home dir: {}", e))?;
@@ -324,13 +324,18 @@ fn copy_dir_all(src: impl AsRef<Path>, dst: impl AsRef<Path>) -> anyhow::Result<
     Ok(())
 }
 
-/// 导出配置到 zip 文件
+/// 导出配置到 zip 文件；默认不包含加密密钥 `config.key`，`include_key` 为 true 时才附带
 #[tauri::command]
 pub fn export_config(
     app: tauri::AppHandle<Runtime>,
     out_path: String,
+    include_key: Option<bool>,
 ) -> anyhow_tauri::TAResult<String> {
-    fn inner(app: &tauri::AppHandle<Runtime>, out_path: &str) -> anyhow::Result<String> {
+    fn inner(
+        app: &tauri::AppHandle<Runtime>,
+        out_path: &str,
+        include_key: bool,
+    ) -> anyhow::Result<String> {
         let out_path = out_path.trim();
         if out_path.is_empty() {
             return Err(anyhow::anyhow!("Output path is required"));
@@ -359,6 +364,7 @@ pub fn export_config(
             options: &zip::write::FileOptions<'_, ()>,
             src_dir: &Path,
             base_dir: &Path,
+            include_key: bool,
         ) -> anyhow::Result<()> {
             for entry in fs::read_dir(src_dir)? {
                 let entry = entry?;
@@ -372,9 +378,14 @@ pub fn export_config(
                 }
                 
                 let relative_path = path.strip_prefix(base_dir)?;
+
+                // 密钥与加密后的配置放在一起等于没有加密，默认排除
+                if !include_key && relative_path == Path::new(crate::secrets::KEY_FILE) {
+                    continue;
+                }
                 
                 if path.is_dir() {
-                    add_dir_to_zip(zip, options, &path, base_dir)?;
+                    add_dir_to_zip(zip, options, &path, base_dir, include_key)?;
                 } else {
                     let entry_name = relative_path.to_string_lossy().replace('\\', "/");
                     zip.start_file(&entry_name, *options)?;
@@ -388,14 +399,14 @@ pub fn export_config(
         }
 
         if data_dir.exists() {
-            add_dir_to_zip(&mut zip, &options, &data_dir, &data_dir)?;
+            add_dir_to_zip(&mut zip, &options, &data_dir, &data_dir, include_key)?;
         }
 
         zip.finish().map_err(anyhow::Error::from)?;
         Ok(out.to_string_lossy().to_string())
     }
 
-    inner(&app, &out_path).map_err(Into::into)
+    inner(&app, &out_path, include_key.unwrap_or(false)).map_err(Into::into)
 }
 
 /// 从 zip 文件导入配置
@@ -518,6 +529,9 @@ pub fn import_config(
             }
         }
         
+        // 备份中没有密钥时保留本机的 config.key（在本机恢复时可继续解密）
+        let keep_key = !temp_dir.join(crate::secrets::KEY_FILE).exists();
+
         // 删除旧配置（忽略失败，因为文件可能被占用）
         for entry in fs::read_dir(&data_dir)? {
             let entry = entry?;
@@ -528,6 +542,9 @@ pub fn import_config(
             if file_name_str == ".backup_temp" || file_name_str == ".backup_old" {
                 continue;
             }
+            if keep_key && file_name_str == crate::secrets::KEY_FILE {
+                continue;
+            }
             
             let path = entry.path();
             if path.is_dir() {
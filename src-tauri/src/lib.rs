@@ -12,11 +12,17 @@ use locale::Locale;
 use tray::Tray;
 
 mod autostart;
+mod bind;
 mod config;
 mod diagnostics;
 mod fs;
 mod locale;
+mod mount;
+mod rclone;
+mod relay;
+mod secrets;
 mod sidecar;
+mod task;
 mod tray;
 mod utils;
 
@@ -66,6 +72,16 @@ use crate::utils::is_winfsp_installed;
 
 pub(crate) type Runtime = tauri::Wry;
 
+/// 先写入临时文件再替换 config.json，写入中途失败时不会留下不完整的配置
+fn write_config_file(path: &Path, data: &serde_json::Value) -> anyhow::Result<()> {
+    let tmp = path.with_extension("json.tmp");
+    let file = File::create(&tmp)?;
+    serde_json::to_writer_pretty(&file, data)?;
+    file.sync_all()?;
+    std::fs::rename(&tmp, path)?;
+    Ok(())
+}
+
 pub trait State: Send + Sync + 'static {}
 pub struct StateWrapper<T: State>(RwLock<T>);
 
@@ -75,6 +91,7 @@ pub trait AppExt {
     fn set_app_state<T: State>(&self, state: T);
     fn update_app_config(&self) -> anyhow::Result<()>;
     fn write_app_config(&self, config: Config) -> anyhow::Result<()>;
+    fn modify_app_config(&self, modify: impl FnOnce(&mut Config)) -> anyhow::Result<()>;
     fn app_data_dir(&self) -> PathBuf;
     fn app_config_file(&self) -> PathBuf;
     fn app_quit(&self);
@@ -116,12 +133,18 @@ impl<M: tauri::Manager<Runtime>> AppExt for M {
 
     fn write_app_config(&self, config: Config) -> anyhow::Result<()> {
         self.set_app_state(config);
-        let file = File::create(self.app_config_file())?;
-        serde_json::to_writer_pretty(
-            file,
-            &self.with_app_state::<Config, _>(|config| config.0.clone()),
-        )?;
-        Ok(())
+        // 敏感字段加密后再写入，失败时不截断原文件
+        let data = self.with_app_state::<Config, _>(|config| secrets::seal_config(&config.0))?;
+        write_config_file(&self.app_config_file(), &data)
+    }
+
+    fn modify_app_config(&self, modify: impl FnOnce(&mut Config)) -> anyhow::Result<()> {
+        // 修改与写入期间持有写锁，避免与其它保存交错而覆盖修改
+        let wrapper = self.state::<StateWrapper<Config>>();
+        let mut config = wrapper.deref().0.write().unwrap();
+        modify(&mut *config);
+        let data = secrets::seal_config(&config.0)?;
+        write_config_file(&self.app_config_file(), &data)
     }
 
     fn app_data_dir(&self) -> PathBuf {
@@ -133,7 +156,7 @@ impl<M: tauri::Manager<Runtime>> AppExt for M {
     }
 
     fn app_quit(&self) {
-        sidecar::cleanup();
+        // 卸载与终止 sidecar 在 ExitRequested 中完成
         self.app_handle().exit(0)
     }
 
@@ -250,6 +273,9 @@ pub fn init() -> anyhow::Result<()> {
             toggle_devtools,
             get_config,
             update_config,
+            get_config_encryption,
+            unlock_config,
+            set_config_passphrase,
             get_language_pack,
             download_file,
             diagnostics::export_diagnostics,
@@ -273,10 +299,59 @@ pub fn init() -> anyhow::Result<()> {
             copy_file,
             register_sidecar_pid,
             spawn_sidecar,
-            run_sidecar_once,
             kill_sidecar,
             fs::export_config,
-            fs::import_config
+            fs::import_config,
+            task::run_task,
+            task::get_task_history,
+            task::prune_task_history,
+            task::read_task_run_log,
+            task::clean_task_run_logs,
+            task::get_task_queue,
+            task::move_queued_task,
+            task::cancel_queued_task,
+            task::dry_run_task,
+            task::list_task_plans,
+            task::get_task_plan,
+            task::approve_task_plan,
+            task::get_check_report,
+            task::export_check_report,
+            task::get_bisync_state,
+            task::clean_bisync_locks,
+            task::preview_bisync_resync,
+            task::bisync_resync,
+            task::list_task_versions,
+            task::restore_task_version,
+            task::start_task_watch,
+            task::stop_task_watch,
+            task::list_task_watches,
+            task::get_task_checkpoint,
+            task::discard_task_checkpoint,
+            mount::get_mount_health,
+            mount::check_mount_health,
+            mount::reset_mount_health,
+            mount::get_fuse_readiness,
+            mount::validate_mount_point,
+            mount::unmount_all,
+            mount::mount_and_wait,
+            mount::suggest_mount_path,
+            rclone::rclone_obscure,
+            rclone::rclone_looks_obscured,
+            rclone::cleanup::rclone_cleanup,
+            rclone::preview_rclone_conf,
+            rclone::import_rclone_conf,
+            rclone::export_rclone_conf,
+            rclone::get_rclone_config_encryption,
+            rclone::unlock_rclone_config,
+            rclone::set_rclone_config_password,
+            relay::relay_request,
+            relay::relay_openlist_login,
+            relay::relay_upload_begin,
+            relay::relay_upload_chunk,
+            relay::relay_upload_finish,
+            relay::relay_upload_abort,
+            relay::relay_add_openlist_remote,
+            reset_openlist_password
         ])
         .setup(|app| {
             // 初始化 Job Object（Windows 进程树管理）
@@ -302,12 +377,32 @@ pub fn init() -> anyhow::Result<()> {
 
             //配置文件
             if let Some(file) = File::open(app.app_config_file()).ok() {
-                app.set_app_state(Config(serde_json::from_reader(file)?))
+                let mut data: serde_json::Value = serde_json::from_reader(file)?;
+                let migrate = match secrets::open_config(&mut data) {
+                    // 旧配置中的明文敏感字段，加密后写回
+                    Ok(plaintext) => plaintext,
+                    Err(e) => {
+                        // 保持密文，写回时也不会覆盖
+                        eprintln!("Failed to decrypt config secrets: {}", e);
+                        false
+                    }
+                };
+                let mut config = Config(data);
+                // 框架凭据原先由前端生成，缺失时在后端补全
+                if config.ensure_credentials() || migrate {
+                    app.write_app_config(config)?
+                } else {
+                    app.set_app_state(config)
+                }
             } else {
                 app.write_app_config(Config::default())?
             };
             app.update_app_config()?;
 
+            // 挂载健康监测（rclone 重启后残留的 FUSE 挂载自动恢复）
+            #[cfg(target_os = "linux")]
+            mount::health::spawn_monitor(app.handle().clone());
+
             //开发者工具
             #[cfg(debug_assertions)]
             if let Some(window) = app.app_main_window() {
@@ -316,8 +411,15 @@ pub fn init() -> anyhow::Result<()> {
             Ok(())
         })
         .build(tauri::generate_context!())?
-        .run(|_, event| match event {
-            tauri::RunEvent::ExitRequested { .. } | tauri::RunEvent::Exit => {
+        .run(|app, event| match event {
+            tauri::RunEvent::ExitRequested { api, code, .. } => {
+                // 先卸载挂载并等待写回缓存上传，完成后再次退出
+                if !mount::shutdown::exit_ready() {
+                    api.prevent_exit();
+                    mount::shutdown::exit_after_shutdown(app.clone(), code.unwrap_or(0));
+                }
+            }
+            tauri::RunEvent::Exit => {
                 // Ensure sidecars don't survive app shutdown on any platform.
                 sidecar::cleanup();
             }
@@ -343,7 +445,37 @@ fn get_language_pack(app: tauri::AppHandle<Runtime>) -> serde_json::Value {
 
 #[tauri::command]
 fn get_config(app: tauri::AppHandle<Runtime>) -> serde_json::Value {
-    app.with_app_state::<Config, _>(|config| config.0.clone())
+    // 框架密码只在后端使用，不返回给 webview
+    app.with_app_state::<Config, _>(|config| config.redacted())
+}
+
+/// config.json 敏感字段的加密状态
+#[tauri::command]
+fn get_config_encryption() -> secrets::ConfigEncryption {
+    secrets::status()
+}
+
+/// 口令模式下输入口令，解密内存中的配置
+#[tauri::command]
+fn unlock_config(app: tauri::AppHandle<Runtime>, passphrase: String) -> anyhow_tauri::TAResult<()> {
+    let mut data = app.with_app_state::<Config, _>(|config| config.0.clone());
+    secrets::unlock(&mut data, &passphrase)?;
+    app.set_app_state(Config(data));
+    app.update_app_config()?;
+    Ok(())
+}
+
+/// 设置或修改加密口令；`passphrase` 为空时改回使用密钥文件
+#[tauri::command]
+fn set_config_passphrase(
+    app: tauri::AppHandle<Runtime>,
+    passphrase: Option<String>,
+) -> anyhow_tauri::TAResult<secrets::ConfigEncryption> {
+    secrets::set_passphrase(passphrase.as_deref())?;
+    let data = app.with_app_state::<Config, _>(|config| config.0.clone());
+    app.write_app_config(Config(data))?;
+    secrets::remove_stale_key_file();
+    Ok(secrets::status())
 }
 
 #[tauri::command]
@@ -351,7 +483,13 @@ fn update_config(
     app: tauri::AppHandle<Runtime>,
     data: serde_json::Value,
 ) -> anyhow_tauri::TAResult<()> {
-    app.write_app_config(Config(data))?;
+    // 任务依赖存在环时拒绝保存
+    task::chain::validate(&task::parse_tasks(&data))?;
+    app.modify_app_config(|config| {
+        let mut data = data;
+        config.restore_secrets(&mut data);
+        config.0 = data;
+    })?;
     app.update_app_config()?;
     Ok(())
 }
@@ -369,6 +507,7 @@ async fn stop_components() -> Result<(), String> {
     if let Some(pid) = sidecar::get_sidecar_pid("rclone") {
         println!("Stopping rclone (PID: {})", pid);
         sidecar::kill_sidecar("rclone");
+        rclone::clear_endpoint();
         // 等待进程完全退出
         tokio::time::sleep(Duration::from_millis(300)).await;
     }
@@ -377,6 +516,7 @@ async fn stop_components() -> Result<(), String> {
     if let Some(pid) = sidecar::get_sidecar_pid("openlist") {
         println!("Stopping openlist (PID: {})", pid);
         sidecar::kill_sidecar("openlist");
+        relay::clear_openlist();
         // 等待进程完全退出
         tokio::time::sleep(Duration::from_millis(300)).await;
     }
@@ -564,6 +704,26 @@ async fn spawn_sidecar(
         let _ = std::fs::create_dir_all(&work_dir);
     }
 
+    // rclone rc 与 OpenList 只允许监听回环地址（Linux 下 rc 可改用 Unix 套接字）
+    let mut rc_socket = None;
+    let args = match sidecar_name {
+        "rclone" => {
+            let use_socket = app.with_app_state::<Config, _>(|config| {
+                bind::rc_unix_socket_enabled(&config.0["settings"])
+            });
+            if use_socket {
+                rc_socket = bind::prepare_rc_socket(&resolve_data_dir());
+            }
+            bind::secure_rclone_args(args, rc_socket.as_deref())
+        }
+        "openlist" => {
+            bind::secure_openlist_config(&relay::openlist_data_dir(&args, &work_dir))
+                .map_err(|e| format!("Failed to restrict OpenList address: {}", e))?;
+            args
+        }
+        _ => args,
+    };
+
     // sidecar 统一诊断日志
     let log_dir = work_dir.join("log");
     let _ = std::fs::create_dir_all(&log_dir);
@@ -588,6 +748,22 @@ async fn spawn_sidecar(
         .current_dir(&work_dir)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped());
+
+    // 加密的 rclone 配置：通过环境变量传入密码，未输入密码时不启动（rclone 会等待终端输入）
+    // rc 凭据同样通过环境变量传入，不出现在启动参数中；开启轮换时每次启动重新生成，只保存在内存中
+    let rc_credentials = app.with_app_state::<Config, _>(|config| {
+        let credentials = config.credentials("rclone");
+        // 用户名为空表示不启用 rc 认证（--rc-no-auth），不轮换
+        if config.rotate_credentials() && !credentials.user.is_empty() {
+            config.rotated_credentials("rclone")
+        } else {
+            credentials
+        }
+    });
+    if sidecar_name == "rclone" {
+        rclone::configpass::apply_env(&mut cmd).map_err(|e| e.to_string())?;
+        rclone::apply_rc_env(&mut cmd, &rc_credentials);
+    }
     
     // Windows: 隐藏命令行窗口
     #[cfg(target_os = "windows")]
@@ -606,6 +782,14 @@ async fn spawn_sidecar(
     
     // 注册到 Job Object（使用简短名称）
     sidecar::register_sidecar_pid(sidecar_name, pid);
+    if let Some(socket) = rc_socket {
+        bind::restrict_socket(socket);
+    }
+    match sidecar_name {
+        "rclone" => rclone::register_endpoint(&sidecar_path, &args, rc_credentials),
+        "openlist" => relay::register_openlist(&args, &work_dir),
+        _ => {}
+    }
     println!("Sidecar {} spawned with PID: {}", sidecar_name, pid);
     
     // 获取 stdout 和 stderr 处理输出
@@ -754,7 +938,7 @@ struct RunSidecarOnceResult {
     stderr: String,
 }
 
-#[tauri::command]
+/// 运行一次 sidecar 并等待退出，仅供后端调用（前端的清理操作见 `rclone::cleanup`）
 async fn run_sidecar_once(
     app: tauri::AppHandle<Runtime>,
     name: String,
@@ -809,6 +993,16 @@ async fn run_sidecar_once(
         let _ = std::fs::create_dir_all(&work_dir);
     }
 
+    // `openlist admin set <密码>` 的密码不写入日志，也不返回给前端
+    let secret = args
+        .windows(3)
+        .find(|w| w[0] == "admin" && w[1] == "set" && !w[2].is_empty())
+        .map(|w| w[2].clone());
+    let mask = |text: &str| match &secret {
+        Some(secret) => text.replace(secret.as_str(), "******"),
+        None => text.to_string(),
+    };
+
     // sidecar 统一诊断日志
     let log_dir = work_dir.join("log");
     let _ = std::fs::create_dir_all(&log_dir);
@@ -823,7 +1017,7 @@ async fn run_sidecar_once(
             "\n=== run once {} ===\npath: {}\nargs: {}\n",
             sidecar_name,
             sidecar_path.display(),
-            args.join(" ")
+            mask(&args.join(" "))
         );
     }
 
@@ -851,8 +1045,8 @@ async fn run_sidecar_once(
         .map_err(|e| format!("Failed to wait sidecar {}: {}", sidecar_name, e))?;
 
     let code = output.status.code().unwrap_or(-1);
-    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
-    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    let stdout = mask(&String::from_utf8_lossy(&output.stdout));
+    let stderr = mask(&String::from_utf8_lossy(&output.stderr));
 
     if let Ok(mut f) = std::fs::OpenOptions::new()
         .create(true)
@@ -884,7 +1078,52 @@ async fn run_sidecar_once(
     Err(msg)
 }
 
+/// 把 OpenList 管理员密码重置为配置中的密码（`openlist admin set`），密码不经过 webview。
+/// 开启凭据轮换时改为设置新生成的密码，设置成功后再写入配置。数据目录由后端确定
+#[tauri::command]
+async fn reset_openlist_password(app: tauri::AppHandle<Runtime>) -> Result<(), String> {
+    let data_dir = resolve_data_dir()
+        .join("openlist")
+        .to_string_lossy()
+        .to_string();
+    let (password, rotate) = app.with_app_state::<Config, _>(|config| {
+        if config.rotate_credentials() {
+            (config.rotated_credentials("openlist").password, true)
+        } else {
+            (config.credentials("openlist").password, false)
+        }
+    });
+    let args = vec![
+        "--data".to_string(),
+        data_dir.clone(),
+        "admin".to_string(),
+        "set".to_string(),
+        password.clone(),
+    ];
+    run_sidecar_once(
+        app.clone(),
+        "binaries/openlist".to_string(),
+        args,
+        Some(15_000),
+        Some(data_dir),
+    )
+    .await?;
+
+    if rotate {
+        // OpenList 已使用新密码，旧 token 随之失效
+        app.modify_app_config(|config| config.set_password("openlist", password))
+            .map_err(|e| format!("Failed to save rotated OpenList password: {}", e))?;
+        relay::clear_openlist_token();
+    }
+    Ok(())
+}
+
 #[tauri::command]
 fn kill_sidecar(name: String) -> Result<bool, String> {
+    match name.as_str() {
+        "rclone" => rclone::clear_endpoint(),
+        "openlist" => relay::clear_openlist(),
+        _ => {}
+    }
     Ok(sidecar::kill_sidecar(&name))
 }
@@ -0,0 +1,481 @@
+//! Linux FUSE 挂载前的环境检查（类似 Windows 上的 WinFsp 检查）：
+//! `/dev/fuse`、`fusermount3`/`fusermount` 及其 setuid 位、内核模块，
+//! 以及请求 `AllowOther` 时 `/etc/fuse.conf` 中的 `user_allow_other`。
+//!
+//! 所有路径都从 [`FuseRoots`] 取得，可指向临时目录进行检查。
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// 检查时使用的根目录与 PATH
+#[derive(Clone, Debug)]
+pub struct FuseRoots {
+    pub dev: PathBuf,
+    pub etc: PathBuf,
+    pub proc: PathBuf,
+    pub sys: PathBuf,
+    /// 查找 fusermount 的 PATH，为空时不查找
+    pub path: Option<OsString>,
+}
+
+impl Default for FuseRoots {
+    fn default() -> Self {
+        Self {
+            dev: PathBuf::from("/dev"),
+            etc: PathBuf::from("/etc"),
+            proc: PathBuf::from("/proc"),
+            sys: PathBuf::from("/sys"),
+            path: std::env::var_os("PATH"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CheckStatus {
+    Ok,
+    /// 可能影响挂载，但不一定失败
+    Warning,
+    /// 挂载会失败
+    Error,
+    /// 不适用（如未请求 AllowOther）
+    Skipped,
+}
+
+/// 单项检查结果
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FuseCheck {
+    /// `device` / `kernelModule` / `fusermount` / `allowOther`
+    pub id: String,
+    pub status: CheckStatus,
+    pub message: String,
+    /// 修复建议
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hint: Option<String>,
+}
+
+/// FUSE 环境检查结果
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FuseReadiness {
+    /// 没有任何 Error 级别的检查项
+    pub ready: bool,
+    /// 找到的 fusermount 路径
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fusermount: Option<PathBuf>,
+    pub checks: Vec<FuseCheck>,
+}
+
+fn check(id: &str, status: CheckStatus, message: String, hint: Option<&str>) -> FuseCheck {
+    FuseCheck {
+        id: id.to_string(),
+        status,
+        message,
+        hint: hint.map(|h| h.to_string()),
+    }
+}
+
+/// 当前进程的有效 UID、GID 与附加组（读取 `<proc>/self/status`）
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ProcessIds {
+    pub uid: u32,
+    pub gid: u32,
+    pub groups: Vec<u32>,
+}
+
+pub(crate) fn process_ids(proc: &Path) -> Option<ProcessIds> {
+    let status = std::fs::read_to_string(proc.join("self/status")).ok()?;
+    // `Uid:`/`Gid:` 依次为实际、有效、保存、文件系统 ID
+    let effective = |key: &str| {
+        status
+            .lines()
+            .find_map(|line| line.strip_prefix(key))
+            .and_then(|ids| ids.split_whitespace().nth(1)?.parse::<u32>().ok())
+    };
+    Some(ProcessIds {
+        uid: effective("Uid:")?,
+        gid: effective("Gid:")?,
+        groups: status
+            .lines()
+            .find_map(|line| line.strip_prefix("Groups:"))
+            .map(|ids| {
+                ids.split_whitespace()
+                    .filter_map(|id| id.parse().ok())
+                    .collect()
+            })
+            .unwrap_or_default(),
+    })
+}
+
+/// 当前进程是否以 root 运行
+fn is_root(roots: &FuseRoots) -> bool {
+    process_ids(&roots.proc).is_some_and(|ids| ids.uid == 0)
+}
+
+fn check_device(roots: &FuseRoots) -> FuseCheck {
+    let device = roots.dev.join("fuse");
+    if !device.exists() {
+        return check(
+            "device",
+            CheckStatus::Error,
+            format!("{} does not exist", device.display()),
+            Some("Load the FUSE kernel module with `sudo modprobe fuse`; in containers, pass the device with `--device /dev/fuse` and add `--cap-add SYS_ADMIN`"),
+        );
+    }
+    match std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(&device)
+    {
+        Ok(_) => check(
+            "device",
+            CheckStatus::Ok,
+            format!("{} is accessible", device.display()),
+            None,
+        ),
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => check(
+            "device",
+            CheckStatus::Error,
+            format!("No permission to open {}", device.display()),
+            Some("Add your user to the `fuse` group (`sudo usermod -aG fuse $USER`) and log in again, or make the device world read-writable (`sudo chmod 666 /dev/fuse`)"),
+        ),
+        Err(e) => check(
+            "device",
+            CheckStatus::Warning,
+            format!("Failed to open {}: {}", device.display(), e),
+            None,
+        ),
+    }
+}
+
+fn check_kernel_module(roots: &FuseRoots) -> FuseCheck {
+    let in_filesystems = std::fs::read_to_string(roots.proc.join("filesystems"))
+        .map(|content| {
+            content
+                .lines()
+                .any(|line| line.split_whitespace().last() == Some("fuse"))
+        })
+        .unwrap_or(false);
+    if in_filesystems {
+        return check(
+            "kernelModule",
+            CheckStatus::Ok,
+            "fuse is registered in /proc/filesystems".to_string(),
+            None,
+        );
+    }
+    if roots.sys.join("module/fuse").exists() {
+        return check(
+            "kernelModule",
+            CheckStatus::Ok,
+            "fuse module is loaded".to_string(),
+            None,
+        );
+    }
+    // /dev/fuse 存在时打开设备通常会自动加载模块
+    let status = if roots.dev.join("fuse").exists() {
+        CheckStatus::Warning
+    } else {
+        CheckStatus::Error
+    };
+    check(
+        "kernelModule",
+        status,
+        "The fuse kernel module is not loaded".to_string(),
+        Some("Run `sudo modprobe fuse`, and add `fuse` to /etc/modules-load.d/ to load it at boot"),
+    )
+}
+
+/// 在 PATH 中查找 fusermount，优先 fusermount3
+fn find_fusermount(roots: &FuseRoots) -> Option<PathBuf> {
+    let path = roots.path.as_ref()?;
+    ["fusermount3", "fusermount"].iter().find_map(|bin| {
+        std::env::split_paths(path)
+            .map(|dir| dir.join(bin))
+            .find(|candidate| candidate.is_file())
+    })
+}
+
+fn check_fusermount(roots: &FuseRoots, fusermount: Option<&Path>) -> FuseCheck {
+    let Some(bin) = fusermount else {
+        return check(
+            "fusermount",
+            CheckStatus::Error,
+            "Neither fusermount3 nor fusermount was found in PATH".to_string(),
+            Some("Install the FUSE userspace tools: `sudo apt install fuse3` (Debian/Ubuntu), `sudo dnf install fuse3` (Fedora) or `sudo pacman -S fuse3` (Arch)"),
+        );
+    };
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        let Ok(metadata) = std::fs::metadata(bin) else {
+            return check(
+                "fusermount",
+                CheckStatus::Error,
+                format!("Failed to read {}", bin.display()),
+                None,
+            );
+        };
+        let mode = metadata.mode();
+        if mode & 0o111 == 0 {
+            return check(
+                "fusermount",
+                CheckStatus::Error,
+                format!("{} is not executable", bin.display()),
+                Some("Reinstall the fuse3 package"),
+            );
+        }
+        // 非 root 用户挂载需要 setuid root 的 fusermount
+        if (mode & 0o4000 == 0 || metadata.uid() != 0) && !is_root(roots) {
+            return check(
+                "fusermount",
+                CheckStatus::Error,
+                format!("{} is not setuid root", bin.display()),
+                Some(&format!(
+                    "Restore the setuid bit with `sudo chown root {0} && sudo chmod u+s {0}`, or reinstall the fuse3 package",
+                    bin.display()
+                )),
+            );
+        }
+    }
+    check(
+        "fusermount",
+        CheckStatus::Ok,
+        format!("Found {}", bin.display()),
+        None,
+    )
+}
+
+/// `fuse.conf` 中是否启用了 `user_allow_other`
+fn user_allow_other_enabled(content: &str) -> bool {
+    content.lines().any(|line| {
+        let line = line.split('#').next().unwrap_or("").trim();
+        line == "user_allow_other"
+    })
+}
+
+fn check_allow_other(roots: &FuseRoots, allow_other: bool) -> FuseCheck {
+    if !allow_other {
+        return check(
+            "allowOther",
+            CheckStatus::Skipped,
+            "AllowOther is not requested".to_string(),
+            None,
+        );
+    }
+    if is_root(roots) {
+        return check(
+            "allowOther",
+            CheckStatus::Ok,
+            "Running as root, user_allow_other is not required".to_string(),
+            None,
+        );
+    }
+    let conf = roots.etc.join("fuse.conf");
+    let enabled = std::fs::read_to_string(&conf)
+        .map(|content| user_allow_other_enabled(&content))
+        .unwrap_or(false);
+    if enabled {
+        check(
+            "allowOther",
+            CheckStatus::Ok,
+            format!("user_allow_other is enabled in {}", conf.display()),
+            None,
+        )
+    } else {
+        check(
+            "allowOther",
+            CheckStatus::Error,
+            format!("user_allow_other is not enabled in {}", conf.display()),
+            Some("Uncomment or add `user_allow_other` in /etc/fuse.conf (requires root), or turn off AllowOther for this mount"),
+        )
+    }
+}
+
+/// 按给定根目录检查 FUSE 环境
+pub fn check_with(roots: &FuseRoots, allow_other: bool) -> FuseReadiness {
+    let fusermount = find_fusermount(roots);
+    let checks = vec![
+        check_device(roots),
+        check_kernel_module(roots),
+        check_fusermount(roots, fusermount.as_deref()),
+        check_allow_other(roots, allow_other),
+    ];
+    FuseReadiness {
+        ready: checks.iter().all(|c| c.status != CheckStatus::Error),
+        fusermount,
+        checks,
+    }
+}
+
+/// 检查当前系统的 FUSE 环境
+pub fn check_system(allow_other: bool) -> FuseReadiness {
+    check_with(&FuseRoots::default(), allow_other)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+    /// 临时的 dev/etc/proc/sys/bin 目录，离开作用域时删除
+    struct TempRoots {
+        base: PathBuf,
+        roots: FuseRoots,
+    }
+
+    impl TempRoots {
+        fn new(name: &str) -> Self {
+            let base =
+                std::env::temp_dir().join(format!("netmount-fuse-{}-{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&base);
+            for dir in ["dev", "etc", "proc/self", "sys/module", "bin"] {
+                fs::create_dir_all(base.join(dir)).unwrap();
+            }
+            let roots = FuseRoots {
+                dev: base.join("dev"),
+                etc: base.join("etc"),
+                proc: base.join("proc"),
+                sys: base.join("sys"),
+                path: Some(base.join("bin").into_os_string()),
+            };
+            Self { base, roots }
+        }
+
+        fn write(&self, rel: &str, content: &str) -> PathBuf {
+            let path = self.base.join(rel);
+            fs::write(&path, content).unwrap();
+            path
+        }
+
+        fn uid(&self, uid: u32) {
+            self.write(
+                "proc/self/status",
+                &format!("Name:\tnetmount\nUid:\t{0}\t{0}\t{0}\t{0}\nGid:\t100\t100\t100\t100\nGroups:\t100 27\n", uid),
+            );
+        }
+
+        fn fusermount(&self, name: &str, mode: u32) -> PathBuf {
+            let path = self.write(&format!("bin/{}", name), "");
+            fs::set_permissions(&path, fs::Permissions::from_mode(mode)).unwrap();
+            path
+        }
+
+        fn status(&self, id: &str, allow_other: bool) -> CheckStatus {
+            check_with(&self.roots, allow_other)
+                .checks
+                .into_iter()
+                .find(|c| c.id == id)
+                .unwrap()
+                .status
+        }
+    }
+
+    impl Drop for TempRoots {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.base);
+        }
+    }
+
+    #[test]
+    fn missing_device_is_an_error() {
+        let t = TempRoots::new("device");
+        t.uid(1000);
+        let readiness = check_with(&t.roots, false);
+        assert!(!readiness.ready);
+        assert_eq!(t.status("device", false), CheckStatus::Error);
+        // 没有 /dev/fuse 也没有加载模块
+        assert_eq!(t.status("kernelModule", false), CheckStatus::Error);
+
+        t.write("dev/fuse", "");
+        assert_eq!(t.status("device", false), CheckStatus::Ok);
+        assert_eq!(t.status("kernelModule", false), CheckStatus::Warning);
+    }
+
+    #[test]
+    fn kernel_module_from_filesystems_or_sysfs() {
+        let t = TempRoots::new("module");
+        t.write("proc/filesystems", "nodev\tsysfs\nnodev\tfuseblk\n\text4\n");
+        assert_eq!(t.status("kernelModule", false), CheckStatus::Error);
+
+        t.write("proc/filesystems", "nodev\tsysfs\nnodev\tfuse\n\text4\n");
+        assert_eq!(t.status("kernelModule", false), CheckStatus::Ok);
+
+        fs::remove_file(t.base.join("proc/filesystems")).unwrap();
+        fs::create_dir_all(t.base.join("sys/module/fuse")).unwrap();
+        assert_eq!(t.status("kernelModule", false), CheckStatus::Ok);
+    }
+
+    #[test]
+    fn fusermount_lookup_and_setuid() {
+        let t = TempRoots::new("fusermount");
+        t.uid(1000);
+        assert_eq!(t.status("fusermount", false), CheckStatus::Error);
+        assert!(check_with(&t.roots, false).fusermount.is_none());
+
+        let legacy = t.fusermount("fusermount", 0o755);
+        assert_eq!(check_with(&t.roots, false).fusermount, Some(legacy));
+        // 普通用户需要 setuid root 的 fusermount
+        assert_eq!(t.status("fusermount", false), CheckStatus::Error);
+
+        let fuse3 = t.fusermount("fusermount3", 0o4755);
+        assert_eq!(check_with(&t.roots, false).fusermount, Some(fuse3.clone()));
+        let expected = if fs::metadata(&fuse3).unwrap().uid() == 0 {
+            CheckStatus::Ok
+        } else {
+            // 测试进程不是 root 时无法创建 root 拥有的文件
+            CheckStatus::Error
+        };
+        assert_eq!(t.status("fusermount", false), expected);
+
+        t.fusermount("fusermount3", 0o644);
+        assert_eq!(t.status("fusermount", false), CheckStatus::Error);
+
+        // root 不需要 setuid
+        t.fusermount("fusermount3", 0o755);
+        t.uid(0);
+        assert_eq!(t.status("fusermount", false), CheckStatus::Ok);
+    }
+
+    #[test]
+    fn allow_other_requires_user_allow_other() {
+        let t = TempRoots::new("allow-other");
+        t.uid(1000);
+        assert_eq!(t.status("allowOther", false), CheckStatus::Skipped);
+        assert_eq!(t.status("allowOther", true), CheckStatus::Error);
+
+        t.write("etc/fuse.conf", "# user_allow_other\nmount_max = 1000\n");
+        assert_eq!(t.status("allowOther", true), CheckStatus::Error);
+
+        t.write(
+            "etc/fuse.conf",
+            "mount_max = 1000\n  user_allow_other  # enabled\n",
+        );
+        assert_eq!(t.status("allowOther", true), CheckStatus::Ok);
+
+        fs::remove_file(t.base.join("etc/fuse.conf")).unwrap();
+        t.uid(0);
+        assert_eq!(t.status("allowOther", true), CheckStatus::Ok);
+    }
+
+    #[test]
+    fn process_ids_use_effective_ids() {
+        let t = TempRoots::new("ids");
+        t.write(
+            "proc/self/status",
+            "Uid:\t1000\t0\t0\t0\nGid:\t100\t27\t27\t27\nGroups:\t4 24 100\n",
+        );
+        let ids = process_ids(&t.roots.proc).unwrap();
+        assert_eq!((ids.uid, ids.gid), (0, 27));
+        assert_eq!(ids.groups, [4, 24, 100]);
+        assert!(is_root(&t.roots));
+
+        fs::remove_file(t.base.join("proc/self/status")).unwrap();
+        assert!(process_ids(&t.roots.proc).is_none());
+        assert!(!is_root(&t.roots));
+    }
+}
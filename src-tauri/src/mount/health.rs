@@ -0,0 +1,280 @@
+//! 挂载健康监测：定期检查各挂载点，rclone 重启或崩溃后残留的 FUSE 挂载
+//! （"Transport endpoint is not connected"）会被惰性卸载并按保存的参数重新挂载。
+//!
+//! 同时核对 `/proc/self/mountinfo` 与 rclone 的 `mount/listmounts`：
+//! 内核中有 rclone 挂载而 rclone 不知道，或 rclone 认为已挂载而内核中没有，都视为失效。
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use tauri::Emitter;
+
+use super::mountinfo::{self, MountInfoEntry};
+use super::{list_active, load_mounts, local_path, rc_mount_point, ActiveMount, MountSpec};
+use crate::config::Config;
+use crate::task::now_ms;
+use crate::{rclone, AppExt, Runtime};
+
+/// 默认检查间隔（秒）
+const DEFAULT_INTERVAL_SECS: u64 = 30;
+/// 默认连续重新挂载的最大次数
+const DEFAULT_MAX_RETRIES: u32 = 3;
+/// stat 挂载点的超时，FUSE 无响应时 stat 可能一直阻塞
+const STAT_TIMEOUT: Duration = Duration::from_secs(5);
+/// ENOTCONN：Transport endpoint is not connected
+const ENOTCONN: i32 = 107;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HealthState {
+    Healthy,
+    /// stat 超时，可能只是网络较慢，不做处理
+    Unresponsive,
+    /// 已失效，正在恢复
+    Stale,
+    /// 已重新挂载，等待下次检查确认
+    Remounted,
+    /// 重新挂载次数用尽
+    Failed,
+}
+
+/// 单个挂载点的健康状态
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MountHealth {
+    pub mount_path: String,
+    pub storage_name: String,
+    pub state: HealthState,
+    /// 连续重新挂载次数，检查正常后清零
+    pub retries: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    pub checked_at: u64,
+}
+
+/// `settings.mountHealth`
+#[derive(Clone, Debug)]
+pub struct MonitorSettings {
+    pub enabled: bool,
+    pub interval_secs: u64,
+    pub max_retries: u32,
+}
+
+impl MonitorSettings {
+    pub fn from_settings(settings: &serde_json::Value) -> Self {
+        let v = &settings["mountHealth"];
+        Self {
+            enabled: v["enabled"].as_bool().unwrap_or(true),
+            interval_secs: v["intervalSecs"]
+                .as_u64()
+                .unwrap_or(DEFAULT_INTERVAL_SECS)
+                .max(5),
+            max_retries: v["maxRetries"]
+                .as_u64()
+                .map(|n| n as u32)
+                .unwrap_or(DEFAULT_MAX_RETRIES),
+        }
+    }
+}
+
+lazy_static! {
+    static ref HEALTH: Mutex<HashMap<String, MountHealth>> = Mutex::new(HashMap::new());
+}
+
+pub(crate) enum Probe {
+    Ok,
+    NotConnected,
+    Error(String),
+    Timeout,
+}
+
+/// 在阻塞线程中 stat 挂载点，避免无响应的 FUSE 卡住异步运行时
+pub(crate) async fn probe(path: PathBuf) -> Probe {
+    let task = tokio::task::spawn_blocking(move || std::fs::metadata(&path));
+    match tokio::time::timeout(STAT_TIMEOUT, task).await {
+        Err(_) => Probe::Timeout,
+        Ok(Err(e)) => Probe::Error(e.to_string()),
+        Ok(Ok(Ok(_))) => Probe::Ok,
+        Ok(Ok(Err(e))) if e.raw_os_error() == Some(ENOTCONN) => Probe::NotConnected,
+        // 没有权限访问也说明文件系统仍在响应
+        Ok(Ok(Err(e))) if e.kind() == std::io::ErrorKind::PermissionDenied => Probe::Ok,
+        Ok(Ok(Err(e))) => Probe::Error(e.to_string()),
+    }
+}
+
+fn same_mount_point(active: &ActiveMount, spec: &MountSpec, local: &Path) -> bool {
+    active.mount_point == rc_mount_point(&spec.mount_path)
+        || active.mount_point == spec.mount_path
+        || Path::new(&active.mount_point) == local
+}
+
+/// 惰性卸载内核残留并让 rclone 重新挂载
+async fn recover(
+    spec: &MountSpec,
+    local: &Path,
+    in_kernel: bool,
+    in_rclone: bool,
+) -> anyhow::Result<()> {
+    if in_rclone {
+        // 清理 rclone 中的失效记录，失败不影响后续步骤
+        let _ = super::unmount(&spec.mount_path).await;
+    }
+    #[cfg(target_os = "linux")]
+    if in_kernel {
+        super::lazy_unmount(local)?;
+    }
+    #[cfg(not(target_os = "linux"))]
+    let _ = (local, in_kernel);
+    super::mount(spec).await
+}
+
+fn publish(app: &tauri::AppHandle<Runtime>, health: MountHealth) {
+    let changed = {
+        let mut map = HEALTH.lock().unwrap();
+        let prev = map.insert(health.mount_path.clone(), health.clone());
+        prev.is_none_or(|p| p.state != health.state || p.retries != health.retries)
+    };
+    if changed {
+        let _ = app.emit("mount-health", health);
+    }
+}
+
+/// 检查全部挂载点并尝试恢复失效的挂载，返回检查后的状态
+pub async fn check_once(app: &tauri::AppHandle<Runtime>) -> anyhow::Result<Vec<MountHealth>> {
+    // 退出流程会卸载全部挂载，此时不能再重新挂载
+    if rclone::endpoint().is_none() || super::shutdown::exiting() {
+        return Ok(snapshot());
+    }
+    let settings = app.with_app_state::<Config, _>(|config| {
+        MonitorSettings::from_settings(&config.0["settings"])
+    });
+    let active = list_active().await?;
+    let kernel: Vec<MountInfoEntry> = mountinfo::read();
+    let mounts = load_mounts(app);
+
+    // 配置中已删除的挂载不再跟踪
+    HEALTH
+        .lock()
+        .unwrap()
+        .retain(|path, _| mounts.iter().any(|m| &m.mount_path == path));
+
+    for spec in &mounts {
+        let local = local_path(app, &spec.mount_path);
+        let in_rclone = active.iter().any(|a| same_mount_point(a, spec, &local));
+        let kernel_entry = mountinfo::find(&kernel, &local);
+        // 只处理 rclone 的 FUSE 挂载，不动同一路径上的其它文件系统
+        let in_kernel = kernel_entry.is_some_and(|e| e.is_rclone());
+        if !in_rclone && !in_kernel {
+            // 未挂载（用户卸载或尚未挂载）
+            HEALTH.lock().unwrap().remove(&spec.mount_path);
+            continue;
+        }
+
+        let prev = HEALTH.lock().unwrap().get(&spec.mount_path).cloned();
+        let retries = prev.as_ref().map(|p| p.retries).unwrap_or(0);
+        let mut health = MountHealth {
+            mount_path: spec.mount_path.clone(),
+            storage_name: spec.storage_name.clone(),
+            state: HealthState::Healthy,
+            retries,
+            last_error: None,
+            checked_at: now_ms(),
+        };
+
+        // 非 Linux 系统没有 mountinfo，只根据 stat 判断
+        let has_mountinfo = !kernel.is_empty();
+        let probe = probe(local.clone()).await;
+        let stale_reason = match probe {
+            Probe::Timeout => {
+                health.state = HealthState::Unresponsive;
+                publish(app, health);
+                continue;
+            }
+            Probe::NotConnected => Some("Transport endpoint is not connected".to_string()),
+            _ if has_mountinfo && in_kernel && !in_rclone => {
+                Some("Mount is not managed by the running rclone".to_string())
+            }
+            // nfsmount 等非 FUSE 后端的类型不是 fuse.rclone，只要路径上有挂载即可
+            _ if has_mountinfo && in_rclone && kernel_entry.is_none() => {
+                Some("rclone reports the mount but the kernel does not".to_string())
+            }
+            Probe::Error(e) if in_rclone => Some(e),
+            _ => None,
+        };
+
+        let Some(reason) = stale_reason else {
+            health.retries = 0;
+            publish(app, health);
+            continue;
+        };
+        if retries >= settings.max_retries {
+            health.state = HealthState::Failed;
+            health.last_error = prev.and_then(|p| p.last_error).or(Some(reason));
+            publish(app, health);
+            continue;
+        }
+
+        health.state = HealthState::Stale;
+        health.last_error = Some(reason);
+        publish(app, health.clone());
+        // 检查期间开始退出时放弃恢复
+        if super::shutdown::exiting() {
+            break;
+        }
+
+        health.retries = retries + 1;
+        match recover(spec, &local, in_kernel, in_rclone).await {
+            Ok(()) => health.state = HealthState::Remounted,
+            Err(e) => {
+                health.state = if health.retries >= settings.max_retries {
+                    HealthState::Failed
+                } else {
+                    HealthState::Stale
+                };
+                health.last_error = Some(e.to_string());
+            }
+        }
+        health.checked_at = now_ms();
+        publish(app, health);
+    }
+    Ok(snapshot())
+}
+
+pub fn snapshot() -> Vec<MountHealth> {
+    let mut list: Vec<MountHealth> = HEALTH.lock().unwrap().values().cloned().collect();
+    list.sort_by(|a, b| a.mount_path.cmp(&b.mount_path));
+    list
+}
+
+/// 清零重试次数，下次检查时重新尝试恢复
+pub fn reset(mount_path: &str) -> bool {
+    match HEALTH.lock().unwrap().get_mut(mount_path) {
+        Some(health) => {
+            health.retries = 0;
+            true
+        }
+        None => false,
+    }
+}
+
+/// 启动后台监测
+pub fn spawn_monitor(app: tauri::AppHandle<Runtime>) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let settings = app.with_app_state::<Config, _>(|config| {
+                MonitorSettings::from_settings(&config.0["settings"])
+            });
+            tokio::time::sleep(Duration::from_secs(settings.interval_secs)).await;
+            if !settings.enabled {
+                continue;
+            }
+            if let Err(e) = check_once(&app).await {
+                eprintln!("Mount health check failed: {}", e);
+            }
+        }
+    });
+}
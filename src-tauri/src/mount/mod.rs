@@ -0,0 +1,251 @@
+//! 后端挂载支持：读取 `config.json` 中的 `mount.lists`，通过 rclone rc 查询、执行挂载与卸载，
+//! 并核对内核挂载表中的实际状态。
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::config::Config;
+use crate::rclone::{self, StorageRef};
+use crate::{AppExt, Runtime};
+
+pub mod fuse;
+pub mod health;
+pub mod mountinfo;
+pub mod ready;
+pub mod shutdown;
+pub mod suggest;
+pub mod validate;
+
+/// `mount.lists` 的一项（与前端 `MountListItem` 对应）
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MountSpec {
+    pub storage_name: String,
+    pub mount_path: String,
+    /// `{ vfsOpt, mountOpt }`
+    #[serde(default)]
+    pub parameters: Value,
+    #[serde(default)]
+    pub auto_mount: bool,
+}
+
+/// rclone `mount/listmounts` 中的一项
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActiveMount {
+    pub fs: String,
+    pub mount_point: String,
+}
+
+/// 从当前配置中读取全部挂载
+pub(crate) fn load_mounts(app: &tauri::AppHandle<Runtime>) -> Vec<MountSpec> {
+    app.with_app_state::<Config, _>(|config| {
+        config.0["mount"]["lists"]
+            .as_array()
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|v| serde_json::from_value::<MountSpec>(v.clone()).ok())
+                    .collect()
+            })
+            .unwrap_or_default()
+    })
+}
+
+/// 挂载路径对应的本地路径（展开 `~`）
+pub(crate) fn local_path(app: &tauri::AppHandle<Runtime>, mount_path: &str) -> PathBuf {
+    crate::fs::resolve_path(app, mount_path).unwrap_or_else(|_| PathBuf::from(mount_path))
+}
+
+/// 传给 rclone 的挂载点，格式与前端 `performMount` 一致
+pub(crate) fn rc_mount_point(mount_path: &str) -> String {
+    #[cfg(target_os = "windows")]
+    {
+        let path = mount_path.replace('/', "\\");
+        // 盘符挂载（如 X:）需写成 X:\
+        if path.len() == 2 && path.ends_with(':') {
+            return format!("{}\\", path);
+        }
+        path
+    }
+    #[cfg(not(target_os = "windows"))]
+    {
+        mount_path.to_string()
+    }
+}
+
+/// 存储对应的 rclone fs 字符串
+pub(crate) async fn storage_fs(storage_name: &str) -> anyhow::Result<String> {
+    let remotes = rclone::list_remotes().await?;
+    Ok(StorageRef::resolve(&remotes, storage_name).fs(""))
+}
+
+/// rclone 当前管理的挂载
+pub(crate) async fn list_active() -> anyhow::Result<Vec<ActiveMount>> {
+    let res = rclone::rc_post("mount/listmounts", json!({})).await?;
+    Ok(res
+        .get("mountPoints")
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|m| {
+                    Some(ActiveMount {
+                        fs: m.get("Fs").or_else(|| m.get("fs"))?.as_str()?.to_string(),
+                        mount_point: m
+                            .get("MountPoint")
+                            .or_else(|| m.get("mountPoint"))?
+                            .as_str()?
+                            .to_string(),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+/// 按保存的参数挂载
+pub(crate) async fn mount(spec: &MountSpec) -> anyhow::Result<()> {
+    let mut body = json!({
+        "fs": storage_fs(&spec.storage_name).await?,
+        "mountPoint": rc_mount_point(&spec.mount_path),
+    });
+    if let Some(params) = spec.parameters.as_object() {
+        for (key, value) in params {
+            body[key] = value.clone();
+        }
+    }
+    rclone::rc_post("mount/mount", body).await?;
+    Ok(())
+}
+
+/// 让 rclone 卸载，失败时返回 rclone 的错误
+pub(crate) async fn unmount(mount_path: &str) -> anyhow::Result<()> {
+    rclone::rc_post(
+        "mount/unmount",
+        json!({ "mountPoint": rc_mount_point(mount_path) }),
+    )
+    .await?;
+    Ok(())
+}
+
+/// 查看挂载健康状态
+#[tauri::command]
+pub fn get_mount_health() -> anyhow_tauri::TAResult<Vec<health::MountHealth>> {
+    Ok(health::snapshot())
+}
+
+/// 立即检查全部挂载点并恢复失效的挂载
+#[tauri::command]
+pub async fn check_mount_health(
+    app: tauri::AppHandle<Runtime>,
+) -> anyhow_tauri::TAResult<Vec<health::MountHealth>> {
+    Ok(health::check_once(&app).await?)
+}
+
+/// 清零重新挂载次数，允许监测再次尝试恢复
+#[tauri::command]
+pub fn reset_mount_health(mount_path: String) -> anyhow_tauri::TAResult<bool> {
+    Ok(health::reset(&mount_path))
+}
+
+/// Linux 上检查 FUSE 挂载环境，其它系统直接视为就绪
+#[tauri::command]
+pub fn get_fuse_readiness(
+    allow_other: Option<bool>,
+) -> anyhow_tauri::TAResult<fuse::FuseReadiness> {
+    #[cfg(target_os = "linux")]
+    return Ok(fuse::check_system(allow_other.unwrap_or(false)));
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = allow_other;
+        Ok(fuse::FuseReadiness {
+            ready: true,
+            fusermount: None,
+            checks: Vec::new(),
+        })
+    }
+}
+
+/// 为存储推荐空闲的挂载目录（非 Windows 系统对应自动分配盘符）
+#[tauri::command]
+pub fn suggest_mount_path(
+    app: tauri::AppHandle<Runtime>,
+    storage_name: String,
+    create: Option<bool>,
+) -> anyhow_tauri::TAResult<suggest::MountPathSuggestion> {
+    Ok(suggest::suggest(
+        &app,
+        &storage_name,
+        create.unwrap_or(false),
+    )?)
+}
+
+/// 挂载前检查挂载点，可选创建目录
+#[tauri::command]
+pub fn validate_mount_point(
+    app: tauri::AppHandle<Runtime>,
+    path: String,
+    opts: Option<validate::ValidateOptions>,
+) -> anyhow_tauri::TAResult<validate::MountPointCheck> {
+    if path.trim().is_empty() {
+        return Err(anyhow::anyhow!("Mount path is empty").into());
+    }
+    Ok(validate::validate_for_app(
+        &app,
+        &path,
+        &opts.unwrap_or_default(),
+    ))
+}
+
+/// 挂载并等待文件系统可访问，返回挂载耗时
+#[tauri::command]
+pub async fn mount_and_wait(
+    app: tauri::AppHandle<Runtime>,
+    mount: MountSpec,
+    timeout_ms: Option<u64>,
+) -> anyhow_tauri::TAResult<ready::MountReady> {
+    let timeout = timeout_ms
+        .map(std::time::Duration::from_millis)
+        .unwrap_or(ready::DEFAULT_READY_TIMEOUT);
+    Ok(ready::mount_and_wait(&app, &mount, timeout).await?)
+}
+
+/// 等待写回缓存上传完成后卸载全部挂载（退出或重启前调用）
+#[tauri::command]
+pub async fn unmount_all(
+    app: tauri::AppHandle<Runtime>,
+) -> anyhow_tauri::TAResult<shutdown::UnmountReport> {
+    Ok(shutdown::unmount_all(&app).await?)
+}
+
+/// 惰性卸载内核中残留的 FUSE 挂载（`fusermount -uz`），优先使用 fusermount3
+#[cfg(target_os = "linux")]
+pub(crate) fn lazy_unmount(path: &std::path::Path) -> anyhow::Result<()> {
+    let mut last_error = String::new();
+    for bin in ["fusermount3", "fusermount"] {
+        match std::process::Command::new(bin)
+            .arg("-uz")
+            .arg(path)
+            .output()
+        {
+            Ok(output) if output.status.success() => return Ok(()),
+            Ok(output) => {
+                last_error = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            }
+            // 未安装该版本的 fusermount，尝试下一个
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => last_error = e.to_string(),
+        }
+    }
+    Err(anyhow::anyhow!(
+        "Failed to unmount {}: {}",
+        path.display(),
+        if last_error.is_empty() {
+            "fusermount not found"
+        } else {
+            &last_error
+        }
+    ))
+}
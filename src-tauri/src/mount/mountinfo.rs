@@ -0,0 +1,159 @@
+//! 解析 `/proc/self/mountinfo`，用于核对内核中实际存在的挂载点。
+
+use std::path::{Path, PathBuf};
+
+/// mountinfo 中的一行
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MountInfoEntry {
+    pub mount_id: u32,
+    pub parent_id: u32,
+    /// 挂载源在其文件系统中的根
+    pub root: String,
+    pub mount_point: PathBuf,
+    pub options: String,
+    /// 文件系统类型，rclone 的 FUSE 挂载为 `fuse.rclone`
+    pub fs_type: String,
+    /// 挂载源，rclone 挂载为 fs 字符串（如 `remote:path`）
+    pub source: String,
+}
+
+impl MountInfoEntry {
+    /// 是否为 FUSE 挂载
+    pub fn is_fuse(&self) -> bool {
+        self.fs_type == "fuse" || self.fs_type.starts_with("fuse.")
+    }
+
+    /// 是否为 rclone 的 FUSE 挂载
+    pub fn is_rclone(&self) -> bool {
+        self.fs_type == "fuse.rclone"
+    }
+}
+
+/// 还原 mountinfo 中的八进制转义（空格为 `\040`、换行为 `\012` 等）
+fn unescape(field: &str) -> String {
+    let bytes = field.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'\\' {
+            if let Some(digits) = bytes.get(i + 1..i + 4) {
+                if digits.iter().all(|b| (b'0'..=b'7').contains(b)) {
+                    let v = digits
+                        .iter()
+                        .fold(0u32, |acc, b| acc * 8 + u32::from(b - b'0'));
+                    if let Ok(v) = u8::try_from(v) {
+                        out.push(v);
+                        i += 4;
+                        continue;
+                    }
+                }
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).to_string()
+}
+
+/// 解析 mountinfo 内容，无法识别的行忽略
+pub fn parse(content: &str) -> Vec<MountInfoEntry> {
+    content
+        .lines()
+        .filter_map(|line| {
+            // 可选字段数量不定，以单独的 `-` 分隔前后两部分
+            let (head, tail) = line.split_once(" - ")?;
+            let head: Vec<&str> = head.split(' ').collect();
+            let tail: Vec<&str> = tail.split(' ').collect();
+            if head.len() < 6 || tail.len() < 2 {
+                return None;
+            }
+            Some(MountInfoEntry {
+                mount_id: head[0].parse().ok()?,
+                parent_id: head[1].parse().ok()?,
+                root: unescape(head[3]),
+                mount_point: PathBuf::from(unescape(head[4])),
+                options: head[5].to_string(),
+                fs_type: unescape(tail[0]),
+                source: unescape(tail[1]),
+            })
+        })
+        .collect()
+}
+
+/// 读取当前进程的挂载表；非 Linux 系统或读取失败时为空
+pub fn read() -> Vec<MountInfoEntry> {
+    read_from(Path::new("/proc/self/mountinfo"))
+}
+
+pub fn read_from(path: &Path) -> Vec<MountInfoEntry> {
+    std::fs::read_to_string(path)
+        .map(|c| parse(&c))
+        .unwrap_or_default()
+}
+
+/// 查找挂载在 `path` 上的条目（同一路径多次挂载时取最上层，即最后一条）
+pub fn find<'a>(entries: &'a [MountInfoEntry], path: &Path) -> Option<&'a MountInfoEntry> {
+    entries.iter().rev().find(|e| e.mount_point == path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+22 1 8:1 / / rw,relatime shared:1 - ext4 /dev/sda1 rw
+36 22 0:32 / /home/me/My\\040Drive rw,nosuid,nodev,relatime shared:20 - fuse.rclone gdrive:docs\\040old rw,user_id=1000
+37 22 0:33 / /mnt/sshfs rw,relatime - fuse.sshfs me@host:/srv rw
+38 36 0:34 / /home/me/My\\040Drive rw,relatime shared:21 master:3 - fuse.rclone s3:bucket rw
+broken line without separator
+x 22 0:35 / /mnt/bad rw - ext4 /dev/sdb1 rw
+";
+
+    #[test]
+    fn unescape_octal_sequences() {
+        assert_eq!(unescape("My\\040Drive"), "My Drive");
+        assert_eq!(unescape("a\\011b\\012c\\134d"), "a\tb\nc\\d");
+        // UTF-8 字节逐个转义
+        assert_eq!(unescape("\\346\\226\\207"), "文");
+        // 不完整或非八进制的转义原样保留
+        assert_eq!(unescape("a\\04"), "a\\04");
+        assert_eq!(unescape("a\\089"), "a\\089");
+        assert_eq!(unescape("\\777"), "\\777");
+    }
+
+    #[test]
+    fn parse_skips_unrecognized_lines() {
+        let entries = parse(SAMPLE);
+        assert_eq!(entries.len(), 4);
+        assert_eq!(
+            entries[1],
+            MountInfoEntry {
+                mount_id: 36,
+                parent_id: 22,
+                root: "/".to_string(),
+                mount_point: PathBuf::from("/home/me/My Drive"),
+                options: "rw,nosuid,nodev,relatime".to_string(),
+                fs_type: "fuse.rclone".to_string(),
+                source: "gdrive:docs old".to_string(),
+            }
+        );
+        // 可选字段数量不同也能解析
+        assert_eq!(entries[3].source, "s3:bucket");
+    }
+
+    #[test]
+    fn fuse_and_rclone_detection() {
+        let entries = parse(SAMPLE);
+        assert!(!entries[0].is_fuse());
+        assert!(entries[1].is_fuse() && entries[1].is_rclone());
+        assert!(entries[2].is_fuse() && !entries[2].is_rclone());
+    }
+
+    #[test]
+    fn find_returns_topmost_mount() {
+        let entries = parse(SAMPLE);
+        let found = find(&entries, Path::new("/home/me/My Drive")).unwrap();
+        assert_eq!(found.mount_id, 38);
+        assert!(find(&entries, Path::new("/home/me")).is_none());
+    }
+}
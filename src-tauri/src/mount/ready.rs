@@ -0,0 +1,143 @@
+//! 挂载就绪检测：`mount/mount` 返回时 FUSE 文件系统不一定已可访问，
+//! 轮询内核挂载表并 stat 挂载根目录，确认真正可用后再报告成功。
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use super::health::{probe, Probe};
+use super::{list_active, local_path, rc_mount_point, MountSpec};
+use crate::task::runlog;
+use crate::Runtime;
+
+/// 默认等待就绪的时间
+pub const DEFAULT_READY_TIMEOUT: Duration = Duration::from_secs(15);
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+/// 核对 rclone 是否仍在管理该挂载的间隔
+const LIST_INTERVAL: Duration = Duration::from_secs(1);
+/// 超时错误中最多附带的 rclone 日志行数
+const MAX_ERROR_LINES: usize = 20;
+
+/// 挂载就绪结果
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MountReady {
+    pub mount_path: String,
+    /// `mount/mount` 请求耗时
+    pub mount_ms: u64,
+    /// 从请求返回到文件系统可访问的耗时
+    pub ready_ms: u64,
+    /// 总耗时
+    pub latency_ms: u64,
+}
+
+/// 挂载点是否已由新的文件系统提供服务
+async fn is_served(path: &Path) -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        let kernel = super::mountinfo::read();
+        if super::mountinfo::find(&kernel, path).is_none() {
+            return false;
+        }
+    }
+    #[cfg(all(unix, not(target_os = "linux")))]
+    {
+        // 没有 mountinfo，比较挂载点与上级目录的设备号
+        use std::os::unix::fs::MetadataExt;
+        let parent = path.parent().unwrap_or(path);
+        match (std::fs::metadata(path), std::fs::metadata(parent)) {
+            (Ok(m), Ok(p)) if m.dev() != p.dev() => {}
+            _ => return false,
+        }
+    }
+    matches!(probe(path.to_path_buf()).await, Probe::Ok)
+}
+
+/// rclone.log 中与本次挂载相关的错误
+fn rclone_errors(log_offset: Option<u64>, mount_path: &str) -> String {
+    let Some(content) = log_offset.and_then(|start| runlog::rclone_log_since(start).ok()) else {
+        return String::new();
+    };
+    let lines: Vec<&str> = content
+        .lines()
+        .filter(|line| {
+            line.contains("ERROR") || line.contains("CRITICAL") || line.contains(mount_path)
+        })
+        .collect();
+    lines[lines.len().saturating_sub(MAX_ERROR_LINES)..].join("\n")
+}
+
+fn not_ready_error(message: String, log_offset: Option<u64>, mount_path: &str) -> anyhow::Error {
+    let errors = rclone_errors(log_offset, mount_path);
+    if errors.is_empty() {
+        anyhow::anyhow!(message)
+    } else {
+        anyhow::anyhow!("{}\n{}", message, errors)
+    }
+}
+
+/// 等待挂载点可访问；rclone 不再管理该挂载或超时时返回带 rclone 日志的错误
+pub async fn wait_ready(
+    app: &tauri::AppHandle<Runtime>,
+    mount_path: &str,
+    log_offset: Option<u64>,
+    timeout: Duration,
+) -> anyhow::Result<Duration> {
+    let started = Instant::now();
+    let local: PathBuf = local_path(app, mount_path);
+    let rc_point = rc_mount_point(mount_path);
+    let mut last_list = started;
+    loop {
+        if is_served(&local).await {
+            return Ok(started.elapsed());
+        }
+        if started.elapsed() >= timeout {
+            return Err(not_ready_error(
+                format!(
+                    "Mount {} was not ready after {}s",
+                    mount_path,
+                    timeout.as_secs()
+                ),
+                log_offset,
+                mount_path,
+            ));
+        }
+        // 挂载在后台失败时 rclone 会移除该挂载，无需等到超时
+        if last_list.elapsed() >= LIST_INTERVAL {
+            last_list = Instant::now();
+            if let Ok(active) = list_active().await {
+                if !active
+                    .iter()
+                    .any(|m| m.mount_point == rc_point || m.mount_point == mount_path)
+                {
+                    return Err(not_ready_error(
+                        format!("rclone is no longer serving {}", mount_path),
+                        log_offset,
+                        mount_path,
+                    ));
+                }
+            }
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+/// 挂载并等待就绪
+pub async fn mount_and_wait(
+    app: &tauri::AppHandle<Runtime>,
+    spec: &MountSpec,
+    timeout: Duration,
+) -> anyhow::Result<MountReady> {
+    let log_offset = runlog::rclone_log_offset();
+    let started = Instant::now();
+    super::mount(spec).await?;
+    let mount_ms = started.elapsed();
+    let ready = wait_ready(app, &spec.mount_path, log_offset, timeout).await?;
+    Ok(MountReady {
+        mount_path: spec.mount_path.clone(),
+        mount_ms: mount_ms.as_millis() as u64,
+        ready_ms: ready.as_millis() as u64,
+        latency_ms: started.elapsed().as_millis() as u64,
+    })
+}
@@ -0,0 +1,188 @@
+//! 退出时干净地卸载：先等待 VFS 写回队列上传完成（有时限），再通过 rclone 卸载全部挂载，
+//! 然后终止 sidecar，最后对仍残留在内核中的 rclone 挂载执行 `fusermount -uz`。
+//!
+//! 必须在卸载前等待上传：rclone 卸载时会关闭对应的 VFS，之后 `vfs/stats` 无法再查询。
+
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tauri::Emitter;
+
+use super::{list_active, load_mounts, local_path};
+use crate::config::Config;
+use crate::{rclone, sidecar, AppExt, Runtime};
+
+/// 默认最多等待上传的时间（秒）
+const DEFAULT_UPLOAD_WAIT_SECS: u64 = 120;
+/// 单次 rc 请求的超时，避免 rclone 无响应时卡住退出
+const RC_TIMEOUT: Duration = Duration::from_secs(10);
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 退出流程状态：0 未开始，1 进行中，2 已完成
+static EXIT_STATE: AtomicU8 = AtomicU8::new(0);
+
+/// 卸载结果
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnmountReport {
+    /// rclone 卸载的挂载点
+    pub mount_points: Vec<String>,
+    /// 超时后仍未上传的文件数
+    pub pending_uploads: u64,
+    pub timed_out: bool,
+}
+
+/// 通过 `mount-exit-progress` 事件通知前端剩余的上传
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadProgress {
+    pub pending_uploads: u64,
+    pub remaining_secs: u64,
+}
+
+/// `settings.exitUploadWaitSecs`，0 表示不等待
+fn upload_wait(app: &tauri::AppHandle<Runtime>) -> Duration {
+    let secs = app.with_app_state::<Config, _>(|config| {
+        config.0["settings"]["exitUploadWaitSecs"]
+            .as_u64()
+            .unwrap_or(DEFAULT_UPLOAD_WAIT_SECS)
+    });
+    Duration::from_secs(secs)
+}
+
+async fn rc(path: &str, body: serde_json::Value) -> anyhow::Result<serde_json::Value> {
+    tokio::time::timeout(RC_TIMEOUT, rclone::rc_post(path, body))
+        .await
+        .map_err(|_| anyhow::anyhow!("rclone did not respond to {}", path))?
+}
+
+/// 各挂载 VFS 中正在上传与排队的文件数之和
+async fn pending_uploads(fs_list: &[String]) -> u64 {
+    let mut total = 0;
+    for fs in fs_list {
+        let Ok(stats) = rc("vfs/stats", json!({ "fs": fs })).await else {
+            continue;
+        };
+        // 未启用缓存（CacheMode=off）时没有 diskCache
+        let cache = &stats["diskCache"];
+        total += cache["uploadsInProgress"].as_u64().unwrap_or(0)
+            + cache["uploadsQueued"].as_u64().unwrap_or(0);
+    }
+    total
+}
+
+/// 等待写回队列清空后让 rclone 卸载全部挂载；rclone 未运行时直接返回
+pub async fn unmount_all(app: &tauri::AppHandle<Runtime>) -> anyhow::Result<UnmountReport> {
+    let mut report = UnmountReport::default();
+    if rclone::endpoint().is_none() {
+        return Ok(report);
+    }
+    let active = list_active().await?;
+    if active.is_empty() {
+        return Ok(report);
+    }
+    let mut fs_list: Vec<String> = active.iter().map(|m| m.fs.clone()).collect();
+    fs_list.sort();
+    fs_list.dedup();
+
+    let wait = upload_wait(app);
+    let deadline = Instant::now() + wait;
+    let mut last = None;
+    loop {
+        let pending = pending_uploads(&fs_list).await;
+        if last != Some(pending) {
+            let remaining_secs = deadline.saturating_duration_since(Instant::now()).as_secs();
+            println!(
+                "Waiting for {} file(s) to upload before unmounting",
+                pending
+            );
+            let _ = app.emit(
+                "mount-exit-progress",
+                UploadProgress {
+                    pending_uploads: pending,
+                    remaining_secs,
+                },
+            );
+            last = Some(pending);
+        }
+        if pending == 0 {
+            break;
+        }
+        if Instant::now() >= deadline {
+            report.pending_uploads = pending;
+            report.timed_out = true;
+            eprintln!(
+                "Gave up waiting for {} pending upload(s) after {}s",
+                pending,
+                wait.as_secs()
+            );
+            break;
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+
+    rc("mount/unmountall", json!({})).await?;
+    report.mount_points = active.into_iter().map(|m| m.mount_point).collect();
+    Ok(report)
+}
+
+/// 退出流程：卸载、终止 sidecar、清理残留的挂载
+pub async fn shutdown(app: &tauri::AppHandle<Runtime>) {
+    // 记录需要检查残留的路径（rclone 终止后无法再查询）
+    let mut paths: Vec<PathBuf> = load_mounts(app)
+        .iter()
+        .map(|m| local_path(app, &m.mount_path))
+        .collect();
+    match unmount_all(app).await {
+        Ok(report) => paths.extend(report.mount_points.iter().map(PathBuf::from)),
+        Err(e) => eprintln!("Failed to unmount before exit: {}", e),
+    }
+
+    sidecar::cleanup();
+    rclone::clear_endpoint();
+
+    #[cfg(target_os = "linux")]
+    {
+        // 给 rclone 处理 SIGTERM（它也会尝试卸载）留出时间
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        let kernel = super::mountinfo::read();
+        paths.sort();
+        paths.dedup();
+        for path in paths {
+            if super::mountinfo::find(&kernel, &path).is_some_and(|e| e.is_rclone()) {
+                match super::lazy_unmount(&path) {
+                    Ok(()) => println!("Lazily unmounted leftover mount {}", path.display()),
+                    Err(e) => eprintln!("{}", e),
+                }
+            }
+        }
+    }
+}
+
+/// 退出流程已开始，挂载正在或已经被卸载
+pub fn exiting() -> bool {
+    EXIT_STATE.load(Ordering::SeqCst) != 0
+}
+
+/// 退出流程已完成，可以直接退出
+pub fn exit_ready() -> bool {
+    EXIT_STATE.load(Ordering::SeqCst) == 2
+}
+
+/// 后台执行退出流程，完成后再次退出应用；重复调用时忽略
+pub fn exit_after_shutdown(app: tauri::AppHandle<Runtime>, code: i32) {
+    if EXIT_STATE
+        .compare_exchange(0, 1, Ordering::SeqCst, Ordering::SeqCst)
+        .is_err()
+    {
+        return;
+    }
+    tauri::async_runtime::spawn(async move {
+        shutdown(&app).await;
+        EXIT_STATE.store(2, Ordering::SeqCst);
+        app.exit(code);
+    });
+}
@@ -0,0 +1,180 @@
+//! 为存储推荐空闲的挂载目录（Linux 上对应 Windows 的自动分配盘符）。
+//!
+//! 基础目录取 `settings.mountBase`，默认 `~/NetMount`；以 `$XDG_RUNTIME_DIR` 开头时展开为
+//! 该环境变量（未设置时退回默认值）。存储名转换为安全的目录名，并避开已配置的挂载、
+//! 内核中已有的挂载点以及非空目录。
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::{load_mounts, local_path, mountinfo};
+use crate::config::Config;
+use crate::{AppExt, Runtime};
+
+const DEFAULT_BASE: &str = "~/NetMount";
+const XDG_RUNTIME_DIR: &str = "$XDG_RUNTIME_DIR";
+/// 同名目录被占用时追加序号的上限
+const MAX_SUFFIX: u32 = 100;
+/// 目录名的最大字节数（大多数文件系统为 255）
+const MAX_NAME_BYTES: usize = 255;
+/// 为序号后缀（最长为 `-100`）预留的字节数
+const SUFFIX_BYTES: usize = 4;
+
+/// 推荐结果
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MountPathSuggestion {
+    /// 保存到配置中的路径（基础目录以 `~` 开头时保留 `~`）
+    pub path: String,
+    /// 展开后的本地路径
+    pub local: PathBuf,
+    /// 本次创建了目录
+    pub created: bool,
+}
+
+/// 把存储名转换为安全的目录名，并为序号后缀留出长度
+fn mount_dir_name(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    for c in name.trim().chars() {
+        let safe = match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        };
+        // 合并连续的替换字符
+        if safe == '_' && out.ends_with('_') && c != '_' {
+            continue;
+        }
+        if out.len() + safe.len_utf8() > MAX_NAME_BYTES - SUFFIX_BYTES {
+            break;
+        }
+        out.push(safe);
+    }
+    // 去掉首尾的点，避免隐藏目录或 `.`、`..`
+    let out = out.trim_matches(|c: char| c == '.' || c.is_whitespace());
+    if out.is_empty() {
+        "storage".to_string()
+    } else {
+        out.to_string()
+    }
+}
+
+/// 配置的基础目录（未展开 `~`）
+fn base_dir(app: &tauri::AppHandle<Runtime>) -> String {
+    let base = app.with_app_state::<Config, _>(|config| {
+        config.0["settings"]["mountBase"]
+            .as_str()
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    });
+    let Some(base) = base else {
+        return DEFAULT_BASE.to_string();
+    };
+    match base.strip_prefix(XDG_RUNTIME_DIR) {
+        Some(rest) => match std::env::var("XDG_RUNTIME_DIR") {
+            Ok(dir) if !dir.is_empty() => {
+                let rest = rest.trim_start_matches('/');
+                if rest.is_empty() {
+                    format!("{}/netmount", dir.trim_end_matches('/'))
+                } else {
+                    format!("{}/{}", dir.trim_end_matches('/'), rest)
+                }
+            }
+            _ => DEFAULT_BASE.to_string(),
+        },
+        None => base,
+    }
+}
+
+/// 目录是否可作为新的挂载点：不存在，或是空目录
+fn is_free_dir(path: &Path) -> bool {
+    match std::fs::metadata(path) {
+        Err(_) => true,
+        Ok(m) if m.is_dir() => std::fs::read_dir(path)
+            .map(|mut entries| entries.next().is_none())
+            .unwrap_or(false),
+        Ok(_) => false,
+    }
+}
+
+/// 推荐挂载目录，`create` 为 true 时创建该目录
+pub fn suggest(
+    app: &tauri::AppHandle<Runtime>,
+    storage_name: &str,
+    create: bool,
+) -> anyhow::Result<MountPathSuggestion> {
+    let base = base_dir(app);
+    let name = mount_dir_name(storage_name);
+    let taken: Vec<PathBuf> = load_mounts(app)
+        .iter()
+        .map(|m| local_path(app, &m.mount_path))
+        .collect();
+    let kernel = mountinfo::read();
+
+    for n in 1..=MAX_SUFFIX {
+        let dir_name = if n == 1 {
+            name.clone()
+        } else {
+            format!("{}-{}", name, n)
+        };
+        let path = format!("{}/{}", base.trim_end_matches('/'), dir_name);
+        let local = local_path(app, &path);
+        if taken.iter().any(|t| t == &local)
+            || mountinfo::find(&kernel, &local).is_some()
+            || !is_free_dir(&local)
+        {
+            continue;
+        }
+        let mut created = false;
+        if create && !local.exists() {
+            std::fs::create_dir_all(&local)
+                .map_err(|e| anyhow::anyhow!("Failed to create {}: {}", local.display(), e))?;
+            created = true;
+        }
+        return Ok(MountPathSuggestion {
+            path,
+            local,
+            created,
+        });
+    }
+    Err(anyhow::anyhow!(
+        "No free mount directory for {} under {}",
+        storage_name,
+        base
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replaces_unsafe_characters() {
+        assert_eq!(mount_dir_name("gdrive"), "gdrive");
+        assert_eq!(mount_dir_name(" my/drive:backup "), "my_drive_backup");
+        // 连续的替换字符合并，原有的下划线保留
+        assert_eq!(mount_dir_name("a<>|b"), "a_b");
+        assert_eq!(mount_dir_name("a__b"), "a__b");
+        assert_eq!(mount_dir_name("tab\there"), "tab_here");
+        assert_eq!(mount_dir_name("网盘"), "网盘");
+    }
+
+    #[test]
+    fn trims_dots_and_falls_back() {
+        assert_eq!(mount_dir_name(".hidden."), "hidden");
+        assert_eq!(mount_dir_name(".."), "storage");
+        assert_eq!(mount_dir_name("  "), "storage");
+    }
+
+    #[test]
+    fn keeps_room_for_suffix() {
+        let name = mount_dir_name(&"a".repeat(300));
+        assert_eq!(name.len(), MAX_NAME_BYTES - SUFFIX_BYTES);
+        assert!(format!("{}-{}", name, MAX_SUFFIX).len() <= MAX_NAME_BYTES);
+        // 不在多字节字符中间截断
+        let name = mount_dir_name(&"网".repeat(100));
+        assert!(name.len() <= MAX_NAME_BYTES - SUFFIX_BYTES);
+        assert!(name.chars().all(|c| c == '网'));
+    }
+}
@@ -0,0 +1,533 @@
+//! 挂载前检查挂载点：与 `mount.lists` 中的挂载重复或嵌套、已被挂载、
+//! 目录不存在或非空、所有者与写权限等，返回具体问题供前端展示。
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(target_os = "windows"))]
+use super::mountinfo::{self, MountInfoEntry};
+use super::{load_mounts, local_path};
+use crate::Runtime;
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidateOptions {
+    /// 目录不存在时创建
+    #[serde(default)]
+    pub create: bool,
+    /// 挂载参数中启用了 AllowNonEmpty
+    #[serde(default)]
+    pub allow_non_empty: bool,
+    /// 编辑挂载时原来的挂载路径，不与自身比较
+    #[serde(default)]
+    pub ignore_mount_path: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Severity {
+    /// 挂载会失败
+    Error,
+    /// 可以挂载，但需要注意
+    Warning,
+}
+
+/// 检查发现的问题
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MountPointProblem {
+    /// `duplicate` / `nested` / `alreadyMounted` / `insideMount` / `notFound` / `createFailed` /
+    /// `exists` / `notDirectory` / `notEmpty` / `notOwner` / `notWritable` / `parentNotWritable`
+    pub code: String,
+    pub severity: Severity,
+    pub message: String,
+    /// 冲突的挂载路径
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conflict: Option<String>,
+}
+
+/// 挂载点检查结果
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MountPointCheck {
+    /// 展开 `~` 后的路径
+    pub path: PathBuf,
+    /// 没有 Error 级别的问题
+    pub valid: bool,
+    /// 本次检查创建了目录
+    pub created: bool,
+    pub problems: Vec<MountPointProblem>,
+}
+
+fn problem(code: &str, severity: Severity, message: String) -> MountPointProblem {
+    MountPointProblem {
+        code: code.to_string(),
+        severity,
+        message,
+        conflict: None,
+    }
+}
+
+/// 比较用的路径：统一分隔符、去掉末尾分隔符，Windows 上不区分大小写
+fn normalize(path: &Path) -> PathBuf {
+    let s = path.to_string_lossy().replace('\\', "/");
+    let trimmed = if s.len() > 1 && !s.ends_with(":/") {
+        s.trim_end_matches('/')
+    } else {
+        &s
+    };
+    if cfg!(target_os = "windows") {
+        PathBuf::from(trimmed.to_lowercase())
+    } else {
+        PathBuf::from(trimmed)
+    }
+}
+
+/// 与已配置挂载的重复与嵌套
+fn check_configured(path: &Path, configured: &[(String, PathBuf)]) -> Vec<MountPointProblem> {
+    let target = normalize(path);
+    let mut problems = Vec::new();
+    for (mount_path, local) in configured {
+        let other = normalize(local);
+        let found = if other == target {
+            Some(problem(
+                "duplicate",
+                Severity::Error,
+                format!("{} is already used by another mount", mount_path),
+            ))
+        } else if target.starts_with(&other) || other.starts_with(&target) {
+            Some(problem(
+                "nested",
+                Severity::Error,
+                format!(
+                    "{} overlaps with the mount at {}",
+                    path.display(),
+                    mount_path
+                ),
+            ))
+        } else {
+            None
+        };
+        problems.extend(found.map(|p| MountPointProblem {
+            conflict: Some(mount_path.clone()),
+            ..p
+        }));
+    }
+    problems
+}
+
+/// 与内核挂载表的冲突：路径本身已是挂载点，或位于其它 FUSE 挂载之内
+#[cfg(not(target_os = "windows"))]
+fn check_kernel(path: &Path, kernel: &[MountInfoEntry]) -> Vec<MountPointProblem> {
+    let mut problems = Vec::new();
+    if let Some(entry) = mountinfo::find(kernel, path) {
+        problems.push(MountPointProblem {
+            conflict: Some(entry.source.clone()),
+            ..problem(
+                "alreadyMounted",
+                Severity::Error,
+                format!(
+                    "{} is already a mount point ({} from {})",
+                    path.display(),
+                    entry.fs_type,
+                    entry.source
+                ),
+            )
+        });
+    }
+    if let Some(entry) = kernel
+        .iter()
+        .rev()
+        .find(|e| e.is_fuse() && e.mount_point != path && path.starts_with(&e.mount_point))
+    {
+        problems.push(MountPointProblem {
+            conflict: Some(entry.mount_point.to_string_lossy().to_string()),
+            ..problem(
+                "insideMount",
+                Severity::Error,
+                format!(
+                    "{} lies inside the FUSE mount at {}",
+                    path.display(),
+                    entry.mount_point.display()
+                ),
+            )
+        });
+    }
+    problems
+}
+
+/// 按权限位判断当前用户能否写入
+#[cfg(unix)]
+fn writable(metadata: &std::fs::Metadata, ids: Option<&super::fuse::ProcessIds>) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    let Some(ids) = ids else {
+        return !metadata.permissions().readonly();
+    };
+    if ids.uid == 0 {
+        return true;
+    }
+    let mode = metadata.mode();
+    if metadata.uid() == ids.uid {
+        mode & 0o200 != 0
+    } else if metadata.gid() == ids.gid || ids.groups.contains(&metadata.gid()) {
+        mode & 0o020 != 0
+    } else {
+        mode & 0o002 != 0
+    }
+}
+
+/// 已存在目录的类型、是否为空、所有者与权限
+#[cfg(not(target_os = "windows"))]
+fn check_directory(path: &Path, opts: &ValidateOptions) -> Vec<MountPointProblem> {
+    let mut problems = Vec::new();
+    let metadata = match std::fs::metadata(path) {
+        Ok(metadata) => metadata,
+        Err(e) => {
+            problems.push(problem(
+                "notFound",
+                Severity::Error,
+                format!("Failed to access {}: {}", path.display(), e),
+            ));
+            return problems;
+        }
+    };
+    if !metadata.is_dir() {
+        problems.push(problem(
+            "notDirectory",
+            Severity::Error,
+            format!("{} is not a directory", path.display()),
+        ));
+        return problems;
+    }
+    let non_empty = std::fs::read_dir(path)
+        .map(|mut entries| entries.next().is_some())
+        .unwrap_or(false);
+    if non_empty {
+        problems.push(if opts.allow_non_empty {
+            problem(
+                "notEmpty",
+                Severity::Warning,
+                format!(
+                    "{} is not empty, its contents will be hidden while mounted",
+                    path.display()
+                ),
+            )
+        } else {
+            problem(
+                "notEmpty",
+                Severity::Error,
+                format!(
+                    "{} is not empty; choose an empty directory or enable AllowNonEmpty",
+                    path.display()
+                ),
+            )
+        });
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        let ids = super::fuse::process_ids(Path::new("/proc"));
+        if let Some(ids) = ids.as_ref().filter(|ids| ids.uid != 0) {
+            if metadata.uid() != ids.uid {
+                problems.push(problem(
+                    "notOwner",
+                    Severity::Warning,
+                    format!(
+                        "{} is owned by uid {}, not the current user",
+                        path.display(),
+                        metadata.uid()
+                    ),
+                ));
+            }
+        }
+        if !writable(&metadata, ids.as_ref()) {
+            problems.push(problem(
+                "notWritable",
+                Severity::Error,
+                format!("No write permission on {}", path.display()),
+            ));
+        }
+    }
+    problems
+}
+
+/// 目录不存在：按需创建，否则检查最近的已存在上级目录能否写入
+#[cfg(not(target_os = "windows"))]
+fn check_missing(
+    path: &Path,
+    opts: &ValidateOptions,
+    created: &mut bool,
+) -> Vec<MountPointProblem> {
+    if opts.create {
+        return match std::fs::create_dir_all(path) {
+            Ok(()) => {
+                *created = true;
+                Vec::new()
+            }
+            Err(e) => vec![problem(
+                "createFailed",
+                Severity::Error,
+                format!("Failed to create {}: {}", path.display(), e),
+            )],
+        };
+    }
+    let mut problems = vec![problem(
+        "notFound",
+        Severity::Warning,
+        format!(
+            "{} does not exist and will be created when mounting",
+            path.display()
+        ),
+    )];
+    if let Some(parent) = path.ancestors().skip(1).find(|p| p.exists()) {
+        if std::fs::metadata(parent).is_ok_and(|m| m.permissions().readonly()) {
+            problems.push(problem(
+                "parentNotWritable",
+                Severity::Error,
+                format!("No write permission on {}", parent.display()),
+            ));
+        }
+    }
+    problems
+}
+
+/// Windows 上 WinFsp 要求挂载点不存在（盘符未被占用），上级目录必须存在
+#[cfg(target_os = "windows")]
+fn check_windows(path: &Path) -> Vec<MountPointProblem> {
+    let s = path.to_string_lossy();
+    let is_drive = s.len() <= 3 && s.chars().nth(1) == Some(':');
+    if is_drive {
+        let root = format!("{}\\", &s[..2]);
+        if Path::new(&root).exists() {
+            return vec![problem(
+                "alreadyMounted",
+                Severity::Error,
+                format!("Drive {} is already in use", &s[..2]),
+            )];
+        }
+        return Vec::new();
+    }
+    if path.exists() {
+        return vec![problem(
+            "exists",
+            Severity::Error,
+            format!(
+                "{} already exists; WinFsp mounts to a path that does not exist yet",
+                path.display()
+            ),
+        )];
+    }
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() && !parent.exists() => vec![problem(
+            "notFound",
+            Severity::Error,
+            format!("Parent directory {} does not exist", parent.display()),
+        )],
+        _ => Vec::new(),
+    }
+}
+
+/// 检查挂载点，`configured` 为其它已配置挂载的 `(mountPath, 本地路径)`
+pub fn validate(
+    path: &Path,
+    configured: &[(String, PathBuf)],
+    opts: &ValidateOptions,
+) -> MountPointCheck {
+    let mut problems = check_configured(path, configured);
+
+    #[cfg(target_os = "windows")]
+    let created = {
+        let _ = opts;
+        problems.extend(check_windows(path));
+        false
+    };
+
+    #[cfg(not(target_os = "windows"))]
+    let created = {
+        let mut created = false;
+        let kernel = mountinfo::read();
+        let mounted = mountinfo::find(&kernel, path).is_some();
+        problems.extend(check_kernel(path, &kernel));
+        // 已被挂载时目录内容来自挂载的文件系统，不再检查
+        if !mounted {
+            if path.exists() {
+                problems.extend(check_directory(path, opts));
+            } else {
+                problems.extend(check_missing(path, opts, &mut created));
+            }
+        }
+        created
+    };
+
+    MountPointCheck {
+        path: path.to_path_buf(),
+        valid: problems.iter().all(|p| p.severity != Severity::Error),
+        created,
+        problems,
+    }
+}
+
+/// 使用当前配置检查挂载点
+pub fn validate_for_app(
+    app: &tauri::AppHandle<Runtime>,
+    mount_path: &str,
+    opts: &ValidateOptions,
+) -> MountPointCheck {
+    let ignore = opts
+        .ignore_mount_path
+        .as_deref()
+        .map(|p| normalize(&local_path(app, p)));
+    let configured: Vec<(String, PathBuf)> = load_mounts(app)
+        .into_iter()
+        .map(|m| {
+            let local = local_path(app, &m.mount_path);
+            (m.mount_path, local)
+        })
+        .filter(|(_, local)| ignore.as_ref() != Some(&normalize(local)))
+        .collect();
+    validate(&local_path(app, mount_path), &configured, opts)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn codes(problems: &[MountPointProblem]) -> Vec<&str> {
+        problems.iter().map(|p| p.code.as_str()).collect()
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("netmount-validate-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn fuse_entry(id: u32, mount_point: &str, fs_type: &str) -> MountInfoEntry {
+        MountInfoEntry {
+            mount_id: id,
+            mount_point: PathBuf::from(mount_point),
+            fs_type: fs_type.to_string(),
+            source: "remote:".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn normalize_trims_trailing_separators() {
+        assert_eq!(normalize(Path::new("/mnt/a/")), PathBuf::from("/mnt/a"));
+        assert_eq!(normalize(Path::new("/mnt\\a")), PathBuf::from("/mnt/a"));
+        assert_eq!(normalize(Path::new("/")), PathBuf::from("/"));
+    }
+
+    #[test]
+    fn configured_duplicates_and_nesting() {
+        let configured = vec![
+            ("~/a".to_string(), PathBuf::from("/home/me/a/")),
+            ("/mnt/data".to_string(), PathBuf::from("/mnt/data")),
+        ];
+        let problems = check_configured(Path::new("/home/me/a"), &configured);
+        assert_eq!(codes(&problems), ["duplicate"]);
+        assert_eq!(problems[0].conflict.as_deref(), Some("~/a"));
+        assert_eq!(
+            codes(&check_configured(Path::new("/mnt/data/sub"), &configured)),
+            ["nested"]
+        );
+        assert_eq!(
+            codes(&check_configured(Path::new("/mnt"), &configured)),
+            ["nested"]
+        );
+        // 只是名称前缀相同的目录不算嵌套
+        assert!(check_configured(Path::new("/mnt/database"), &configured).is_empty());
+    }
+
+    #[test]
+    fn kernel_conflicts() {
+        let kernel = vec![
+            fuse_entry(1, "/", "ext4"),
+            fuse_entry(2, "/mnt/drive", "fuse.rclone"),
+        ];
+        assert_eq!(
+            codes(&check_kernel(Path::new("/mnt/drive"), &kernel)),
+            ["alreadyMounted"]
+        );
+        let inside = check_kernel(Path::new("/mnt/drive/sub"), &kernel);
+        assert_eq!(codes(&inside), ["insideMount"]);
+        assert_eq!(inside[0].conflict.as_deref(), Some("/mnt/drive"));
+        // 位于普通文件系统中不算冲突
+        assert!(check_kernel(Path::new("/mnt/other"), &kernel).is_empty());
+    }
+
+    #[test]
+    fn writable_follows_owner_group_and_other_bits() {
+        use std::os::unix::fs::{MetadataExt, PermissionsExt};
+        let dir = temp_dir("writable");
+        fs::set_permissions(&dir, fs::Permissions::from_mode(0o750)).unwrap();
+        let metadata = fs::metadata(&dir).unwrap();
+        let ids = |uid, gid, groups: Vec<u32>| crate::mount::fuse::ProcessIds { uid, gid, groups };
+        let (owner, group) = (metadata.uid(), metadata.gid());
+        let stranger = owner.wrapping_add(1000).max(1);
+        assert!(writable(&metadata, Some(&ids(owner, group, vec![]))));
+        assert!(writable(&metadata, Some(&ids(0, 0, vec![]))));
+        // 组只有读权限，其他用户没有权限
+        assert!(!writable(&metadata, Some(&ids(stranger, group, vec![]))));
+        assert!(!writable(
+            &metadata,
+            Some(&ids(stranger, group.wrapping_add(1), vec![]))
+        ));
+        fs::set_permissions(&dir, fs::Permissions::from_mode(0o770)).unwrap();
+        let metadata = fs::metadata(&dir).unwrap();
+        assert!(writable(
+            &metadata,
+            Some(&ids(stranger, group.wrapping_add(1), vec![group]))
+        ));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn directory_checks() {
+        let dir = temp_dir("directory");
+        let opts = ValidateOptions::default();
+        assert!(!codes(&check_directory(&dir, &opts)).contains(&"notEmpty"));
+
+        fs::write(dir.join("file"), "x").unwrap();
+        let problems = check_directory(&dir, &opts);
+        let not_empty = problems.iter().find(|p| p.code == "notEmpty").unwrap();
+        assert_eq!(not_empty.severity, Severity::Error);
+        let allow = ValidateOptions {
+            allow_non_empty: true,
+            ..Default::default()
+        };
+        let problems = check_directory(&dir, &allow);
+        let not_empty = problems.iter().find(|p| p.code == "notEmpty").unwrap();
+        assert_eq!(not_empty.severity, Severity::Warning);
+
+        assert_eq!(
+            codes(&check_directory(&dir.join("file"), &opts)),
+            ["notDirectory"]
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn missing_directory_is_created_on_request() {
+        let dir = temp_dir("missing");
+        let target = dir.join("a/b");
+        let mut created = false;
+        let problems = check_missing(&target, &ValidateOptions::default(), &mut created);
+        assert_eq!(codes(&problems), ["notFound"]);
+        assert_eq!(problems[0].severity, Severity::Warning);
+        assert!(!created && !target.exists());
+
+        let opts = ValidateOptions {
+            create: true,
+            ..Default::default()
+        };
+        assert!(check_missing(&target, &opts, &mut created).is_empty());
+        assert!(created && target.is_dir());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
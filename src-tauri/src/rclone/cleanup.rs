@@ -0,0 +1,234 @@
+//! 缓存与日志清理。
+//!
+//! 前端不再能直接以任意参数运行 rclone，只能通过 `rclone_cleanup` 请求几种固定的清理操作，
+//! 路径限定在缓存目录与日志目录之下。
+
+use std::path::{Component, Path, PathBuf};
+
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::config::Config;
+use crate::{AppExt, Runtime};
+
+/// 未指定超时的清理操作默认等待时间
+const DEFAULT_TIMEOUT_MS: u64 = 30_000;
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(tag = "action", rename_all = "lowercase")]
+pub enum Cleanup {
+    /// 删除目录中超过 `min_age` 的文件（`rclone delete --min-age`），`include` 限定文件名
+    Delete {
+        path: PathBuf,
+        #[serde(rename = "minAge")]
+        min_age: String,
+        #[serde(default)]
+        include: Option<String>,
+    },
+    /// 删除整个目录（`rclone purge`），只允许清理目录下的子目录
+    Purge { path: PathBuf },
+    /// 清理存储中的残留文件（`rclone cleanup <storage>:`）
+    Cleanup { storage: String },
+}
+
+/// 允许清理的目录：设置中的缓存目录与日志目录（未设置日志目录时为数据目录下的 log）
+fn allowed_roots(settings: &Value, data_dir: &Path) -> Vec<PathBuf> {
+    let path = &settings["path"];
+    let mut roots = Vec::new();
+    if let Some(cache) = path["cacheDir"].as_str().filter(|s| !s.is_empty()) {
+        roots.push(PathBuf::from(cache));
+    }
+    match path["logDir"].as_str().filter(|s| !s.is_empty()) {
+        Some(log) => roots.push(PathBuf::from(log)),
+        None => roots.push(data_dir.join("log")),
+    }
+    roots
+}
+
+/// 路径必须是绝对路径、不含 `..`，且位于某个允许的目录下；`strict` 时不能是该目录本身
+fn check_path(path: &Path, roots: &[PathBuf], strict: bool) -> anyhow::Result<()> {
+    let inside = path.is_absolute()
+        && !path.components().any(|c| c == Component::ParentDir)
+        && roots.iter().any(|root| {
+            root.is_absolute()
+                && path.starts_with(root)
+                && !(strict && path.components().eq(root.components()))
+        });
+    if !inside {
+        return Err(anyhow::anyhow!(
+            "Cleanup path is outside the cache and log directories: {}",
+            path.display()
+        ));
+    }
+    Ok(())
+}
+
+/// rclone 的时长格式，如 `1h`、`168h`、`7d`
+fn check_min_age(age: &str) -> anyhow::Result<()> {
+    let unit = age.trim_start_matches(|c: char| c.is_ascii_digit());
+    if unit.len() == age.len() || !matches!(unit, "ms" | "s" | "m" | "h" | "d" | "w" | "M" | "y") {
+        return Err(anyhow::anyhow!("Invalid min age: {}", age));
+    }
+    Ok(())
+}
+
+/// 构造 rclone 命令行参数
+fn cleanup_args(cleanup: &Cleanup, roots: &[PathBuf]) -> anyhow::Result<Vec<String>> {
+    match cleanup {
+        Cleanup::Delete {
+            path,
+            min_age,
+            include,
+        } => {
+            check_path(path, roots, false)?;
+            check_min_age(min_age)?;
+            let mut args = vec!["delete".to_string(), path.to_string_lossy().to_string()];
+            if let Some(include) = include {
+                if include.is_empty() || include.starts_with('-') {
+                    return Err(anyhow::anyhow!("Invalid include pattern: {}", include));
+                }
+                args.extend(["--include".to_string(), include.clone()]);
+            }
+            args.extend(["--min-age".to_string(), min_age.clone()]);
+            Ok(args)
+        }
+        Cleanup::Purge { path } => {
+            check_path(path, roots, true)?;
+            Ok(vec![
+                "purge".to_string(),
+                path.to_string_lossy().to_string(),
+            ])
+        }
+        Cleanup::Cleanup { storage } => {
+            let valid = !storage.is_empty()
+                && !storage.starts_with('-')
+                && !storage.contains([':', '/', '\\']);
+            if !valid {
+                return Err(anyhow::anyhow!("Invalid storage name: {}", storage));
+            }
+            Ok(vec!["cleanup".to_string(), format!("{}:", storage)])
+        }
+    }
+}
+
+/// 运行一次 rclone 清理操作
+#[tauri::command]
+pub async fn rclone_cleanup(
+    app: tauri::AppHandle<Runtime>,
+    cleanup: Cleanup,
+    timeout_ms: Option<u64>,
+) -> anyhow_tauri::TAResult<()> {
+    let roots = app.with_app_state::<Config, _>(|config| {
+        allowed_roots(&config.0["settings"], &crate::resolve_data_dir())
+    });
+    let args = cleanup_args(&cleanup, &roots)?;
+    crate::run_sidecar_once(
+        app,
+        "binaries/rclone".to_string(),
+        args,
+        Some(timeout_ms.unwrap_or(DEFAULT_TIMEOUT_MS)),
+        None,
+    )
+    .await
+    .map_err(|e| anyhow::anyhow!(e))?;
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn roots() -> Vec<PathBuf> {
+        allowed_roots(
+            &json!({ "path": { "cacheDir": "/home/me/.cache/netmount" } }),
+            Path::new("/home/me/.netmount"),
+        )
+    }
+
+    #[test]
+    fn roots_default_log_dir() {
+        assert_eq!(
+            roots(),
+            vec![
+                PathBuf::from("/home/me/.cache/netmount"),
+                PathBuf::from("/home/me/.netmount/log"),
+            ]
+        );
+        let roots = allowed_roots(
+            &json!({ "path": { "cacheDir": "", "logDir": "/var/log/netmount" } }),
+            Path::new("/home/me/.netmount"),
+        );
+        assert_eq!(roots, vec![PathBuf::from("/var/log/netmount")]);
+    }
+
+    #[test]
+    fn delete_args() {
+        let cleanup: Cleanup = serde_json::from_value(json!({
+            "action": "delete",
+            "path": "/home/me/.netmount/log/",
+            "minAge": "168h",
+            "include": "*.log.*",
+        }))
+        .unwrap();
+        assert_eq!(
+            cleanup_args(&cleanup, &roots()).unwrap(),
+            [
+                "delete",
+                "/home/me/.netmount/log/",
+                "--include",
+                "*.log.*",
+                "--min-age",
+                "168h"
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_paths_outside_roots() {
+        let purge = |path: &str| Cleanup::Purge { path: path.into() };
+        assert!(cleanup_args(&purge("/home/me/.cache/netmount/rclone/"), &roots()).is_ok());
+        assert!(cleanup_args(&purge("/home/me/.cache/netmount-other"), &roots()).is_err());
+        assert!(cleanup_args(&purge("/home/me/.cache/netmount/../../"), &roots()).is_err());
+        assert!(cleanup_args(&purge("relative/rclone"), &roots()).is_err());
+        assert!(cleanup_args(&purge("/home/me"), &roots()).is_err());
+        // purge 不能删除整个缓存目录
+        assert!(cleanup_args(&purge("/home/me/.cache/netmount/"), &roots()).is_err());
+        // 未设置缓存目录时不能清理任何缓存路径
+        let no_cache = allowed_roots(&json!({}), Path::new("/home/me/.netmount"));
+        assert!(cleanup_args(&purge("/home/me/.cache/netmount/rclone"), &no_cache).is_err());
+    }
+
+    #[test]
+    fn rejects_bad_options() {
+        let delete = |min_age: &str, include: Option<&str>| Cleanup::Delete {
+            path: "/home/me/.cache/netmount/rclone".into(),
+            min_age: min_age.to_string(),
+            include: include.map(str::to_string),
+        };
+        assert!(cleanup_args(&delete("1h", None), &roots()).is_ok());
+        assert!(cleanup_args(&delete("7d", None), &roots()).is_ok());
+        assert!(cleanup_args(&delete("h", None), &roots()).is_err());
+        assert!(cleanup_args(&delete("1h --dry-run", None), &roots()).is_err());
+        assert!(cleanup_args(&delete("--max-age", None), &roots()).is_err());
+        assert!(cleanup_args(&delete("1h", Some("--config=/x")), &roots()).is_err());
+        assert!(cleanup_args(&delete("1h", Some("")), &roots()).is_err());
+    }
+
+    #[test]
+    fn cleanup_storage_name() {
+        let cleanup = |storage: &str| {
+            cleanup_args(
+                &Cleanup::Cleanup {
+                    storage: storage.to_string(),
+                },
+                &roots(),
+            )
+        };
+        assert_eq!(cleanup("gdrive").unwrap(), ["cleanup", "gdrive:"]);
+        assert!(cleanup("").is_err());
+        assert!(cleanup("-v").is_err());
+        assert!(cleanup("/etc").is_err());
+        assert!(cleanup("gdrive:path").is_err());
+    }
+}
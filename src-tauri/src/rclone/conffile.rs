@@ -0,0 +1,884 @@
+//! 读取用户已有的 rclone.conf（含加密配置），预览其中的 remote 并导入到 NetMount 的 rclone 配置；
+//! 以及把 NetMount 的存储导出为独立的 rclone.conf。
+//!
+//! 加密配置与 rclone `fs/config/crypt.go` 一致：以 `RCLONE_ENCRYPT_V0:` 开头，
+//! 之后是 `base64(nonce[24] || secretbox 密文)`，密钥为 `sha256("[" + 密码 + "][rclone-config]")`。
+//! 导入通过 rc `config/create` 完成，配置中的密码已是混淆后的值，因此使用 `noObscure`。
+//! 导出读取 rc `config/dump`，按 `config/providers` 中的 `IsPassword`、`Sensitive` 处理密钥。
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use crypto_secretbox::aead::{Aead, KeyInit};
+use crypto_secretbox::{Key, Nonce, XSalsa20Poly1305};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use sha2::{Digest, Sha256};
+use unicode_normalization::UnicodeNormalization;
+
+use super::{list_remotes, obscure, rc_post, OPENLIST_REMOTE};
+use crate::Runtime;
+
+const ENCRYPT_HEADER: &str = "RCLONE_ENCRYPT_V";
+const ENCRYPT_V0: &str = "RCLONE_ENCRYPT_V0:";
+/// 与 rclone 写出的加密配置相同的文件头
+const ENCRYPTED_FILE_HEADER: &str = "# Encrypted rclone configuration File\n\n";
+const NONCE_LEN: usize = 24;
+/// secretbox 认证标签长度
+const TAG_LEN: usize = 16;
+/// 重名时追加序号的上限
+const MAX_SUFFIX: u32 = 100;
+/// 值为其它 remote 的选项（crypt、alias、chunker 等的 `remote`，union、combine 的 `upstreams`）
+const REFERENCE_KEYS: [&str; 2] = ["remote", "upstreams"];
+
+/// 配置文件中的一个 remote，选项保持文件中的顺序（含 `type`）
+#[derive(Clone, Debug)]
+pub struct ConfRemote {
+    pub name: String,
+    pub options: Vec<(String, String)>,
+}
+
+impl ConfRemote {
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.options
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    pub fn remote_type(&self) -> Option<&str> {
+        self.get("type").filter(|t| !t.is_empty())
+    }
+
+    /// 选项中引用的其它 remote 名称
+    pub fn references(&self) -> Vec<String> {
+        let mut refs = Vec::new();
+        for (key, value) in &self.options {
+            if !REFERENCE_KEYS.contains(&key.as_str()) {
+                continue;
+            }
+            for token in value.split_whitespace() {
+                if let Some((_, name, _)) = split_reference(token) {
+                    if !refs.iter().any(|r| r == name) {
+                        refs.push(name.to_string());
+                    }
+                }
+            }
+        }
+        refs
+    }
+}
+
+/// 把 `[dir=]remote:path` 拆分为 (`dir=`, `remote`, `:path`)；本地路径（无 `:` 或 Windows 盘符）返回 None
+fn split_reference(token: &str) -> Option<(&str, &str, &str)> {
+    // combine 的 upstreams 形如 `dir=remote:path`
+    let (prefix, rest) = match (token.find('='), token.find(':')) {
+        (Some(eq), Some(colon)) if eq < colon => token.split_at(eq + 1),
+        _ => ("", token),
+    };
+    let colon = rest.find(':')?;
+    let name = &rest[..colon];
+    if name.is_empty() || (name.len() == 1 && cfg!(target_os = "windows")) {
+        return None;
+    }
+    Some((prefix, name, &rest[colon..]))
+}
+
+/// 去掉值两端成对的引号
+fn unquote(value: &str) -> &str {
+    for q in ['"', '\''] {
+        if value.len() >= 2 && value.starts_with(q) && value.ends_with(q) {
+            return &value[1..value.len() - 1];
+        }
+    }
+    value
+}
+
+/// 解析 rclone 的 INI 格式；同名段落合并，后出现的选项覆盖前面的
+pub fn parse(content: &str) -> Vec<ConfRemote> {
+    let mut remotes: Vec<ConfRemote> = Vec::new();
+    let mut current: Option<usize> = None;
+    for line in content.trim_start_matches('\u{feff}').lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            let name = name.trim();
+            current = Some(match remotes.iter().position(|r| r.name == name) {
+                Some(i) => i,
+                None => {
+                    remotes.push(ConfRemote {
+                        name: name.to_string(),
+                        options: Vec::new(),
+                    });
+                    remotes.len() - 1
+                }
+            });
+            continue;
+        }
+        // 段落之外的内容忽略
+        let (Some(i), Some((key, value))) = (current, line.split_once('=')) else {
+            continue;
+        };
+        let key = key.trim().to_string();
+        let value = unquote(value.trim()).to_string();
+        let options = &mut remotes[i].options;
+        match options.iter_mut().find(|(k, _)| *k == key) {
+            Some(option) => option.1 = value,
+            None => options.push((key, value)),
+        }
+    }
+    remotes
+}
+
+/// 配置是否加密（首个非注释行为 `RCLONE_ENCRYPT_V*:`）
+pub fn is_encrypted(content: &str) -> bool {
+    content
+        .trim_start_matches('\u{feff}')
+        .lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty() && !l.starts_with('#') && !l.starts_with(';'))
+        .is_some_and(|l| l.starts_with(ENCRYPT_HEADER))
+}
+
+/// 与 rclone `checkPassword` 一致：NFKC 规范化，不能只包含空白
+fn config_key(password: &str) -> anyhow::Result<[u8; 32]> {
+    let password: String = password.nfkc().collect();
+    if password.trim().is_empty() {
+        return Err(anyhow::anyhow!("no characters in password"));
+    }
+    let mut hasher = Sha256::new();
+    hasher.update(format!("[{}][rclone-config]", password).as_bytes());
+    Ok(hasher.finalize().into())
+}
+
+/// 解密 `rclone config encryption` 生成的配置
+pub fn decrypt(content: &str, password: &str) -> anyhow::Result<String> {
+    let mut lines = content
+        .trim_start_matches('\u{feff}')
+        .lines()
+        .map(str::trim);
+    let header = lines
+        .find(|l| !l.is_empty() && !l.starts_with('#') && !l.starts_with(';'))
+        .unwrap_or_default();
+    if header != ENCRYPT_V0 {
+        return Err(anyhow::anyhow!(
+            "unsupported configuration encryption - update rclone for support"
+        ));
+    }
+    let encoded: String = lines
+        .flat_map(|l| l.chars())
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    let data = STANDARD
+        .decode(encoded.as_bytes())
+        .map_err(|e| anyhow::anyhow!("failed to load base64 encoded data: {}", e))?;
+    if data.len() < NONCE_LEN + TAG_LEN {
+        return Err(anyhow::anyhow!("configuration data too short"));
+    }
+    let (nonce, sealed) = data.split_at(NONCE_LEN);
+    let key = config_key(password)?;
+    let plain = XSalsa20Poly1305::new(Key::from_slice(&key))
+        .decrypt(Nonce::from_slice(nonce), sealed)
+        .map_err(|_| {
+            anyhow::anyhow!("couldn't decrypt configuration, most likely wrong password")
+        })?;
+    String::from_utf8(plain)
+        .map_err(|_| anyhow::anyhow!("decrypted configuration is not valid UTF-8"))
+}
+
+/// 加密配置，输出格式与 `rclone config encryption set` 相同
+pub fn encrypt(plain: &str, password: &str) -> anyhow::Result<String> {
+    let key = config_key(password)?;
+    let nonce: [u8; NONCE_LEN] = rand::random();
+    let sealed = XSalsa20Poly1305::new(Key::from_slice(&key))
+        .encrypt(Nonce::from_slice(&nonce), plain.as_bytes())
+        .map_err(|_| anyhow::anyhow!("failed to encrypt configuration"))?;
+    let mut data = nonce.to_vec();
+    data.extend_from_slice(&sealed);
+    Ok(format!(
+        "{}{}\n{}\n",
+        ENCRYPTED_FILE_HEADER,
+        ENCRYPT_V0,
+        STANDARD.encode(data)
+    ))
+}
+
+/// 按 rclone 的 INI 格式输出
+pub fn serialize(remotes: &[ConfRemote]) -> String {
+    let mut out = String::new();
+    for remote in remotes {
+        out.push_str(&format!("[{}]\n", remote.name));
+        for (key, value) in &remote.options {
+            out.push_str(&format!("{} = {}\n", key, value));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// rclone 默认的配置文件位置，返回第一个存在的
+pub fn default_path(app: &tauri::AppHandle<Runtime>) -> Option<PathBuf> {
+    let mut candidates = Vec::new();
+    if let Ok(path) = std::env::var("RCLONE_CONFIG") {
+        candidates.push(PathBuf::from(path));
+    }
+    #[cfg(target_os = "windows")]
+    if let Ok(dir) = std::env::var("APPDATA") {
+        candidates.push(Path::new(&dir).join("rclone").join("rclone.conf"));
+    }
+    if let Ok(dir) = std::env::var("XDG_CONFIG_HOME") {
+        candidates.push(Path::new(&dir).join("rclone").join("rclone.conf"));
+    }
+    for path in ["~/.config/rclone/rclone.conf", "~/.rclone.conf"] {
+        if let Ok(path) = crate::fs::resolve_path(app, path) {
+            candidates.push(path);
+        }
+    }
+    candidates.into_iter().find(|p| p.is_file())
+}
+
+/// 读取配置文件；加密且未提供密码时返回 `Ok(None)`
+pub fn load(path: &Path, password: Option<&str>) -> anyhow::Result<Option<Vec<ConfRemote>>> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
+    if !is_encrypted(&content) {
+        return Ok(Some(parse(&content)));
+    }
+    match password.filter(|p| !p.is_empty()) {
+        Some(password) => Ok(Some(parse(&decrypt(&content, password)?))),
+        None => Ok(None),
+    }
+}
+
+/// 名称冲突时的处理方式
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConflictPolicy {
+    /// 追加序号后导入
+    #[default]
+    Rename,
+    /// 不导入
+    Skip,
+}
+
+/// 预览中的 remote（不包含选项的值，避免把密钥传给前端）
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfRemotePreview {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub remote_type: String,
+    /// 除 `type` 外的选项名
+    pub keys: Vec<String>,
+    /// 与已有存储重名
+    pub conflict: bool,
+    /// 按重命名策略导入时使用的名称
+    pub rename_to: String,
+    /// 引用的其它 remote
+    pub references: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfPreview {
+    pub path: PathBuf,
+    pub encrypted: bool,
+    /// 配置已加密且需要密码
+    pub locked: bool,
+    pub remotes: Vec<ConfRemotePreview>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportOptions {
+    /// 配置文件路径，为空时使用 rclone 的默认位置
+    #[serde(default)]
+    pub path: Option<String>,
+    #[serde(default)]
+    pub password: Option<String>,
+    /// 要导入的 remote，为空时导入全部
+    #[serde(default)]
+    pub names: Vec<String>,
+    #[serde(default)]
+    pub on_conflict: ConflictPolicy,
+    /// 前端已有的存储名称（如 OpenList 存储），同样视为冲突
+    #[serde(default)]
+    pub reserved: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportedRemote {
+    pub name: String,
+    pub imported_as: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportFailure {
+    pub name: String,
+    pub error: String,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportReport {
+    pub imported: Vec<ImportedRemote>,
+    /// 因重名跳过的 remote
+    pub skipped: Vec<String>,
+    pub failed: Vec<ImportFailure>,
+}
+
+fn resolve_conf_path(
+    app: &tauri::AppHandle<Runtime>,
+    path: Option<&str>,
+) -> anyhow::Result<PathBuf> {
+    match path.map(str::trim).filter(|p| !p.is_empty()) {
+        Some(path) => crate::fs::resolve_path(app, path),
+        None => default_path(app)
+            .ok_or_else(|| anyhow::anyhow!("No rclone.conf found, please choose the file")),
+    }
+}
+
+/// NetMount 中已占用的名称
+async fn taken_names(reserved: &[String]) -> anyhow::Result<Vec<String>> {
+    let mut taken = list_remotes().await?;
+    taken.push(OPENLIST_REMOTE.to_string());
+    taken.extend(reserved.iter().cloned());
+    Ok(taken)
+}
+
+/// 追加 `-2`、`-3`…… 直到不与 `taken` 重名
+fn free_name(name: &str, taken: &[String]) -> Option<String> {
+    (2..=MAX_SUFFIX)
+        .map(|n| format!("{}-{}", name, n))
+        .find(|candidate| !taken.iter().any(|t| t == candidate))
+}
+
+/// 预览配置文件中的 remote 及其与已有存储的冲突
+pub async fn preview(
+    app: &tauri::AppHandle<Runtime>,
+    path: Option<&str>,
+    password: Option<&str>,
+    reserved: &[String],
+) -> anyhow::Result<ConfPreview> {
+    let path = resolve_conf_path(app, path)?;
+    let encrypted = std::fs::read_to_string(&path)
+        .map(|content| is_encrypted(&content))
+        .unwrap_or(false);
+    let Some(remotes) = load(&path, password)? else {
+        return Ok(ConfPreview {
+            path,
+            encrypted,
+            locked: true,
+            remotes: Vec::new(),
+        });
+    };
+
+    let mut taken = taken_names(reserved).await?;
+    let mut previews = Vec::with_capacity(remotes.len());
+    for remote in &remotes {
+        let conflict = taken.iter().any(|t| t == &remote.name);
+        let rename_to = if conflict {
+            free_name(&remote.name, &taken).unwrap_or_default()
+        } else {
+            remote.name.clone()
+        };
+        // 预留名称，后续 remote 的重命名不会与之重复
+        taken.push(rename_to.clone());
+        previews.push(ConfRemotePreview {
+            name: remote.name.clone(),
+            remote_type: remote.remote_type().unwrap_or_default().to_string(),
+            keys: remote
+                .options
+                .iter()
+                .filter(|(k, _)| k != "type")
+                .map(|(k, _)| k.clone())
+                .collect(),
+            conflict,
+            rename_to,
+            references: remote.references(),
+        });
+    }
+    Ok(ConfPreview {
+        path,
+        encrypted,
+        locked: false,
+        remotes: previews,
+    })
+}
+
+/// 把引用了已重命名 remote 的选项改为新名称
+fn rewrite_references(options: &mut [(String, String)], renamed: &HashMap<String, String>) {
+    for (key, value) in options.iter_mut() {
+        if !REFERENCE_KEYS.contains(&key.as_str()) {
+            continue;
+        }
+        let rewritten: Vec<String> = value
+            .split_whitespace()
+            .map(|token| match split_reference(token) {
+                Some((prefix, name, rest)) if renamed.contains_key(name) => {
+                    format!("{}{}{}", prefix, renamed[name], rest)
+                }
+                _ => token.to_string(),
+            })
+            .collect();
+        *value = rewritten.join(" ");
+    }
+}
+
+/// 导入选中的 remote
+pub async fn import(
+    app: &tauri::AppHandle<Runtime>,
+    opts: &ImportOptions,
+) -> anyhow::Result<ImportReport> {
+    let path = resolve_conf_path(app, opts.path.as_deref())?;
+    let remotes = load(&path, opts.password.as_deref())?.ok_or_else(|| {
+        anyhow::anyhow!("{} is encrypted, a password is required", path.display())
+    })?;
+    let selected: Vec<&ConfRemote> = remotes
+        .iter()
+        .filter(|r| opts.names.is_empty() || opts.names.contains(&r.name))
+        .collect();
+
+    let mut report = ImportReport::default();
+    let mut taken = taken_names(&opts.reserved).await?;
+    // 先确定全部名称，以便改写相互之间的引用
+    let mut plan: Vec<(&ConfRemote, String)> = Vec::new();
+    let mut renamed = HashMap::new();
+    for remote in selected {
+        let target = if !taken.iter().any(|t| t == &remote.name) {
+            remote.name.clone()
+        } else if opts.on_conflict == ConflictPolicy::Skip {
+            report.skipped.push(remote.name.clone());
+            continue;
+        } else {
+            match free_name(&remote.name, &taken) {
+                Some(name) => {
+                    renamed.insert(remote.name.clone(), name.clone());
+                    name
+                }
+                None => {
+                    report.failed.push(ImportFailure {
+                        name: remote.name.clone(),
+                        error: "No free name available".to_string(),
+                    });
+                    continue;
+                }
+            }
+        };
+        taken.push(target.clone());
+        plan.push((remote, target));
+    }
+
+    for (remote, target) in plan {
+        let Some(remote_type) = remote.remote_type() else {
+            report.failed.push(ImportFailure {
+                name: remote.name.clone(),
+                error: "Missing remote type".to_string(),
+            });
+            continue;
+        };
+        let mut options = remote.options.clone();
+        rewrite_references(&mut options, &renamed);
+        let parameters: Map<String, Value> = options
+            .into_iter()
+            .filter(|(k, _)| k != "type")
+            .map(|(k, v)| (k, Value::String(v)))
+            .collect();
+        let body = json!({
+            "name": target,
+            "type": remote_type,
+            "parameters": parameters,
+            "opt": { "nonInteractive": true, "noObscure": true },
+        });
+        match rc_post("config/create", body).await {
+            Ok(_) => report.imported.push(ImportedRemote {
+                name: remote.name.clone(),
+                imported_as: target,
+            }),
+            Err(e) => report.failed.push(ImportFailure {
+                name: remote.name.clone(),
+                error: e.to_string(),
+            }),
+        }
+    }
+    Ok(report)
+}
+
+/// 导出时密钥的处理方式
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SecretPolicy {
+    /// 保持 rclone 配置中的混淆值
+    #[default]
+    Keep,
+    /// 还原为明文
+    Reveal,
+    /// 移除密码与令牌等敏感选项
+    Strip,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportOptions {
+    /// 输出文件路径
+    pub path: String,
+    /// 要导出的存储，为空时导出全部 rclone 存储
+    #[serde(default)]
+    pub names: Vec<String>,
+    #[serde(default)]
+    pub secrets: SecretPolicy,
+    /// 非空时使用该密码加密输出
+    #[serde(default)]
+    pub password: Option<String>,
+}
+
+/// 无法导出的存储
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportSkip {
+    pub name: String,
+    /// `openlist`：OpenList 存储；`dependsOnOpenlist`：引用了 OpenList 存储
+    pub reason: String,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportReport {
+    pub path: PathBuf,
+    pub exported: Vec<String>,
+    pub unsupported: Vec<ExportSkip>,
+    /// 被移除的选项，形如 `remote.key`
+    pub stripped: Vec<String>,
+    pub encrypted: bool,
+}
+
+/// 各后端的密码选项与敏感选项名
+#[derive(Default)]
+struct SecretKeys {
+    passwords: HashMap<String, HashSet<String>>,
+    sensitive: HashMap<String, HashSet<String>>,
+}
+
+impl SecretKeys {
+    async fn load() -> anyhow::Result<Self> {
+        let res = rc_post("config/providers", json!({})).await?;
+        let mut keys = Self::default();
+        for provider in res["providers"].as_array().into_iter().flatten() {
+            let Some(prefix) = provider["Prefix"].as_str() else {
+                continue;
+            };
+            for option in provider["Options"].as_array().into_iter().flatten() {
+                let Some(name) = option["Name"].as_str() else {
+                    continue;
+                };
+                if option["IsPassword"].as_bool() == Some(true) {
+                    keys.passwords
+                        .entry(prefix.to_string())
+                        .or_default()
+                        .insert(name.to_string());
+                } else if option["Sensitive"].as_bool() == Some(true) {
+                    keys.sensitive
+                        .entry(prefix.to_string())
+                        .or_default()
+                        .insert(name.to_string());
+                }
+            }
+        }
+        Ok(keys)
+    }
+
+    fn is_password(&self, remote_type: &str, key: &str) -> bool {
+        self.passwords
+            .get(remote_type)
+            .is_some_and(|keys| keys.contains(key))
+    }
+
+    fn is_sensitive(&self, remote_type: &str, key: &str) -> bool {
+        self.sensitive
+            .get(remote_type)
+            .is_some_and(|keys| keys.contains(key))
+    }
+}
+
+/// 读取 NetMount 的 rclone 配置（`config/dump`），选项值为字符串，`type` 排在最前
+async fn dump() -> anyhow::Result<Vec<ConfRemote>> {
+    let res = rc_post("config/dump", json!({})).await?;
+    let Some(map) = res.as_object() else {
+        return Ok(Vec::new());
+    };
+    Ok(map
+        .iter()
+        .map(|(name, options)| {
+            let mut options: Vec<(String, String)> = options
+                .as_object()
+                .into_iter()
+                .flatten()
+                .map(|(k, v)| {
+                    let v = match v {
+                        Value::String(s) => s.clone(),
+                        other => other.to_string(),
+                    };
+                    (k.clone(), v)
+                })
+                .collect();
+            options.sort_by_key(|(k, _)| k != "type");
+            ConfRemote {
+                name: name.clone(),
+                options,
+            }
+        })
+        .collect())
+}
+
+/// 按策略处理密钥，返回被移除的选项名
+fn apply_secret_policy(
+    remote: &mut ConfRemote,
+    policy: SecretPolicy,
+    keys: &SecretKeys,
+) -> anyhow::Result<Vec<String>> {
+    let remote_type = remote.remote_type().unwrap_or_default().to_string();
+    let mut stripped = Vec::new();
+    match policy {
+        SecretPolicy::Keep => {}
+        SecretPolicy::Reveal => {
+            for (key, value) in remote.options.iter_mut() {
+                if keys.is_password(&remote_type, key) && !value.is_empty() {
+                    *value = obscure::reveal(value).map_err(|e| {
+                        anyhow::anyhow!("Failed to reveal {}.{}: {}", remote.name, key, e)
+                    })?;
+                }
+            }
+        }
+        SecretPolicy::Strip => {
+            remote.options.retain(|(key, _)| {
+                let secret =
+                    keys.is_password(&remote_type, key) || keys.is_sensitive(&remote_type, key);
+                if secret {
+                    stripped.push(key.clone());
+                }
+                !secret
+            });
+        }
+    }
+    Ok(stripped)
+}
+
+/// 写入配置文件；Unix 上仅所有者可读写
+pub(crate) fn write_private(path: &Path, content: &str) -> anyhow::Result<()> {
+    use std::io::Write;
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(path)
+        .map_err(|e| anyhow::anyhow!("Failed to write {}: {}", path.display(), e))?;
+    file.write_all(content.as_bytes())?;
+    Ok(())
+}
+
+/// 导出选中的存储为独立的 rclone.conf
+pub async fn export(
+    app: &tauri::AppHandle<Runtime>,
+    opts: &ExportOptions,
+) -> anyhow::Result<ExportReport> {
+    let path = crate::fs::resolve_path(app, opts.path.trim())?;
+    let remotes = dump().await?;
+    let keys = match opts.secrets {
+        SecretPolicy::Keep => SecretKeys::default(),
+        _ => SecretKeys::load().await?,
+    };
+
+    let mut report = ExportReport {
+        path: path.clone(),
+        ..Default::default()
+    };
+    // 不在 rclone 配置中的存储来自 OpenList，rclone.conf 无法表示
+    for name in &opts.names {
+        if name == OPENLIST_REMOTE || !remotes.iter().any(|r| &r.name == name) {
+            report.unsupported.push(ExportSkip {
+                name: name.clone(),
+                reason: "openlist".to_string(),
+            });
+        }
+    }
+
+    let mut selected = Vec::new();
+    for mut remote in remotes {
+        if remote.name == OPENLIST_REMOTE
+            || (!opts.names.is_empty() && !opts.names.contains(&remote.name))
+        {
+            continue;
+        }
+        // 引用 OpenList 的 remote（如加密存储）离开 NetMount 后无法访问
+        if remote.references().iter().any(|r| r == OPENLIST_REMOTE) {
+            report.unsupported.push(ExportSkip {
+                name: remote.name.clone(),
+                reason: "dependsOnOpenlist".to_string(),
+            });
+            continue;
+        }
+        let stripped = apply_secret_policy(&mut remote, opts.secrets, &keys)?;
+        report.stripped.extend(
+            stripped
+                .into_iter()
+                .map(|k| format!("{}.{}", remote.name, k)),
+        );
+        report.exported.push(remote.name.clone());
+        selected.push(remote);
+    }
+
+    let mut content = serialize(&selected);
+    if let Some(password) = opts.password.as_deref().filter(|p| !p.is_empty()) {
+        content = encrypt(&content, password)?;
+        report.encrypted = true;
+    }
+    write_private(&path, &content)?;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\u{feff}# comment
+; another comment
+[gdrive]
+type = drive
+client_id = \"abc\"
+token = {\"access_token\":\"x=y\"}
+
+[secret]
+type = crypt
+remote = gdrive:encrypted
+password = 'obscured'
+
+[gdrive]
+client_id = def
+";
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn parse_merges_sections_and_unquotes() {
+        let remotes = parse(SAMPLE);
+        assert_eq!(remotes.len(), 2);
+        assert_eq!(remotes[0].name, "gdrive");
+        assert_eq!(remotes[0].remote_type(), Some("drive"));
+        // 后出现的同名选项覆盖前面的，值中的 `=` 保留
+        assert_eq!(remotes[0].get("client_id"), Some("def"));
+        assert_eq!(remotes[0].get("token"), Some("{\"access_token\":\"x=y\"}"));
+        assert_eq!(remotes[1].get("password"), Some("obscured"));
+        assert_eq!(remotes[1].references(), ["gdrive"]);
+    }
+
+    #[test]
+    fn serialize_round_trips() {
+        let remotes = parse(SAMPLE);
+        let again = parse(&serialize(&remotes));
+        assert_eq!(again.len(), remotes.len());
+        for (a, b) in remotes.iter().zip(&again) {
+            assert_eq!(a.name, b.name);
+            assert_eq!(a.options, b.options);
+        }
+    }
+
+    #[test]
+    fn split_reference_handles_prefixes_and_local_paths() {
+        assert_eq!(
+            split_reference("gdrive:backup/dir"),
+            Some(("", "gdrive", ":backup/dir"))
+        );
+        assert_eq!(split_reference("gdrive:"), Some(("", "gdrive", ":")));
+        assert_eq!(
+            split_reference("docs=s3:bucket/docs"),
+            Some(("docs=", "s3", ":bucket/docs"))
+        );
+        // `=` 出现在 `:` 之后属于路径
+        assert_eq!(split_reference("s3:a=b"), Some(("", "s3", ":a=b")));
+        assert_eq!(split_reference("/local/path"), None);
+        assert_eq!(split_reference(":local:path"), None);
+    }
+
+    #[test]
+    fn union_references_are_deduplicated() {
+        let remote = ConfRemote {
+            name: "u".to_string(),
+            options: vec![
+                ("type".to_string(), "union".to_string()),
+                (
+                    "upstreams".to_string(),
+                    "a:x b:y:ro /local a:z docs=c:".to_string(),
+                ),
+            ],
+        };
+        assert_eq!(remote.references(), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn config_key_matches_rclone_derivation() {
+        // sha256("[potato][rclone-config]")
+        assert_eq!(
+            hex(&config_key("potato").unwrap()),
+            "df766fc0ba637a7eb773d712316657dee1f033275f2fa228f4cfa735eb048d2f"
+        );
+        // 全角字符经 NFKC 规范化为 `pass`
+        assert_eq!(
+            hex(&config_key("ｐａｓｓ").unwrap()),
+            "7161eabcdf4870552ab36046b13b349f1e5ae87e8338cec261be23926d569246"
+        );
+        assert!(config_key(" \t").is_err());
+    }
+
+    #[test]
+    fn encrypt_decrypt_round_trip() {
+        let plain = serialize(&parse(SAMPLE));
+        let encrypted = encrypt(&plain, "potato").unwrap();
+        assert!(encrypted.starts_with(ENCRYPTED_FILE_HEADER));
+        assert!(is_encrypted(&encrypted));
+        assert!(!is_encrypted(SAMPLE));
+        assert_eq!(decrypt(&encrypted, "potato").unwrap(), plain);
+        assert!(decrypt(&encrypted, "tomato")
+            .unwrap_err()
+            .to_string()
+            .contains("wrong password"));
+
+        // base64 折行后仍可解密
+        let (head, body) = encrypted.split_once(ENCRYPT_V0).unwrap();
+        let body: String = body
+            .trim()
+            .as_bytes()
+            .chunks(40)
+            .map(|c| format!("{}\n", String::from_utf8_lossy(c)))
+            .collect();
+        let wrapped = format!("{}{}\n{}", head, ENCRYPT_V0, body);
+        assert_eq!(decrypt(&wrapped, "potato").unwrap(), plain);
+    }
+
+    #[test]
+    fn decrypt_rejects_bad_input() {
+        assert!(decrypt("RCLONE_ENCRYPT_V1:\nAAAA\n", "p")
+            .unwrap_err()
+            .to_string()
+            .contains("unsupported"));
+        assert!(decrypt("RCLONE_ENCRYPT_V0:\n!!!\n", "p").is_err());
+        assert!(decrypt("RCLONE_ENCRYPT_V0:\nAAAA\n", "p")
+            .unwrap_err()
+            .to_string()
+            .contains("too short"));
+    }
+}
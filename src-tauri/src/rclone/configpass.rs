@@ -0,0 +1,241 @@
+//! NetMount 自身 rclone 配置的加密（`RCLONE_CONFIG_PASS`）。
+//!
+//! 密码只保存在内存中：由前端在需要时提示用户输入，启动 rclone 时通过环境变量传入，
+//! 后端读取配置（诊断等）时用它解密。设置、修改、移除密码时 rclone 必须已停止，
+//! 否则 rclone 会用旧的密钥写回配置。
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use super::conffile;
+use crate::sidecar;
+
+const ENV_PASS: &str = "RCLONE_CONFIG_PASS";
+
+lazy_static::lazy_static! {
+    /// NetMount 启动时环境中已有的密码同样可用
+    static ref CONFIG_PASS: Mutex<Option<String>> =
+        Mutex::new(std::env::var(ENV_PASS).ok().filter(|p| !p.is_empty()));
+}
+
+/// 加密状态
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigEncryption {
+    pub encrypted: bool,
+    /// 内存中已有可用的密码
+    pub unlocked: bool,
+}
+
+/// NetMount 使用的 rclone 配置文件
+pub fn config_path() -> PathBuf {
+    super::endpoint()
+        .and_then(|ep| ep.config)
+        .unwrap_or_else(|| crate::resolve_data_dir().join("rclone.conf"))
+}
+
+pub fn password() -> Option<String> {
+    CONFIG_PASS.lock().unwrap().clone()
+}
+
+fn set_password(password: Option<String>) {
+    *CONFIG_PASS.lock().unwrap() = password.filter(|p| !p.is_empty());
+}
+
+fn read(path: &Path) -> anyhow::Result<String> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => Ok(content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+        Err(e) => Err(anyhow::anyhow!("Failed to read {}: {}", path.display(), e)),
+    }
+}
+
+pub fn is_encrypted() -> bool {
+    is_encrypted_at(&config_path())
+}
+
+fn is_encrypted_at(path: &Path) -> bool {
+    read(path).is_ok_and(|content| conffile::is_encrypted(&content))
+}
+
+pub fn status() -> ConfigEncryption {
+    status_at(&config_path(), password().as_deref())
+}
+
+fn status_at(path: &Path, password: Option<&str>) -> ConfigEncryption {
+    ConfigEncryption {
+        encrypted: is_encrypted_at(path),
+        unlocked: password.is_some(),
+    }
+}
+
+/// 读取配置内容，加密时用内存中的密码解密
+pub fn read_config() -> anyhow::Result<String> {
+    read_config_at(&config_path(), password().as_deref())
+}
+
+fn read_config_at(path: &Path, password: Option<&str>) -> anyhow::Result<String> {
+    let content = read(path)?;
+    if !conffile::is_encrypted(&content) {
+        return Ok(content);
+    }
+    let password =
+        password.ok_or_else(|| anyhow::anyhow!("rclone config is encrypted and locked"))?;
+    conffile::decrypt(&content, password)
+}
+
+/// 验证密码能解密配置后保存在内存中
+pub fn unlock(password: &str) -> anyhow::Result<()> {
+    let content = read(&config_path())?;
+    if conffile::is_encrypted(&content) {
+        conffile::decrypt(&content, password)?;
+    }
+    set_password(Some(password.to_string()));
+    Ok(())
+}
+
+/// 设置、修改或移除配置密码；`new_password` 为空时保存为明文
+pub fn change(
+    current: Option<&str>,
+    new_password: Option<&str>,
+) -> anyhow::Result<ConfigEncryption> {
+    if sidecar::get_sidecar_pid("rclone").is_some_and(sidecar::process_alive) {
+        return Err(anyhow::anyhow!(
+            "Stop rclone before changing the config password"
+        ));
+    }
+    let current = current
+        .filter(|p| !p.is_empty())
+        .map(str::to_string)
+        .or_else(password);
+    let new_password = new_password.filter(|p| !p.is_empty());
+    rewrite(&config_path(), current.as_deref(), new_password)?;
+    set_password(new_password.map(str::to_string));
+    Ok(status())
+}
+
+/// 用 `current` 解密配置后以 `new_password` 重新加密（为 None 时保存为明文）
+fn rewrite(path: &Path, current: Option<&str>, new_password: Option<&str>) -> anyhow::Result<()> {
+    let content = read(path)?;
+    let plain = if conffile::is_encrypted(&content) {
+        let password =
+            current.ok_or_else(|| anyhow::anyhow!("The current config password is required"))?;
+        conffile::decrypt(&content, password)?
+    } else {
+        content
+    };
+    let out = match new_password {
+        Some(password) => conffile::encrypt(&plain, password)?,
+        None => plain,
+    };
+
+    // 先写入临时文件再替换，避免中途失败时丢失配置
+    let tmp = path.with_extension("conf.tmp");
+    conffile::write_private(&tmp, &out)?;
+    std::fs::rename(&tmp, path)
+        .map_err(|e| anyhow::anyhow!("Failed to replace {}: {}", path.display(), e))?;
+    Ok(())
+}
+
+/// 启动 rclone 前检查配置能否解密，并通过环境变量传入密码
+pub fn apply_env(cmd: &mut std::process::Command) -> anyhow::Result<()> {
+    match password() {
+        Some(password) => {
+            cmd.env(ENV_PASS, password);
+        }
+        None if is_encrypted() => {
+            return Err(anyhow::anyhow!(
+                "rclone config is encrypted, a config password is required"
+            ));
+        }
+        None => {
+            cmd.env_remove(ENV_PASS);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PLAIN: &str = "[gdrive]\ntype = drive\ntoken = abc\n";
+
+    fn temp_config(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "netmount-configpass-{}-{}",
+            name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("rclone.conf");
+        std::fs::write(&path, PLAIN).unwrap();
+        path
+    }
+
+    #[test]
+    fn rewrite_encrypts_rekeys_and_decrypts() {
+        let path = temp_config("change");
+
+        rewrite(&path, None, Some("one")).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(conffile::is_encrypted(&content));
+        assert_eq!(conffile::decrypt(&content, "one").unwrap(), PLAIN);
+
+        // 更换密码需要当前密码
+        assert!(rewrite(&path, None, Some("two")).is_err());
+        assert!(rewrite(&path, Some("wrong"), Some("two")).is_err());
+        rewrite(&path, Some("one"), Some("two")).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(conffile::decrypt(&content, "one").is_err());
+        assert_eq!(conffile::decrypt(&content, "two").unwrap(), PLAIN);
+
+        rewrite(&path, Some("two"), None).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), PLAIN);
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn rewrite_replaces_atomically() {
+        let path = temp_config("atomic");
+        rewrite(&path, None, Some("one")).unwrap();
+        let encrypted = std::fs::read_to_string(&path).unwrap();
+
+        // 解密失败时原配置保持不变
+        assert!(rewrite(&path, Some("wrong"), None).is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), encrypted);
+        // 临时文件已被替换掉
+        assert!(!path.with_extension("conf.tmp").exists());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn locked_config() {
+        let path = temp_config("locked");
+        assert!(!status_at(&path, None).encrypted);
+        assert_eq!(read_config_at(&path, None).unwrap(), PLAIN);
+
+        rewrite(&path, None, Some("one")).unwrap();
+        let status = status_at(&path, None);
+        assert!(status.encrypted && !status.unlocked);
+        assert!(read_config_at(&path, None).is_err());
+        assert!(read_config_at(&path, Some("wrong")).is_err());
+        assert!(status_at(&path, Some("one")).unlocked);
+        assert_eq!(read_config_at(&path, Some("one")).unwrap(), PLAIN);
+
+        // 配置文件不存在时视为空的明文配置
+        let missing = path.with_file_name("missing.conf");
+        assert!(!status_at(&missing, None).encrypted);
+        assert_eq!(read_config_at(&missing, None).unwrap(), "");
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+}
@@ -0,0 +1,368 @@
+//! 后端侧的 rclone rc 客户端。
+//!
+//! 前端通过 `spawn_sidecar` 启动 `rclone rcd`，这里从启动参数中记下 rc 地址（回环地址或 Unix 套接字），凭据由后端从配置中注入，
+//! 使后端可以直接调用 rc 接口（任务执行、挂载管理等），无需经过 webview。
+
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+use crate::config::Credentials;
+
+pub mod cleanup;
+pub mod conffile;
+pub mod configpass;
+pub mod obscure;
+
+/// OpenList 存储在 rclone 中对应的 remote 名称（与前端 `openlistInfo.markInRclone` 一致）
+pub const OPENLIST_REMOTE: &str = ".netmount-openlist.";
+
+/// 轮询异步 job 状态的间隔
+const JOB_POLL_INTERVAL: Duration = Duration::from_millis(1000);
+
+#[derive(Clone, Debug)]
+pub struct RcEndpoint {
+    pub url: String,
+    /// rc 监听的 Unix 套接字，此时 `url` 只用于构造请求路径
+    pub socket: Option<PathBuf>,
+    pub user: String,
+    pub pass: String,
+    /// `--log-file` 指定的 rclone 日志文件
+    pub log_file: Option<PathBuf>,
+    /// rclone 可执行文件，用于单独启动 rclone 命令
+    pub binary: PathBuf,
+    /// `--config` 指定的配置文件
+    pub config: Option<PathBuf>,
+}
+
+lazy_static::lazy_static! {
+    static ref RC_ENDPOINT: Mutex<Option<RcEndpoint>> = Mutex::new(None);
+}
+
+/// 通过环境变量传入 rc 凭据（`--rc-user`/`--rc-pass`），避免出现在进程参数与日志中
+pub fn apply_rc_env(cmd: &mut std::process::Command, credentials: &Credentials) {
+    if credentials.user.is_empty() {
+        cmd.env_remove("RCLONE_RC_USER").env_remove("RCLONE_RC_PASS");
+    } else {
+        cmd.env("RCLONE_RC_USER", &credentials.user)
+            .env("RCLONE_RC_PASS", &credentials.password);
+    }
+}
+
+/// 从 `rclone rcd` 的启动参数中解析并记录 rc 地址，凭据由后端传入
+pub fn register_endpoint(binary: &Path, args: &[String], credentials: Credentials) {
+    let mut addr = None;
+    let mut log_file = None;
+    let mut config = None;
+    for arg in args {
+        if let Some(v) = arg.strip_prefix("--rc-addr=") {
+            addr = Some(v.to_string());
+        } else if let Some(v) = arg.strip_prefix("--log-file=") {
+            log_file = Some(PathBuf::from(v));
+        } else if let Some(v) = arg.strip_prefix("--config=") {
+            config = Some(PathBuf::from(v));
+        }
+    }
+
+    let Some(addr) = addr else {
+        return;
+    };
+    // 监听地址已由 `bind::secure_rclone_args` 限制为回环地址或 Unix 套接字
+    let (url, socket) = match addr.strip_prefix("unix://") {
+        Some(path) => ("http://localhost".to_string(), Some(PathBuf::from(path))),
+        None if addr.starts_with(':') => (format!("http://127.0.0.1{}", addr), None),
+        None => (format!("http://{}", addr), None),
+    };
+    *RC_ENDPOINT.lock().unwrap() = Some(RcEndpoint {
+        url,
+        socket,
+        user: credentials.user,
+        pass: credentials.password,
+        log_file,
+        binary: binary.to_path_buf(),
+        config,
+    });
+}
+
+impl RcEndpoint {
+    /// 访问 rc 的 HTTP 客户端（监听 Unix 套接字时所有请求都经过该套接字）
+    pub fn client(&self) -> reqwest::Client {
+        let builder = reqwest::Client::builder();
+        #[cfg(unix)]
+        let builder = match &self.socket {
+            Some(socket) => builder.unix_socket(socket.clone()),
+            None => builder,
+        };
+        builder.build().unwrap_or_default()
+    }
+}
+
+/// 当前记录的 rc 地址（rclone 未启动时为 None）
+pub fn endpoint() -> Option<RcEndpoint> {
+    RC_ENDPOINT.lock().unwrap().clone()
+}
+
+/// rclone 退出后清除记录的 rc 地址
+pub fn clear_endpoint() {
+    *RC_ENDPOINT.lock().unwrap() = None;
+}
+
+/// 构造使用同一可执行文件与配置文件的 rclone 命令（如 `--dry-run`、`check` 等需要解析输出的场景）
+pub fn command(args: &[String]) -> anyhow::Result<std::process::Command> {
+    let ep = endpoint().ok_or_else(|| anyhow::anyhow!("rclone is not running"))?;
+    let mut cmd = std::process::Command::new(&ep.binary);
+    cmd.args(args).current_dir(crate::resolve_data_dir());
+    if let Some(config) = &ep.config {
+        cmd.arg(format!("--config={}", config.display()));
+    }
+    configpass::apply_env(&mut cmd)?;
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        use winapi::um::winbase::CREATE_NO_WINDOW;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+    Ok(cmd)
+}
+
+/// 调用 rc 接口，返回响应 JSON；非 2xx 时返回 rclone 给出的错误信息
+pub async fn rc_post(path: &str, body: Value) -> anyhow::Result<Value> {
+    let ep = endpoint().ok_or_else(|| anyhow::anyhow!("rclone is not running"))?;
+    let url = format!("{}/{}", ep.url, path.trim_start_matches('/'));
+
+    let mut req = ep.client().post(url).json(&body);
+    if !ep.user.is_empty() {
+        req = req.basic_auth(&ep.user, Some(&ep.pass));
+    }
+    let res = req.send().await?;
+    let status = res.status();
+    let value: Value = res.json().await.unwrap_or(Value::Null);
+
+    if !status.is_success() {
+        let msg = value
+            .get("error")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| status.to_string());
+        return Err(anyhow::anyhow!("rclone {} failed: {}", path, msg));
+    }
+    Ok(value)
+}
+
+/// 异步 job 的最终结果
+#[derive(Clone, Debug)]
+pub struct JobOutcome {
+    /// rc 异步 job ID，单独启动 rclone 命令执行时为空
+    pub job_id: Option<u64>,
+    pub success: bool,
+    pub error: String,
+}
+
+/// 以 `_async` 方式启动 rc 调用，并把统计归入 `group`，返回 job ID
+pub async fn start_job(path: &str, mut body: Value, group: &str) -> anyhow::Result<u64> {
+    body["_async"] = json!(true);
+    body["_group"] = json!(group);
+    let res = rc_post(path, body).await?;
+    res.get("jobid")
+        .and_then(|v| v.as_u64())
+        .ok_or_else(|| anyhow::anyhow!("rclone {} did not return a job id", path))
+}
+
+/// 轮询 `job/status` 直到 job 结束
+pub async fn wait_job(job_id: u64) -> anyhow::Result<JobOutcome> {
+    loop {
+        let status = rc_post("job/status", json!({ "jobid": job_id })).await?;
+        if status.get("finished").and_then(|v| v.as_bool()) == Some(true) {
+            return Ok(JobOutcome {
+                job_id: Some(job_id),
+                success: status.get("success").and_then(|v| v.as_bool()) == Some(true),
+                error: status
+                    .get("error")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default()
+                    .to_string(),
+            });
+        }
+        tokio::time::sleep(JOB_POLL_INTERVAL).await;
+    }
+}
+
+/// 启动 job 并等待其结束
+pub async fn run_job(path: &str, body: Value, group: &str) -> anyhow::Result<JobOutcome> {
+    let job_id = start_job(path, body, group).await?;
+    wait_job(job_id).await
+}
+
+/// 获取某个统计分组的 `core/stats`
+pub async fn group_stats(group: &str) -> anyhow::Result<Value> {
+    rc_post("core/stats", json!({ "group": group })).await
+}
+
+/// 某个统计分组中已完成（含失败）的传输对象名称
+pub async fn group_transferred(group: &str) -> anyhow::Result<Vec<String>> {
+    let res = rc_post("core/transferred", json!({ "group": group })).await?;
+    Ok(res
+        .get("transferred")
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.get("name").and_then(|n| n.as_str()))
+                .filter(|n| !n.is_empty())
+                .map(|n| n.to_string())
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+/// 已配置的 rclone remote 名称列表
+pub async fn list_remotes() -> anyhow::Result<Vec<String>> {
+    let res = rc_post("config/listremotes", json!({})).await?;
+    Ok(res
+        .get("remotes")
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+/// 与前端 `formatPathRclone` 一致：统一斜杠并去掉首尾的 `/`
+pub fn format_path(path: &str) -> String {
+    let mut out = String::with_capacity(path.len());
+    for c in path.chars() {
+        let c = if c == '\\' { '/' } else { c };
+        if c == '/' && out.ends_with('/') {
+            continue;
+        }
+        out.push(c);
+    }
+    out.trim_matches('/').to_string()
+}
+
+/// NetMount 存储在 rclone 中的位置：rclone 存储直接对应 remote，
+/// OpenList 存储挂在统一的 OpenList remote 之下
+#[derive(Clone, Debug)]
+pub struct StorageRef {
+    /// `remote:` 形式的 fs 根
+    pub root: String,
+    /// 存储在 remote 内的前缀（仅 OpenList 存储非空）
+    pub prefix: String,
+}
+
+impl StorageRef {
+    pub fn resolve(remotes: &[String], storage_name: &str) -> Self {
+        if remotes.iter().any(|r| r == storage_name) {
+            Self {
+                root: format!("{}:", storage_name),
+                prefix: String::new(),
+            }
+        } else {
+            Self {
+                root: format!("{}:", OPENLIST_REMOTE),
+                prefix: storage_name.to_string(),
+            }
+        }
+    }
+
+    /// 存储内路径对应的 remote 路径（相对于 `root`）
+    pub fn remote(&self, path: &str) -> String {
+        let path = format_path(path);
+        match (self.prefix.is_empty(), path.is_empty()) {
+            (true, _) => path,
+            (false, true) => self.prefix.clone(),
+            (false, false) => format!("{}/{}", self.prefix, path),
+        }
+    }
+
+    /// 存储内路径对应的完整 fs 字符串
+    pub fn fs(&self, path: &str) -> String {
+        format!("{}{}", self.root, self.remote(path))
+    }
+}
+
+/// 取路径最后一段作为文件名
+pub fn file_name(path: &str) -> &str {
+    path.rsplit(['/', '\\']).next().unwrap_or_default()
+}
+
+/// 拼接 remote 路径
+pub fn join_remote(dir: &str, name: &str) -> String {
+    if dir.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}/{}", dir, name)
+    }
+}
+
+/// 本地混淆密码（等同 `rclone obscure`），无需 rclone 运行
+#[tauri::command]
+pub fn rclone_obscure(value: String) -> anyhow_tauri::TAResult<String> {
+    Ok(obscure::obscure(&value))
+}
+
+/// 诊断用：值是否看起来已被 rclone 混淆
+#[tauri::command]
+pub fn rclone_looks_obscured(value: String) -> bool {
+    obscure::looks_obscured(&value)
+}
+
+/// 预览 rclone.conf 中的 remote，`path` 为空时使用 rclone 的默认位置
+#[tauri::command]
+pub async fn preview_rclone_conf(
+    app: tauri::AppHandle<crate::Runtime>,
+    path: Option<String>,
+    password: Option<String>,
+    reserved: Option<Vec<String>>,
+) -> anyhow_tauri::TAResult<conffile::ConfPreview> {
+    Ok(conffile::preview(
+        &app,
+        path.as_deref(),
+        password.as_deref(),
+        &reserved.unwrap_or_default(),
+    )
+    .await?)
+}
+
+/// 把 rclone.conf 中选中的 remote 导入 NetMount 的 rclone 配置
+#[tauri::command]
+pub async fn import_rclone_conf(
+    app: tauri::AppHandle<crate::Runtime>,
+    options: conffile::ImportOptions,
+) -> anyhow_tauri::TAResult<conffile::ImportReport> {
+    Ok(conffile::import(&app, &options).await?)
+}
+
+/// 把 NetMount 的存储导出为独立的 rclone.conf
+#[tauri::command]
+pub async fn export_rclone_conf(
+    app: tauri::AppHandle<crate::Runtime>,
+    options: conffile::ExportOptions,
+) -> anyhow_tauri::TAResult<conffile::ExportReport> {
+    Ok(conffile::export(&app, &options).await?)
+}
+
+/// NetMount 的 rclone 配置是否加密、是否已输入密码
+#[tauri::command]
+pub fn get_rclone_config_encryption() -> configpass::ConfigEncryption {
+    configpass::status()
+}
+
+/// 输入配置密码（仅保存在内存中），启动 rclone 前调用
+#[tauri::command]
+pub fn unlock_rclone_config(password: String) -> anyhow_tauri::TAResult<()> {
+    Ok(configpass::unlock(&password)?)
+}
+
+/// 设置、修改或移除配置密码，需先停止 rclone
+#[tauri::command]
+pub fn set_rclone_config_password(
+    current: Option<String>,
+    password: Option<String>,
+) -> anyhow_tauri::TAResult<configpass::ConfigEncryption> {
+    Ok(configpass::change(current.as_deref(), password.as_deref())?)
+}
@@ -0,0 +1,131 @@
+//! rclone `obscure` / `reveal` 的本地实现，无需启动 rclone 即可处理配置中的密码。
+//!
+//! 与 rclone `fs/config/obscure` 一致：AES-256-CTR，固定密钥，随机 16 字节 IV，
+//! 输出为 `base64url(IV || 密文)`（无填充）。这只是混淆，不提供任何安全性。
+//!
+//! rclone 自带的测试向量（IV 固定时）：
+//!
+//! | 明文     | IV                   | 结果                              |
+//! |----------|----------------------|-----------------------------------|
+//! | ``       | `aaaaaaaaaaaaaaaa`   | `YWFhYWFhYWFhYWFhYWFhYQ`          |
+//! | `potato` | `aaaaaaaaaaaaaaaa`   | `YWFhYWFhYWFhYWFhYWFhYXMaGgIlEQ`  |
+//! | `potato` | `bbbbbbbbbbbbbbbb`   | `YmJiYmJiYmJiYmJiYmJiYp3gcEWbAw`  |
+
+use aes::cipher::{KeyIvInit, StreamCipher};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+
+type Aes256Ctr = ctr::Ctr128BE<aes::Aes256>;
+
+/// rclone 内置的混淆密钥
+const CRYPT_KEY: [u8; 32] = [
+    0x9c, 0x93, 0x5b, 0x48, 0x73, 0x0a, 0x55, 0x4d, 0x6b, 0xfd, 0x7c, 0x63, 0xc8, 0x86, 0xa9, 0x2b,
+    0xd3, 0x90, 0x19, 0x8e, 0xb8, 0x12, 0x8a, 0xfb, 0xf4, 0xde, 0x16, 0x2b, 0x8b, 0x95, 0xf6, 0x38,
+];
+const IV_LEN: usize = 16;
+
+fn crypt(data: &mut [u8], iv: &[u8; IV_LEN]) {
+    let mut cipher = Aes256Ctr::new(&CRYPT_KEY.into(), iv.into());
+    cipher.apply_keystream(data);
+}
+
+/// 使用指定 IV 混淆，结果可复现
+pub fn obscure_with_iv(plain: &str, iv: &[u8; IV_LEN]) -> String {
+    let mut buf = Vec::with_capacity(IV_LEN + plain.len());
+    buf.extend_from_slice(iv);
+    buf.extend_from_slice(plain.as_bytes());
+    crypt(&mut buf[IV_LEN..], iv);
+    URL_SAFE_NO_PAD.encode(buf)
+}
+
+/// 与 `rclone obscure` 相同：每次使用随机 IV
+pub fn obscure(plain: &str) -> String {
+    let iv: [u8; IV_LEN] = rand::random();
+    obscure_with_iv(plain, &iv)
+}
+
+/// 与 `rclone reveal` 相同，错误信息也与 rclone 一致
+pub fn reveal(obscured: &str) -> anyhow::Result<String> {
+    let mut buf = URL_SAFE_NO_PAD.decode(obscured.as_bytes()).map_err(|e| {
+        anyhow::anyhow!(
+            "base64 decode failed when revealing password - is it obscured?: {}",
+            e
+        )
+    })?;
+    if buf.len() < IV_LEN {
+        return Err(anyhow::anyhow!(
+            "input too short when revealing password - is it obscured?"
+        ));
+    }
+    let (iv, data) = buf.split_at_mut(IV_LEN);
+    let iv: [u8; IV_LEN] = (&*iv).try_into()?;
+    crypt(data, &iv);
+    String::from_utf8(data.to_vec())
+        .map_err(|_| anyhow::anyhow!("revealed password is not valid UTF-8 - is it obscured?"))
+}
+
+/// 值是否看起来已被混淆：能按 rclone 格式解码，且还原结果为不含控制字符的文本。
+///
+/// 混淆结果本身也是合法的 base64url，短的明文可能误判，只用于诊断提示。
+pub fn looks_obscured(value: &str) -> bool {
+    // IV 编码后至少 22 个字符
+    if value.len() < 22 {
+        return false;
+    }
+    reveal(value).is_ok_and(|plain| !plain.chars().any(|c| c.is_control()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// rclone `fs/config/obscure` 的测试向量：(明文, 结果, IV)
+    const VECTORS: [(&str, &str, &[u8; IV_LEN]); 3] = [
+        ("", "YWFhYWFhYWFhYWFhYWFhYQ", b"aaaaaaaaaaaaaaaa"),
+        (
+            "potato",
+            "YWFhYWFhYWFhYWFhYWFhYXMaGgIlEQ",
+            b"aaaaaaaaaaaaaaaa",
+        ),
+        (
+            "potato",
+            "YmJiYmJiYmJiYmJiYmJiYp3gcEWbAw",
+            b"bbbbbbbbbbbbbbbb",
+        ),
+    ];
+
+    #[test]
+    fn matches_rclone_vectors() {
+        for (plain, obscured, iv) in VECTORS {
+            assert_eq!(obscure_with_iv(plain, iv), obscured);
+            assert_eq!(reveal(obscured).unwrap(), plain);
+        }
+    }
+
+    #[test]
+    fn obscure_round_trips_with_random_iv() {
+        for plain in ["", "potato", "p@ss wörd/+=", "密码"] {
+            let a = obscure(plain);
+            let b = obscure(plain);
+            assert_ne!(a, b);
+            assert_eq!(reveal(&a).unwrap(), plain);
+            assert_eq!(reveal(&b).unwrap(), plain);
+        }
+    }
+
+    #[test]
+    fn reveal_rejects_invalid_input() {
+        assert!(reveal("not base64!").is_err());
+        assert!(reveal("YWFh").is_err());
+        // 标准 base64 的 `+` `/` 不是 rclone 使用的编码
+        assert!(reveal("YWFhYWFhYWFhYWFhYWFhYQ+/").is_err());
+    }
+
+    #[test]
+    fn looks_obscured_checks_format() {
+        assert!(looks_obscured(&obscure("secret")));
+        assert!(looks_obscured("YWFhYWFhYWFhYWFhYWFhYXMaGgIlEQ"));
+        assert!(!looks_obscured("secret"));
+        assert!(!looks_obscured("short"));
+    }
+}
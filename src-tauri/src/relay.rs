@@ -0,0 +1,510 @@
+//! webview 到 rclone / OpenList 的请求中转。
+//!
+//! rc 密码与 OpenList 管理员密码只保存在后端：前端通过 `relay_request` 发起请求，
+//! 这里注入 Basic 认证或 OpenList token 后转发到本机服务，`get_config` 返回的配置中也不含这些密码。
+//! 文件上传需要流式传输，前端分块写入临时文件，完成后由 rclone 从本地复制到目标存储。
+
+use std::collections::HashMap;
+use std::io::Write as _;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use rand::distr::SampleString as _;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::config::{Config, Credentials};
+use crate::rclone::{self, OPENLIST_REMOTE};
+use crate::{AppExt, Runtime};
+
+/// 未指定超时的请求默认等待时间（rc 同步调用可能较慢）
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(300);
+const OPENLIST_LOGIN_TIMEOUT: Duration = Duration::from_secs(15);
+/// 上传临时文件目录（位于数据目录下）
+const UPLOAD_DIR: &str = "upload-temp";
+/// 允许经中转调用的 rclone rc 接口（前端用到的接口），`core/command`、`config/update` 等不在其中
+const RCLONE_PATHS: &[&str] = &[
+    "rc/noop",
+    "core/version",
+    "core/stats",
+    "core/quit",
+    "config/providers",
+    "config/dump",
+    "config/get",
+    "config/create",
+    "config/delete",
+    "job/status",
+    "job/stop",
+    "mount/mount",
+    "mount/unmount",
+    "mount/listmounts",
+    "operations/about",
+    "operations/copyfile",
+    "operations/movefile",
+    "operations/deletefile",
+    "operations/list",
+    "operations/mkdir",
+    "operations/purge",
+    "sync/copy",
+    "sync/move",
+    "sync/sync",
+    "sync/bisync",
+    "vfs/forget",
+    "vfs/refresh",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Framework {
+    Rclone,
+    Openlist,
+}
+
+/// 转发结果，前端据此构造 `Response`
+#[derive(Clone, Debug, Serialize)]
+pub struct RelayResponse {
+    pub status: u16,
+    pub body: String,
+}
+
+struct Upload {
+    fs: String,
+    remote: String,
+    file: PathBuf,
+}
+
+lazy_static::lazy_static! {
+    /// OpenList 地址（由启动参数中的数据目录读取端口）
+    static ref OPENLIST_URL: Mutex<Option<String>> = Mutex::new(None);
+    static ref OPENLIST_TOKEN: Mutex<Option<String>> = Mutex::new(None);
+    static ref UPLOADS: Mutex<HashMap<String, Upload>> = Mutex::new(HashMap::new());
+}
+
+fn credentials(app: &tauri::AppHandle<Runtime>, framework: &str) -> Credentials {
+    app.with_app_state::<Config, _>(|config| config.credentials(framework))
+}
+
+/// `openlist server --data <dir>` 的数据目录，未指定时为工作目录
+pub fn openlist_data_dir(args: &[String], work_dir: &std::path::Path) -> PathBuf {
+    args.iter()
+        .position(|arg| arg == "--data")
+        .and_then(|i| args.get(i + 1))
+        .map(PathBuf::from)
+        .unwrap_or_else(|| work_dir.to_path_buf())
+}
+
+/// 从 `openlist server --data <dir>` 的启动参数定位 config.json，记录 OpenList 地址
+pub fn register_openlist(args: &[String], work_dir: &std::path::Path) {
+    let data_dir = openlist_data_dir(args, work_dir);
+    let port = std::fs::read_to_string(data_dir.join("config.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+        .and_then(|config| config.pointer("/scheme/http_port").and_then(Value::as_u64));
+
+    *OPENLIST_TOKEN.lock().unwrap() = None;
+    *OPENLIST_URL.lock().unwrap() = port.map(|port| format!("http://127.0.0.1:{}", port));
+}
+
+/// OpenList 退出后清除记录的地址与 token
+pub fn clear_openlist() {
+    *OPENLIST_URL.lock().unwrap() = None;
+    clear_openlist_token();
+}
+
+/// OpenList 管理员密码变更后丢弃缓存的 token
+pub fn clear_openlist_token() {
+    *OPENLIST_TOKEN.lock().unwrap() = None;
+}
+
+fn openlist_url() -> anyhow::Result<String> {
+    OPENLIST_URL
+        .lock()
+        .unwrap()
+        .clone()
+        .ok_or_else(|| anyhow::anyhow!("OpenList is not running"))
+}
+
+async fn openlist_login(app: &tauri::AppHandle<Runtime>) -> anyhow::Result<String> {
+    let creds = credentials(app, "openlist");
+    let res = reqwest::Client::new()
+        .post(format!("{}/api/auth/login", openlist_url()?))
+        .timeout(OPENLIST_LOGIN_TIMEOUT)
+        .json(&json!({ "username": creds.user, "password": creds.password }))
+        .send()
+        .await?;
+    let status = res.status();
+    let value: Value = res.json().await.unwrap_or(Value::Null);
+    // 旧版本直接返回字符串 token
+    let token = match value.get("data") {
+        Some(Value::String(token)) => Some(token.clone()),
+        Some(data) => data.get("token").and_then(Value::as_str).map(str::to_string),
+        None => None,
+    };
+    match token {
+        Some(token) if status.is_success() => {
+            *OPENLIST_TOKEN.lock().unwrap() = Some(token.clone());
+            Ok(token)
+        }
+        _ => Err(anyhow::anyhow!(
+            "OpenList login failed: HTTP {} {}",
+            status,
+            value.get("message").and_then(Value::as_str).unwrap_or_default()
+        )),
+    }
+}
+
+async fn openlist_token(app: &tauri::AppHandle<Runtime>) -> anyhow::Result<String> {
+    let cached = OPENLIST_TOKEN.lock().unwrap().clone();
+    match cached {
+        Some(token) => Ok(token),
+        None => openlist_login(app).await,
+    }
+}
+
+/// 只允许转发到服务根路径下，避免拼接出其它主机
+fn check_path(path: &str) -> anyhow::Result<()> {
+    if !path.starts_with('/') || path.starts_with("//") {
+        return Err(anyhow::anyhow!("Invalid relay path: {}", path));
+    }
+    Ok(())
+}
+
+async fn send(req: reqwest::RequestBuilder) -> anyhow::Result<RelayResponse> {
+    let res = req.send().await?;
+    Ok(RelayResponse {
+        status: res.status().as_u16(),
+        body: res.text().await.unwrap_or_default(),
+    })
+}
+
+/// OpenList 在 token 失效时返回 HTTP 200 与业务码 401
+fn is_unauthorized(res: &RelayResponse) -> bool {
+    res.status == 401
+        || serde_json::from_str::<Value>(&res.body)
+            .is_ok_and(|v| v.get("code").and_then(Value::as_u64) == Some(401))
+}
+
+/// 校验 rclone rc 路径并返回接口名：不允许查询参数，接口须在允许列表中
+fn rclone_op(path: &str) -> anyhow::Result<&str> {
+    let op = path.trim_matches('/');
+    if path.contains(['?', '#']) || !RCLONE_PATHS.contains(&op) {
+        return Err(anyhow::anyhow!("rclone rc path is not allowed: {}", path));
+    }
+    Ok(op)
+}
+
+/// 是否为 OpenList remote 的名称（rclone 接受首尾空白与末尾的 `:`）
+fn is_openlist_remote(name: &str) -> bool {
+    name.trim().trim_end_matches(':').trim() == OPENLIST_REMOTE
+}
+
+/// 请求体中的 `name` 参数是否指向 OpenList remote
+fn names_openlist(request: Option<&str>) -> bool {
+    request
+        .and_then(|body| serde_json::from_str::<Value>(body).ok())
+        .is_some_and(|body| body["name"].as_str().is_some_and(is_openlist_remote))
+}
+
+/// OpenList remote 由后端维护，不允许经中转创建、覆盖或删除
+fn check_rclone_request(op: &str, request: Option<&str>) -> anyhow::Result<()> {
+    if op.starts_with("config/")
+        && op != "config/get"
+        && op != "config/dump"
+        && names_openlist(request)
+    {
+        return Err(anyhow::anyhow!(
+            "{} is managed by NetMount",
+            OPENLIST_REMOTE
+        ));
+    }
+    Ok(())
+}
+
+/// 去掉 rclone 配置中 OpenList WebDAV remote 的密码（即 OpenList 管理员密码）
+fn redact_rclone(op: &str, request: Option<&str>, res: &mut RelayResponse) {
+    let dump = match op {
+        "config/dump" => true,
+        "config/get" if names_openlist(request) => false,
+        _ => return,
+    };
+    let Ok(mut value) = serde_json::from_str::<Value>(&res.body) else {
+        return;
+    };
+    let Some(object) = value.as_object_mut() else {
+        return;
+    };
+    let mut changed = false;
+    if dump {
+        for (_, remote) in object
+            .iter_mut()
+            .filter(|(name, _)| is_openlist_remote(name))
+        {
+            if let Some(remote) = remote.as_object_mut() {
+                changed |= remote.remove("pass").is_some();
+            }
+        }
+    } else {
+        changed = object.remove("pass").is_some();
+    }
+    if changed {
+        res.body = value.to_string();
+    }
+}
+
+/// 转发请求并注入凭据
+#[tauri::command]
+pub async fn relay_request(
+    app: tauri::AppHandle<Runtime>,
+    framework: Framework,
+    method: String,
+    path: String,
+    body: Option<String>,
+    timeout_ms: Option<u64>,
+) -> anyhow_tauri::TAResult<RelayResponse> {
+    check_path(&path)?;
+    let method = reqwest::Method::from_bytes(method.to_ascii_uppercase().as_bytes())
+        .map_err(|_| anyhow::anyhow!("Invalid HTTP method: {}", method))?;
+    let timeout = timeout_ms.map_or(DEFAULT_TIMEOUT, Duration::from_millis);
+    let build = |client: &reqwest::Client, url: String| {
+        let mut req = client.request(method.clone(), url).timeout(timeout);
+        if let Some(body) = &body {
+            req = req
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(body.clone());
+        }
+        req
+    };
+
+    match framework {
+        Framework::Rclone => {
+            let op = rclone_op(&path)?;
+            check_rclone_request(op, body.as_deref())?;
+            let ep = rclone::endpoint().ok_or_else(|| anyhow::anyhow!("rclone is not running"))?;
+            let mut req = build(&ep.client(), format!("{}/{}", ep.url, op));
+            if !ep.user.is_empty() {
+                req = req.basic_auth(&ep.user, Some(&ep.pass));
+            }
+            let mut res = send(req).await?;
+            redact_rclone(op, body.as_deref(), &mut res);
+            Ok(res)
+        }
+        Framework::Openlist => {
+            let url = format!("{}{}", openlist_url()?, path);
+            // 启动时就绪检查的 ping 不需要登录，避免反复登录失败触发 OpenList 的登录限制
+            let token = match path.as_str() {
+                "/ping" => None,
+                _ => Some(openlist_token(&app).await?),
+            };
+            let client = reqwest::Client::new();
+            let mut req = build(&client, url.clone());
+            if let Some(token) = &token {
+                req = req.header(reqwest::header::AUTHORIZATION, token);
+            }
+            let res = send(req).await?;
+            if token.is_none() || !is_unauthorized(&res) {
+                return Ok(res);
+            }
+            // 修改用户后旧 token 会失效，重新登录后重试一次
+            *OPENLIST_TOKEN.lock().unwrap() = None;
+            let token = openlist_login(&app).await?;
+            Ok(send(build(&client, url).header(reqwest::header::AUTHORIZATION, token)).await?)
+        }
+    }
+}
+
+/// 重新登录 OpenList（启动、重置密码或修改用户后调用，旧 token 可能已失效）
+#[tauri::command]
+pub async fn relay_openlist_login(app: tauri::AppHandle<Runtime>) -> anyhow_tauri::TAResult<()> {
+    *OPENLIST_TOKEN.lock().unwrap() = None;
+    openlist_login(&app).await?;
+    Ok(())
+}
+
+/// 开始上传：创建临时文件，返回上传 ID
+#[tauri::command]
+pub fn relay_upload_begin(fs: String, remote: String) -> anyhow_tauri::TAResult<String> {
+    let dir = crate::resolve_data_dir().join(UPLOAD_DIR);
+    std::fs::create_dir_all(&dir)?;
+    let id = rand::distr::Alphanumeric.sample_string(&mut rand::rng(), 16);
+    let file = dir.join(&id);
+    std::fs::File::create(&file)?;
+    UPLOADS
+        .lock()
+        .unwrap()
+        .insert(id.clone(), Upload { fs, remote, file });
+    Ok(id)
+}
+
+/// 追加一块数据；请求体为原始字节，`upload-id` 头指定上传 ID
+#[tauri::command]
+pub fn relay_upload_chunk(request: tauri::ipc::Request<'_>) -> anyhow_tauri::TAResult<()> {
+    let id = request
+        .headers()
+        .get("upload-id")
+        .and_then(|v| v.to_str().ok())
+        .ok_or_else(|| anyhow::anyhow!("Missing upload-id header"))?;
+    let tauri::ipc::InvokeBody::Raw(data) = request.body() else {
+        return Err(anyhow::anyhow!("Upload chunk must be raw bytes").into());
+    };
+    let file = UPLOADS
+        .lock()
+        .unwrap()
+        .get(id)
+        .map(|upload| upload.file.clone())
+        .ok_or_else(|| anyhow::anyhow!("Unknown upload: {}", id))?;
+    std::fs::OpenOptions::new()
+        .append(true)
+        .open(&file)?
+        .write_all(data)?;
+    Ok(())
+}
+
+/// 完成上传：由 rclone 把临时文件复制到目标存储
+#[tauri::command]
+pub async fn relay_upload_finish(id: String) -> anyhow_tauri::TAResult<()> {
+    let upload = UPLOADS
+        .lock()
+        .unwrap()
+        .remove(&id)
+        .ok_or_else(|| anyhow::anyhow!("Unknown upload: {}", id))?;
+    let dir = upload.file.parent().unwrap_or(&upload.file);
+    let result = rclone::rc_post(
+        "operations/copyfile",
+        json!({
+            "srcFs": dir.to_string_lossy(),
+            "srcRemote": id,
+            "dstFs": upload.fs,
+            "dstRemote": upload.remote,
+        }),
+    )
+    .await;
+    let _ = std::fs::remove_file(&upload.file);
+    result?;
+    Ok(())
+}
+
+/// 取消上传并删除临时文件
+#[tauri::command]
+pub fn relay_upload_abort(id: String) {
+    if let Some(upload) = UPLOADS.lock().unwrap().remove(&id) {
+        let _ = std::fs::remove_file(&upload.file);
+    }
+}
+
+/// 在 rclone 中创建指向 OpenList WebDAV 的 remote，密码由后端填入
+#[tauri::command]
+pub async fn relay_add_openlist_remote(
+    app: tauri::AppHandle<Runtime>,
+) -> anyhow_tauri::TAResult<()> {
+    let url = format!("{}/dav", openlist_url()?);
+    let creds = credentials(&app, "openlist");
+    // 先删除旧配置，避免端口变化后仍指向旧地址
+    let _ = rclone::rc_post("config/delete", json!({ "name": OPENLIST_REMOTE })).await;
+    rclone::rc_post(
+        "config/create",
+        json!({
+            "name": OPENLIST_REMOTE,
+            "type": "webdav",
+            "parameters": {
+                "url": url,
+                "vendor": "other",
+                "user": creds.user,
+                "pass": creds.password,
+            },
+            "opt": { "obscure": true },
+        }),
+    )
+    .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(body: Value) -> RelayResponse {
+        RelayResponse {
+            status: 200,
+            body: body.to_string(),
+        }
+    }
+
+    fn body(res: &RelayResponse) -> Value {
+        serde_json::from_str(&res.body).unwrap()
+    }
+
+    #[test]
+    fn rclone_paths_are_allowlisted() {
+        assert_eq!(rclone_op("/config/dump").unwrap(), "config/dump");
+        assert_eq!(rclone_op("sync/copy").unwrap(), "sync/copy");
+        assert_eq!(rclone_op("/operations/list/").unwrap(), "operations/list");
+        assert!(rclone_op("/core/command").is_err());
+        assert!(rclone_op("/config/update").is_err());
+        assert!(rclone_op("/config/listremotes").is_err());
+        assert!(rclone_op("/config/dump?x=1").is_err());
+        assert!(rclone_op("/config/get#frag").is_err());
+        assert!(rclone_op("/../config/dump").is_err());
+    }
+
+    #[test]
+    fn openlist_remote_writes_are_rejected() {
+        let named = |name: &str| json!({ "name": name, "type": "webdav" }).to_string();
+        for name in [
+            ".netmount-openlist.",
+            " .netmount-openlist.: ",
+            ".netmount-openlist.:",
+        ] {
+            assert!(check_rclone_request("config/create", Some(&named(name))).is_err());
+            assert!(check_rclone_request("config/delete", Some(&named(name))).is_err());
+        }
+        assert!(check_rclone_request("config/create", Some(&named("gdrive"))).is_ok());
+        assert!(check_rclone_request("config/get", Some(&named(".netmount-openlist."))).is_ok());
+        assert!(check_rclone_request("config/delete", None).is_ok());
+    }
+
+    #[test]
+    fn redacts_openlist_pass_from_dump() {
+        let mut res = response(json!({
+            ".netmount-openlist.": { "type": "webdav", "user": "admin", "pass": "secret" },
+            "gdrive": { "type": "drive", "token": "t" },
+            "dav": { "type": "webdav", "pass": "mine" },
+        }));
+        redact_rclone("config/dump", None, &mut res);
+        let value = body(&res);
+        assert!(value[".netmount-openlist."].get("pass").is_none());
+        assert_eq!(value[".netmount-openlist."]["user"], "admin");
+        assert_eq!(value["dav"]["pass"], "mine");
+    }
+
+    #[test]
+    fn redacts_openlist_pass_from_get_by_any_name() {
+        for name in [
+            ".netmount-openlist.",
+            ".netmount-openlist.:",
+            " .netmount-openlist. ",
+        ] {
+            let mut res = response(json!({ "type": "webdav", "pass": "secret" }));
+            let request = json!({ "name": name }).to_string();
+            redact_rclone("config/get", Some(&request), &mut res);
+            assert!(body(&res).get("pass").is_none(), "{}", name);
+        }
+        let mut res = response(json!({ "type": "webdav", "pass": "mine" }));
+        redact_rclone("config/get", Some(r#"{"name":"dav"}"#), &mut res);
+        assert_eq!(body(&res)["pass"], "mine");
+    }
+
+    #[test]
+    fn redact_ignores_other_responses() {
+        let original = json!({ "pass": "x" });
+        let mut res = response(original.clone());
+        redact_rclone("operations/list", None, &mut res);
+        assert_eq!(body(&res), original);
+        let mut res = RelayResponse {
+            status: 500,
+            body: "not json".to_string(),
+        };
+        redact_rclone("config/dump", None, &mut res);
+        assert_eq!(res.body, "not json");
+    }
+}
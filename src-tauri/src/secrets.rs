@@ -0,0 +1,402 @@
+//! `config.json` 中敏感字段的静态加密。
+//!
+//! 加密后的字段保存为 `enc:v1:<base64url(nonce || 密文)>`，使用带认证的 XSalsa20-Poly1305
+//! （与 rclone 配置加密相同的 secretbox）。密钥默认来自数据目录中随机生成的 `config.key`，
+//! 也可改为由用户口令经 scrypt 派生；口令模式下盐与校验值保存在 `config.json` 的 `secrets` 中。
+//!
+//! 内存中的配置始终是明文，只在读写文件时转换，`write_app_config`/`get_config` 的行为不变；
+//! 读取到明文的敏感字段时自动加密写回。配置备份默认不包含 `config.key`：在本机恢复时沿用现有密钥，
+//! 在其它设备上恢复需要使用口令模式，或导出时明确选择附带密钥。
+
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use crypto_secretbox::aead::{Aead, KeyInit};
+use crypto_secretbox::{Key, Nonce, XSalsa20Poly1305};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+const PREFIX: &str = "enc:v1:";
+pub(crate) const KEY_FILE: &str = "config.key";
+/// 保存密钥来源的顶层字段，不出现在内存中的配置里
+const META_FIELD: &str = "secrets";
+/// 无界面启动（服务模式）时可通过该环境变量提供口令
+const ENV_PASSPHRASE: &str = "NETMOUNT_CONFIG_PASSPHRASE";
+/// 用于校验口令的已知明文
+const CHECK_PLAIN: &str = "netmount";
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 24;
+const SALT_LEN: usize = 16;
+/// scrypt 参数：N=2^15, r=8, p=1（约 32 MiB 内存）
+const SCRYPT_LOG_N: u8 = 15;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+
+/// 需要加密的字段（JSON Pointer）
+const SECRET_FIELDS: [&str; 5] = [
+    "/framework/rclone/password",
+    "/framework/openlist/password",
+    "/settings/proxy/username",
+    "/settings/proxy/password",
+    "/settings/security/startupPassword",
+];
+
+/// 密钥来源
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum KeySource {
+    /// 数据目录中的 `config.key`
+    #[default]
+    KeyFile,
+    /// 用户口令
+    Passphrase,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SecretsMeta {
+    #[serde(default)]
+    source: KeySource,
+    /// 口令模式的 scrypt 盐（base64url）
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    salt: String,
+    /// 口令模式下用密钥加密的 `CHECK_PLAIN`
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    check: String,
+}
+
+struct KeyState {
+    meta: SecretsMeta,
+    key: Option<[u8; KEY_LEN]>,
+}
+
+lazy_static::lazy_static! {
+    static ref STATE: Mutex<KeyState> = Mutex::new(KeyState {
+        meta: SecretsMeta::default(),
+        key: None,
+    });
+}
+
+/// 加密状态
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigEncryption {
+    pub key_source: KeySource,
+    /// 口令模式下尚未输入口令，敏感字段仍是密文
+    pub locked: bool,
+}
+
+pub fn is_sealed(value: &str) -> bool {
+    value.starts_with(PREFIX)
+}
+
+fn seal(key: &[u8; KEY_LEN], plain: &str) -> anyhow::Result<String> {
+    let nonce: [u8; NONCE_LEN] = rand::random();
+    let sealed = XSalsa20Poly1305::new(Key::from_slice(key))
+        .encrypt(Nonce::from_slice(&nonce), plain.as_bytes())
+        .map_err(|_| anyhow::anyhow!("Failed to encrypt config value"))?;
+    let mut data = nonce.to_vec();
+    data.extend_from_slice(&sealed);
+    Ok(format!("{}{}", PREFIX, URL_SAFE_NO_PAD.encode(data)))
+}
+
+fn open(key: &[u8; KEY_LEN], value: &str) -> anyhow::Result<String> {
+    let data = URL_SAFE_NO_PAD
+        .decode(value.trim_start_matches(PREFIX))
+        .map_err(|e| anyhow::anyhow!("Invalid encrypted config value: {}", e))?;
+    if data.len() < NONCE_LEN {
+        return Err(anyhow::anyhow!("Invalid encrypted config value: too short"));
+    }
+    let (nonce, sealed) = data.split_at(NONCE_LEN);
+    let plain = XSalsa20Poly1305::new(Key::from_slice(key))
+        .decrypt(Nonce::from_slice(nonce), sealed)
+        .map_err(|_| anyhow::anyhow!("Failed to decrypt config value, wrong key or passphrase"))?;
+    String::from_utf8(plain).map_err(|_| anyhow::anyhow!("Decrypted config value is not UTF-8"))
+}
+
+fn key_file_path() -> PathBuf {
+    crate::resolve_data_dir().join(KEY_FILE)
+}
+
+/// 读取 `config.key`；不存在且 `create` 为 true 时生成新密钥
+fn load_key_file(create: bool) -> anyhow::Result<[u8; KEY_LEN]> {
+    let path = key_file_path();
+    match std::fs::read_to_string(&path) {
+        Ok(content) => {
+            let bytes = URL_SAFE_NO_PAD
+                .decode(content.trim())
+                .map_err(|e| anyhow::anyhow!("Invalid {}: {}", path.display(), e))?;
+            bytes
+                .try_into()
+                .map_err(|_| anyhow::anyhow!("Invalid {}: wrong key length", path.display()))
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound && create => {
+            let key: [u8; KEY_LEN] = rand::random();
+            crate::rclone::conffile::write_private(&path, &URL_SAFE_NO_PAD.encode(key))?;
+            Ok(key)
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Err(anyhow::anyhow!(
+            "{} is missing, encrypted settings cannot be read",
+            path.display()
+        )),
+        Err(e) => Err(anyhow::anyhow!("Failed to read {}: {}", path.display(), e)),
+    }
+}
+
+fn derive_key(passphrase: &str, salt: &str) -> anyhow::Result<[u8; KEY_LEN]> {
+    let salt = URL_SAFE_NO_PAD
+        .decode(salt)
+        .map_err(|e| anyhow::anyhow!("Invalid passphrase salt: {}", e))?;
+    let params = scrypt::Params::new(SCRYPT_LOG_N, SCRYPT_R, SCRYPT_P, KEY_LEN)
+        .map_err(|e| anyhow::anyhow!("Invalid scrypt parameters: {}", e))?;
+    let mut key = [0u8; KEY_LEN];
+    scrypt::scrypt(passphrase.as_bytes(), &salt, &params, &mut key)
+        .map_err(|e| anyhow::anyhow!("Failed to derive key: {}", e))?;
+    Ok(key)
+}
+
+/// 口令模式：派生密钥并用校验值验证口令
+fn unlock_key(meta: &SecretsMeta, passphrase: &str) -> anyhow::Result<[u8; KEY_LEN]> {
+    let key = derive_key(passphrase, &meta.salt)?;
+    match open(&key, &meta.check) {
+        Ok(check) if check == CHECK_PLAIN => Ok(key),
+        _ => Err(anyhow::anyhow!("Wrong config passphrase")),
+    }
+}
+
+fn has_sealed_fields(config: &Value) -> bool {
+    SECRET_FIELDS
+        .iter()
+        .any(|ptr| matches!(config.pointer(ptr), Some(Value::String(s)) if is_sealed(s)))
+}
+
+/// 就地解密敏感字段，返回是否存在未加密的字段
+fn open_fields(config: &mut Value, key: &[u8; KEY_LEN]) -> anyhow::Result<bool> {
+    let mut plaintext = false;
+    for ptr in SECRET_FIELDS {
+        if let Some(Value::String(s)) = config.pointer_mut(ptr) {
+            if is_sealed(s) {
+                *s = open(key, s)?;
+            } else if !s.is_empty() {
+                plaintext = true;
+            }
+        }
+    }
+    Ok(plaintext)
+}
+
+/// 从文件读取配置后调用：取出 `secrets` 元数据并解密敏感字段。
+/// 返回 true 表示需要写回（存在明文字段，即旧配置的迁移）。口令模式且没有口令时保持密文。
+pub fn open_config(config: &mut Value) -> anyhow::Result<bool> {
+    let meta: SecretsMeta = config
+        .as_object_mut()
+        .and_then(|obj| obj.remove(META_FIELD))
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default();
+
+    let mut state = STATE.lock().unwrap();
+    state.meta = meta;
+    state.key = None;
+    let key = match state.meta.source {
+        KeySource::KeyFile => Some(load_key_file(!has_sealed_fields(config))?),
+        KeySource::Passphrase => match std::env::var(ENV_PASSPHRASE) {
+            Ok(passphrase) if !passphrase.is_empty() => Some(unlock_key(&state.meta, &passphrase)?),
+            _ => None,
+        },
+    };
+    state.key = key;
+    match key {
+        Some(key) => open_fields(config, &key),
+        None => Ok(false),
+    }
+}
+
+/// 写入文件前调用：返回加密了敏感字段并带有 `secrets` 元数据的副本
+pub fn seal_config(config: &Value) -> anyhow::Result<Value> {
+    let mut out = config.clone();
+    let mut state = STATE.lock().unwrap();
+    let key = match (state.key, state.meta.source) {
+        (Some(key), _) => key,
+        (None, KeySource::KeyFile) => {
+            let key = load_key_file(!has_sealed_fields(config))?;
+            state.key = Some(key);
+            key
+        }
+        (None, KeySource::Passphrase) => {
+            // 未解锁时只能原样写回密文，拒绝写入新的明文
+            let plaintext = SECRET_FIELDS.iter().any(|ptr| {
+                matches!(out.pointer(ptr), Some(Value::String(s)) if !s.is_empty() && !is_sealed(s))
+            });
+            if plaintext {
+                return Err(anyhow::anyhow!(
+                    "Config is locked, enter the passphrase before saving secrets"
+                ));
+            }
+            out[META_FIELD] = serde_json::to_value(&state.meta)?;
+            return Ok(out);
+        }
+    };
+    seal_fields(&mut out, &key)?;
+    out[META_FIELD] = serde_json::to_value(&state.meta)?;
+    Ok(out)
+}
+
+/// 就地加密尚未加密的敏感字段
+fn seal_fields(config: &mut Value, key: &[u8; KEY_LEN]) -> anyhow::Result<()> {
+    for ptr in SECRET_FIELDS {
+        if let Some(Value::String(s)) = config.pointer_mut(ptr) {
+            if !s.is_empty() && !is_sealed(s) {
+                *s = seal(key, s)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn status() -> ConfigEncryption {
+    let state = STATE.lock().unwrap();
+    ConfigEncryption {
+        key_source: state.meta.source,
+        locked: state.key.is_none(),
+    }
+}
+
+/// 输入口令并解密内存中的配置
+pub fn unlock(config: &mut Value, passphrase: &str) -> anyhow::Result<()> {
+    let mut state = STATE.lock().unwrap();
+    if state.key.is_some() {
+        return Ok(());
+    }
+    let key = unlock_key(&state.meta, passphrase)?;
+    open_fields(config, &key)?;
+    state.key = Some(key);
+    Ok(())
+}
+
+/// 切换密钥来源：`passphrase` 非空时改用口令，否则改用新生成的 `config.key`。
+/// 只更新内存中的密钥，调用方随后需写回配置以重新加密，再调用 [`remove_stale_key_file`]。
+pub fn set_passphrase(passphrase: Option<&str>) -> anyhow::Result<()> {
+    let mut state = STATE.lock().unwrap();
+    if state.key.is_none() {
+        return Err(anyhow::anyhow!(
+            "Config is locked, enter the current passphrase first"
+        ));
+    }
+    match passphrase.filter(|p| !p.is_empty()) {
+        Some(passphrase) => {
+            let salt: [u8; SALT_LEN] = rand::random();
+            let salt = URL_SAFE_NO_PAD.encode(salt);
+            let key = derive_key(passphrase, &salt)?;
+            state.meta = SecretsMeta {
+                source: KeySource::Passphrase,
+                check: seal(&key, CHECK_PLAIN)?,
+                salt,
+            };
+            state.key = Some(key);
+        }
+        None if state.meta.source == KeySource::KeyFile => {}
+        None => {
+            // 磁盘上的配置仍由口令加密，残留的旧密钥文件可以直接替换
+            let _ = std::fs::remove_file(key_file_path());
+            state.key = Some(load_key_file(true)?);
+            state.meta = SecretsMeta::default();
+        }
+    }
+    Ok(())
+}
+
+/// 配置已用口令重新加密后删除不再需要的 `config.key`
+pub fn remove_stale_key_file() {
+    if STATE.lock().unwrap().meta.source == KeySource::Passphrase {
+        let _ = std::fs::remove_file(key_file_path());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn legacy_config() -> Value {
+        json!({
+            "framework": {
+                "rclone": { "user": "admin", "password": "rc-pass" },
+                "openlist": { "password": "" }
+            },
+            "settings": {
+                "proxy": { "username": "proxy-user", "password": "proxy-pass" },
+                "language": "en"
+            }
+        })
+    }
+
+    #[test]
+    fn seal_and_open_round_trip() {
+        let key: [u8; KEY_LEN] = rand::random();
+        let sealed = seal(&key, "secret").unwrap();
+        assert!(is_sealed(&sealed));
+        assert_ne!(sealed, seal(&key, "secret").unwrap());
+        assert_eq!(open(&key, &sealed).unwrap(), "secret");
+
+        let other: [u8; KEY_LEN] = rand::random();
+        assert!(open(&other, &sealed).is_err());
+        assert!(open(&key, "enc:v1:AAAA").is_err());
+        assert!(open(&key, "enc:v1:!!").is_err());
+    }
+
+    #[test]
+    fn legacy_plaintext_fields_are_migrated() {
+        let key: [u8; KEY_LEN] = rand::random();
+        let mut config = legacy_config();
+        assert!(!has_sealed_fields(&config));
+        // 旧配置中的明文字段需要写回加密
+        assert!(open_fields(&mut config.clone(), &key).unwrap());
+
+        seal_fields(&mut config, &key).unwrap();
+        assert!(has_sealed_fields(&config));
+        for ptr in [
+            "/framework/rclone/password",
+            "/settings/proxy/username",
+            "/settings/proxy/password",
+        ] {
+            assert!(is_sealed(config.pointer(ptr).unwrap().as_str().unwrap()));
+        }
+        // 空值与非敏感字段保持不变
+        assert_eq!(config["framework"]["openlist"]["password"], "");
+        assert_eq!(config["framework"]["rclone"]["user"], "admin");
+        assert_eq!(config["settings"]["language"], "en");
+
+        // 已加密的字段不会被重复加密
+        let sealed = config.clone();
+        seal_fields(&mut config, &key).unwrap();
+        assert_eq!(config, sealed);
+
+        assert!(!open_fields(&mut config, &key).unwrap());
+        assert_eq!(config, legacy_config());
+    }
+
+    #[test]
+    fn open_fields_fails_with_wrong_key() {
+        let key: [u8; KEY_LEN] = rand::random();
+        let mut config = legacy_config();
+        seal_fields(&mut config, &key).unwrap();
+        let other: [u8; KEY_LEN] = rand::random();
+        assert!(open_fields(&mut config, &other).is_err());
+    }
+
+    #[test]
+    fn passphrase_check_value() {
+        let salt = URL_SAFE_NO_PAD.encode([7u8; SALT_LEN]);
+        let key = derive_key("correct horse", &salt).unwrap();
+        let meta = SecretsMeta {
+            source: KeySource::Passphrase,
+            check: seal(&key, CHECK_PLAIN).unwrap(),
+            salt,
+        };
+        assert_eq!(unlock_key(&meta, "correct horse").unwrap(), key);
+        assert!(unlock_key(&meta, "wrong").is_err());
+        assert!(derive_key("x", "not base64!").is_err());
+    }
+}
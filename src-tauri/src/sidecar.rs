@@ -139,6 +139,36 @@ pub fn get_sidecar_pid(name: &str) -> Option<u32> {
     children.get(name).copied()
 }
 
+/// 检查进程是否仍在运行
+pub fn process_alive(pid: u32) -> bool {
+    #[cfg(target_os = "windows")]
+    unsafe {
+        use winapi::um::processthreadsapi::GetExitCodeProcess;
+        use winapi::um::winnt::PROCESS_QUERY_LIMITED_INFORMATION;
+        // GetExitCodeProcess 对仍在运行的进程返回 STILL_ACTIVE
+        const STILL_ACTIVE: u32 = 259;
+
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if process.is_null() {
+            return false;
+        }
+        let mut code = 0;
+        let ok = GetExitCodeProcess(process, &mut code);
+        CloseHandle(process);
+        ok != 0 && code == STILL_ACTIVE
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        use nix::sys::signal::kill;
+        // 信号 0 只检查进程是否存在；EPERM 表示进程存在但属于其它用户
+        match kill(nix::unistd::Pid::from_raw(pid as i32), None) {
+            Ok(()) => true,
+            Err(e) => e == nix::errno::Errno::EPERM,
+        }
+    }
+}
+
 /// 终止指定 sidecar 进程
 pub fn kill_sidecar(name: &str) -> bool {
     let pid = {
@@ -0,0 +1,457 @@
+//! 双向同步（`bisync`）状态管理。
+//!
+//! 每个任务使用独立的 workdir（`task/bisync/<任务名>-<哈希>/`）保存 rclone 的列表与锁文件，
+//! 并在 `state.json` 中记录是否需要 resync、最近一次错误等。运行前自动清理
+//! 无进程持有的锁；需要 resync 时拒绝直接运行，改由带预览的 resync 命令处理。
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+use super::plan::{self, TaskPlan};
+use super::{new_run_id, now_ms, queue, TaskSpec};
+use crate::rclone::{self, JobOutcome, StorageRef};
+
+/// resync 预览的有效期
+const PREVIEW_VALID_MS: u64 = 60 * 60 * 1000;
+
+/// rclone 支持的冲突处理策略（`--conflict-resolve`）
+const CONFLICT_POLICIES: [&str; 6] = ["none", "newer", "older", "larger", "path1", "path2"];
+
+lazy_static::lazy_static! {
+    static ref STATE_LOCK: Mutex<()> = Mutex::new(());
+}
+
+/// 持久化的单个任务状态
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BisyncState {
+    /// 上次运行报告需要 resync
+    pub needs_resync: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_run_at: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_resync_at: Option<u64>,
+}
+
+/// workdir 中的锁文件
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockInfo {
+    pub file: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pid: Option<u32>,
+    /// 是否仍被进程持有
+    pub held: bool,
+}
+
+/// 提供给前端的状态
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BisyncStatus {
+    pub task_name: String,
+    pub workdir: String,
+    /// workdir 中已有上次同步的列表
+    pub initialized: bool,
+    pub locks: Vec<LockInfo>,
+    #[serde(flatten)]
+    pub state: BisyncState,
+}
+
+/// 运行失败的原因分类
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Failure {
+    MustResync,
+    Locked,
+    Other,
+}
+
+fn classify(error: &str) -> Failure {
+    let lower = error.to_lowercase();
+    if lower.contains("must run --resync")
+        || lower.contains("cannot find prior path1 or path2 listings")
+    {
+        Failure::MustResync
+    } else if lower.contains("prior lock file found") || lower.contains(".lck") {
+        Failure::Locked
+    } else {
+        Failure::Other
+    }
+}
+
+fn base_dir() -> PathBuf {
+    crate::resolve_data_dir().join("task").join("bisync")
+}
+
+/// 任务名中的非法字符替换为 `_`
+fn sanitize_name(task_name: &str) -> String {
+    task_name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// workdir 名：替换后的任务名加原始任务名的哈希，避免 `a b` 与 `a_b` 共用目录
+fn workdir_name(task_name: &str) -> String {
+    let hash = Sha256::digest(task_name.as_bytes());
+    let suffix: String = hash[..8].iter().map(|b| format!("{:02x}", b)).collect();
+    format!("{}-{}", sanitize_name(task_name), suffix)
+}
+
+/// 任务的 workdir
+pub fn workdir(task_name: &str) -> PathBuf {
+    base_dir().join(workdir_name(task_name))
+}
+
+fn state_file() -> PathBuf {
+    base_dir().join("state.json")
+}
+
+fn read_states() -> HashMap<String, BisyncState> {
+    fs::read(state_file())
+        .ok()
+        .and_then(|c| serde_json::from_slice(&c).ok())
+        .unwrap_or_default()
+}
+
+fn load_state(task_name: &str) -> BisyncState {
+    let _guard = STATE_LOCK.lock().unwrap();
+    read_states().remove(task_name).unwrap_or_default()
+}
+
+fn update_state(task_name: &str, update: impl FnOnce(&mut BisyncState)) -> anyhow::Result<()> {
+    let _guard = STATE_LOCK.lock().unwrap();
+    let mut states = read_states();
+    update(states.entry(task_name.to_string()).or_default());
+    fs::create_dir_all(base_dir())?;
+    let tmp = state_file().with_extension("json.tmp");
+    fs::write(&tmp, serde_json::to_vec_pretty(&states)?)?;
+    fs::rename(tmp, state_file())?;
+    Ok(())
+}
+
+fn files_with_ext(dir: &Path, ext: &str) -> Vec<PathBuf> {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok().map(|e| e.path()))
+                .filter(|p| p.extension().is_some_and(|e| e == ext))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// 锁文件中记录的 PID：新版 rclone 为 JSON（`PID` 字段），较早的版本为纯文本
+fn lock_pid(path: &Path) -> Option<u32> {
+    let content = fs::read_to_string(path).ok()?;
+    if let Ok(v) = serde_json::from_str::<serde_json::Value>(&content) {
+        let pid = &v["PID"];
+        return pid
+            .as_u64()
+            .map(|p| p as u32)
+            .or_else(|| pid.as_str().and_then(|s| s.trim().parse().ok()));
+    }
+    content.trim().parse().ok()
+}
+
+/// 检查锁文件是否仍被持有。
+///
+/// 经 rc 运行的 bisync 锁中记录的是 rclone rcd 的 PID，此时以该任务是否正在运行为准；
+/// 其它 PID（如手动运行的 rclone 命令）以进程是否存在为准。
+fn lock_info(path: &Path, task_running: bool) -> LockInfo {
+    let pid = lock_pid(path);
+    let rclone_pid = crate::sidecar::get_sidecar_pid("rclone");
+    let held = match pid {
+        Some(pid) if Some(pid) == rclone_pid => task_running,
+        Some(pid) => crate::sidecar::process_alive(pid),
+        None => task_running,
+    };
+    LockInfo {
+        file: path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        pid,
+        held,
+    }
+}
+
+/// 删除无进程持有的锁文件，返回删除数量
+fn clean_locks(task_name: &str, task_running: bool) -> usize {
+    let dir = workdir(task_name);
+    let mut removed = 0;
+    for path in files_with_ext(&dir, "lck") {
+        if !lock_info(&path, task_running).held && fs::remove_file(&path).is_ok() {
+            removed += 1;
+        }
+    }
+    removed
+}
+
+fn task_running(task_name: &str) -> bool {
+    let snapshot = queue::snapshot();
+    snapshot
+        .running
+        .iter()
+        .chain(snapshot.waiting.iter())
+        .any(|e| e.task_name == task_name)
+}
+
+pub fn status(task_name: &str) -> BisyncStatus {
+    let dir = workdir(task_name);
+    let running = task_running(task_name);
+    BisyncStatus {
+        task_name: task_name.to_string(),
+        workdir: dir.to_string_lossy().to_string(),
+        initialized: !files_with_ext(&dir, "lst").is_empty(),
+        locks: files_with_ext(&dir, "lck")
+            .iter()
+            .map(|p| lock_info(p, running))
+            .collect(),
+        state: load_state(task_name),
+    }
+}
+
+/// 手动清理锁文件；任务正在运行或排队时拒绝
+pub fn clean_task_locks(task_name: &str) -> anyhow::Result<usize> {
+    if task_running(task_name) {
+        return Err(anyhow::anyhow!("Task {} is running", task_name));
+    }
+    Ok(clean_locks(task_name, false))
+}
+
+/// `parameters.conflictResolve`，未设置时为空
+fn conflict_policy(task: &TaskSpec) -> anyhow::Result<Option<String>> {
+    let Some(policy) = task
+        .parameters
+        .as_ref()
+        .and_then(|p| p.get("conflictResolve"))
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+    else {
+        return Ok(None);
+    };
+    if !CONFLICT_POLICIES.contains(&policy) {
+        return Err(anyhow::anyhow!("Invalid conflict policy: {}", policy));
+    }
+    Ok(Some(policy.to_string()))
+}
+
+/// 作为任务运行一次 bisync（由运行队列调用，锁检查时不把本任务视为占用者）
+pub async fn run(task: &TaskSpec, group: &str) -> anyhow::Result<JobOutcome> {
+    let dir = workdir(&task.name);
+    fs::create_dir_all(&dir)?;
+    let state = load_state(&task.name);
+    // 保存在任务参数中的 `resync` 不生效，只有确认预览后的 resync 命令可以强制 resync
+    let forced = task.resync;
+    // workdir 中没有列表说明从未同步过，首次运行自动 resync
+    let first_run = files_with_ext(&dir, "lst").is_empty();
+    if state.needs_resync && !forced && !first_run {
+        return Err(anyhow::anyhow!(
+            "Bisync state for {} requires a resync; preview and run the resync first",
+            task.name
+        ));
+    }
+    clean_locks(&task.name, false);
+
+    let remotes = rclone::list_remotes().await?;
+    let src = StorageRef::resolve(&remotes, &task.source.storage_name);
+    let dst = StorageRef::resolve(&remotes, &task.target.storage_name);
+    let resync = forced || first_run;
+    // checksum 比较可避免部分远程不支持 modtime 导致的失败
+    let mut body = json!({
+        "path1": src.fs(&task.source.path),
+        "path2": dst.fs(&task.target.path),
+        "checksum": true,
+        "resync": resync,
+        "workdir": dir.to_string_lossy(),
+    });
+    if let Some(policy) = conflict_policy(task)? {
+        body["conflictResolve"] = json!(policy);
+    }
+    let rules = task.filter_rules();
+    if !rules.is_empty() {
+        body["_filter"] = json!({ "FilterRule": rules });
+    }
+
+    let outcome = rclone::run_job("sync/bisync", body, group).await;
+    let (success, error) = match &outcome {
+        Ok(o) => (o.success, o.error.clone()),
+        Err(e) => (false, e.to_string()),
+    };
+    let now = now_ms();
+    update_state(&task.name, |s| {
+        s.last_run_at = Some(now);
+        if success {
+            s.needs_resync = false;
+            s.last_error = None;
+            if resync {
+                s.last_resync_at = Some(now);
+            }
+        } else {
+            if classify(&error) == Failure::MustResync {
+                s.needs_resync = true;
+            }
+            s.last_error = Some(error.clone());
+        }
+    })?;
+    // 锁冲突说明有其它进程在运行，保留锁，由下次运行或手动清理处理
+    if !success && classify(&error) == Failure::Locked {
+        return Err(anyhow::anyhow!(
+            "Bisync workdir is locked by another process: {}",
+            error
+        ));
+    }
+    outcome
+}
+
+/// 以 `--resync --dry-run` 预览 resync 的结果，并保存以供确认
+pub async fn preview_resync(task: &TaskSpec) -> anyhow::Result<TaskPlan> {
+    if task.task_type != "bisync" {
+        return Err(anyhow::anyhow!("Task {} is not a bisync task", task.name));
+    }
+    let dir = workdir(&task.name);
+    fs::create_dir_all(&dir)?;
+    let remotes = rclone::list_remotes().await?;
+    let src = StorageRef::resolve(&remotes, &task.source.storage_name);
+    let dst = StorageRef::resolve(&remotes, &task.target.storage_name);
+
+    let mut args = vec![
+        "bisync".to_string(),
+        src.fs(&task.source.path),
+        dst.fs(&task.target.path),
+        "--resync".to_string(),
+        "--checksum".to_string(),
+        "--dry-run".to_string(),
+        format!("--workdir={}", dir.display()),
+        "--use-json-log".to_string(),
+        "--log-level=NOTICE".to_string(),
+    ];
+    if let Some(policy) = conflict_policy(task)? {
+        args.push(format!("--conflict-resolve={}", policy));
+    }
+    args.extend(
+        task.filter_rules()
+            .into_iter()
+            .map(|r| format!("--filter={}", r)),
+    );
+
+    let mut cmd = rclone::command(&args)?;
+    cmd.stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    let output = tokio::process::Command::from(cmd)
+        .kill_on_drop(true)
+        .output()
+        .await?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let (skipped, mut errors) = plan::parse_json_log(&stderr);
+    if !output.status.success() && errors.is_empty() {
+        errors.push(stderr.trim().to_string());
+    }
+    let entries = plan::build_entries("", &skipped, false);
+    let preview = TaskPlan {
+        id: new_run_id(),
+        task_name: task.name.clone(),
+        task_type: "bisync-resync".to_string(),
+        created_at: now_ms(),
+        totals: plan::totals(&entries),
+        entries,
+        errors,
+        approved_at: None,
+        fingerprint: String::new(),
+        consumed_at: None,
+    };
+    plan::save(&preview)?;
+    Ok(preview)
+}
+
+/// 校验 resync 预览：必须属于该任务、未过期且没有错误
+pub fn check_preview(task: &TaskSpec, preview_id: &str) -> anyhow::Result<()> {
+    let preview = plan::load(preview_id)?;
+    if preview.task_name != task.name || preview.task_type != "bisync-resync" {
+        return Err(anyhow::anyhow!(
+            "Preview {} does not belong to {}",
+            preview_id,
+            task.name
+        ));
+    }
+    if now_ms().saturating_sub(preview.created_at) > PREVIEW_VALID_MS {
+        return Err(anyhow::anyhow!(
+            "Preview {} has expired, please preview again",
+            preview_id
+        ));
+    }
+    if !preview.errors.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Preview {} reported errors: {}",
+            preview_id,
+            preview.errors.join("; ")
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn workdir_names_do_not_collide() {
+        assert_ne!(workdir_name("a b"), workdir_name("a_b"));
+        assert_ne!(workdir_name("a/b"), workdir_name("a?b"));
+        assert_eq!(workdir_name("备份"), workdir_name("备份"));
+        let name = workdir_name("../etc");
+        assert!(name.starts_with("___etc-"));
+        assert_eq!(name.len(), "___etc-".len() + 16);
+    }
+
+    #[test]
+    fn saved_resync_parameter_does_not_force_resync() {
+        let task: TaskSpec = serde_json::from_value(json!({
+            "name": "docs",
+            "taskType": "bisync",
+            "parameters": { "resync": true },
+            "resync": true,
+        }))
+        .unwrap();
+        assert!(!task.resync);
+    }
+
+    #[test]
+    fn classify_bisync_errors() {
+        assert_eq!(
+            classify("Bisync aborted. Must run --resync to recover."),
+            Failure::MustResync
+        );
+        assert_eq!(
+            classify("prior lock file found: /tmp/x.lck"),
+            Failure::Locked
+        );
+        assert_eq!(classify("connection refused"), Failure::Other);
+    }
+
+    #[test]
+    fn conflict_policy_validation() {
+        let mut task = TaskSpec::default();
+        assert_eq!(conflict_policy(&task).unwrap(), None);
+        task.parameters = Some(json!({ "conflictResolve": "newer" }));
+        assert_eq!(conflict_policy(&task).unwrap().as_deref(), Some("newer"));
+        task.parameters = Some(json!({ "conflictResolve": "random" }));
+        assert!(conflict_policy(&task).is_err());
+    }
+}
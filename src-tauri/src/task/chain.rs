@@ -0,0 +1,380 @@
+//! 任务链：任务通过 `after` 声明前置任务及触发条件，运行某个任务时按依赖顺序
+//! 继续运行其下游任务，整条链记录为一次分组运行（共享 `chainId`）。
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::history::TaskHistory;
+use super::queue::{self, Admission, ConcurrencyLimits};
+use super::{new_run_id, now_ms, runner, TaskSpec};
+use crate::Runtime;
+
+/// 前置任务结束后触发下游任务的条件
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RunCondition {
+    #[default]
+    Success,
+    Failure,
+    Always,
+}
+
+impl RunCondition {
+    fn satisfied_by(self, success: bool) -> bool {
+        match self {
+            RunCondition::Success => success,
+            RunCondition::Failure => !success,
+            RunCondition::Always => true,
+        }
+    }
+}
+
+/// `after` 中的一项
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskDependency {
+    /// 前置任务名称
+    pub task: String,
+    #[serde(default)]
+    pub on: RunCondition,
+}
+
+/// 一次链式运行的汇总
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainRun {
+    /// 链 ID（即各运行记录的 `chainId`）
+    pub id: String,
+    /// 触发本次运行的任务
+    pub root: String,
+    /// success / failed：任一已运行的任务失败即为 failed；
+    /// coalesced / cancelled：根任务已在运行队列中或排队时被取消，未运行任何任务
+    pub status: String,
+    pub started_at: u64,
+    pub completed_at: u64,
+    /// 按执行顺序排列的运行记录，第一条为根任务
+    pub runs: Vec<TaskHistory>,
+    /// 因条件不满足、未启用、已在运行或被取消而跳过的下游任务
+    pub skipped: Vec<String>,
+}
+
+/// 查找依赖环，返回环上的任务名（首尾相同）。指向不存在任务的依赖忽略
+pub fn find_cycle(tasks: &[TaskSpec]) -> Option<Vec<String>> {
+    let deps: HashMap<&str, Vec<&str>> = tasks
+        .iter()
+        .map(|t| {
+            (
+                t.name.as_str(),
+                t.after.iter().map(|d| d.task.as_str()).collect(),
+            )
+        })
+        .collect();
+
+    // 0：未访问，1：在当前路径上，2：已完成
+    fn visit<'a>(
+        name: &'a str,
+        deps: &HashMap<&'a str, Vec<&'a str>>,
+        state: &mut HashMap<&'a str, u8>,
+        path: &mut Vec<&'a str>,
+    ) -> Option<Vec<String>> {
+        state.insert(name, 1);
+        path.push(name);
+        for &dep in deps.get(name).into_iter().flatten() {
+            if !deps.contains_key(dep) {
+                continue;
+            }
+            match state.get(dep).copied().unwrap_or(0) {
+                1 => {
+                    let start = path.iter().position(|n| *n == dep).unwrap_or(0);
+                    let mut cycle: Vec<String> =
+                        path[start..].iter().map(|n| n.to_string()).collect();
+                    cycle.push(dep.to_string());
+                    return Some(cycle);
+                }
+                0 => {
+                    if let Some(cycle) = visit(dep, deps, state, path) {
+                        return Some(cycle);
+                    }
+                }
+                _ => {}
+            }
+        }
+        path.pop();
+        state.insert(name, 2);
+        None
+    }
+
+    let mut state = HashMap::new();
+    let mut path = Vec::new();
+    for task in tasks {
+        if state.get(task.name.as_str()).copied().unwrap_or(0) == 0 {
+            if let Some(cycle) = visit(&task.name, &deps, &mut state, &mut path) {
+                return Some(cycle);
+            }
+        }
+    }
+    None
+}
+
+/// 保存配置前校验任务依赖
+pub fn validate(tasks: &[TaskSpec]) -> anyhow::Result<()> {
+    if let Some(task) = tasks
+        .iter()
+        .find(|t| t.after.iter().any(|d| d.task == t.name))
+    {
+        return Err(anyhow::anyhow!(
+            "Task {} cannot depend on itself",
+            task.name
+        ));
+    }
+    if let Some(cycle) = find_cycle(tasks) {
+        return Err(anyhow::anyhow!(
+            "Task dependency cycle detected: {}",
+            cycle.join(" -> ")
+        ));
+    }
+    Ok(())
+}
+
+/// 从 `root` 出发可达的下游任务，按拓扑顺序排列（同层保持配置中的顺序），不含 `root`
+fn plan<'a>(tasks: &'a [TaskSpec], root: &str) -> Vec<&'a TaskSpec> {
+    let mut reachable: HashSet<&str> = HashSet::from([root]);
+    loop {
+        let before = reachable.len();
+        for task in tasks {
+            if task
+                .after
+                .iter()
+                .any(|d| reachable.contains(d.task.as_str()))
+            {
+                reachable.insert(task.name.as_str());
+            }
+        }
+        if reachable.len() == before {
+            break;
+        }
+    }
+
+    let mut done: HashSet<&str> = HashSet::from([root]);
+    let mut ordered = Vec::new();
+    loop {
+        let next = tasks.iter().find(|t| {
+            reachable.contains(t.name.as_str())
+                && !done.contains(t.name.as_str())
+                && t.after
+                    .iter()
+                    .filter(|d| reachable.contains(d.task.as_str()))
+                    .all(|d| done.contains(d.task.as_str()))
+        });
+        match next {
+            Some(task) => {
+                done.insert(task.name.as_str());
+                ordered.push(task);
+            }
+            // 剩余任务存在依赖环（保存时已校验，这里仅防御）
+            None => break,
+        }
+    }
+    ordered
+}
+
+/// 下游任务是否可以运行：已启用，且链内的每个前置任务都已运行并满足条件
+fn ready(task: &TaskSpec, in_chain: &HashSet<&str>, results: &HashMap<&str, bool>) -> bool {
+    task.enable
+        && task
+            .after
+            .iter()
+            .filter(|d| in_chain.contains(d.task.as_str()))
+            .all(|d| {
+                results
+                    .get(d.task.as_str())
+                    .is_some_and(|success| d.on.satisfied_by(*success))
+            })
+}
+
+/// 经运行队列执行单个任务；任务被合并或取消时返回 Err(状态)
+async fn run_queued(
+    app: &tauri::AppHandle<Runtime>,
+    task: &TaskSpec,
+    chain: Option<&str>,
+    limits: &ConcurrencyLimits,
+) -> anyhow::Result<Result<TaskHistory, &'static str>> {
+    let _permit = match queue::admit(task, limits).await {
+        Admission::Run(permit) => permit,
+        Admission::Coalesced => return Ok(Err("coalesced")),
+        Admission::Cancelled => return Ok(Err("cancelled")),
+    };
+    Ok(Ok(runner::execute(app, task, chain).await?))
+}
+
+/// 运行 `root` 及其下游任务。
+///
+/// 下游任务仅在已启用、且链内的每个前置任务都已运行并满足对应条件时运行；
+/// 链外的前置任务不参与判断。每个任务都经过运行队列，受并发限制约束。
+pub async fn run_chain(
+    app: &tauri::AppHandle<Runtime>,
+    tasks: &[TaskSpec],
+    root: &TaskSpec,
+    limits: &ConcurrencyLimits,
+) -> anyhow::Result<ChainRun> {
+    let chain_id = new_run_id();
+    let started_at = now_ms();
+    let order = plan(tasks, &root.name);
+    let in_chain: HashSet<&str> = order
+        .iter()
+        .map(|t| t.name.as_str())
+        .chain([root.name.as_str()])
+        .collect();
+
+    let chain = (!order.is_empty()).then_some(chain_id.as_str());
+    let first = match run_queued(app, root, chain, limits).await? {
+        Ok(record) => record,
+        Err(status) => {
+            return Ok(ChainRun {
+                id: chain_id.clone(),
+                root: root.name.clone(),
+                status: status.to_string(),
+                started_at,
+                completed_at: now_ms(),
+                runs: Vec::new(),
+                skipped: order.iter().map(|t| t.name.clone()).collect(),
+            })
+        }
+    };
+    // 已运行任务的结果，跳过的任务不在其中
+    let mut results: HashMap<&str, bool> = HashMap::new();
+    results.insert(root.name.as_str(), first.status == "success");
+    let mut runs = vec![first];
+    let mut skipped = Vec::new();
+
+    for task in order {
+        if !ready(task, &in_chain, &results) {
+            skipped.push(task.name.clone());
+            continue;
+        }
+        let Ok(record) = run_queued(app, task, chain, limits).await? else {
+            skipped.push(task.name.clone());
+            continue;
+        };
+        results.insert(task.name.as_str(), record.status == "success");
+        runs.push(record);
+    }
+
+    let success = runs.iter().all(|r| r.status == "success");
+    Ok(ChainRun {
+        // 没有下游任务时不分组，直接使用根任务的运行 ID
+        id: if chain.is_some() {
+            chain_id.clone()
+        } else {
+            runs[0].id.clone()
+        },
+        root: root.name.clone(),
+        status: if success { "success" } else { "failed" }.to_string(),
+        started_at,
+        completed_at: now_ms(),
+        runs,
+        skipped,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use RunCondition::*;
+
+    fn task(name: &str, after: &[(&str, RunCondition)]) -> TaskSpec {
+        TaskSpec {
+            name: name.to_string(),
+            task_type: "copy".to_string(),
+            enable: true,
+            after: after
+                .iter()
+                .map(|(task, on)| TaskDependency {
+                    task: task.to_string(),
+                    on: *on,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn names(tasks: &[&TaskSpec]) -> Vec<String> {
+        tasks.iter().map(|t| t.name.clone()).collect()
+    }
+
+    #[test]
+    fn rejects_self_dependency() {
+        let tasks = [task("a", &[("a", Success)])];
+        let err = validate(&tasks).unwrap_err().to_string();
+        assert!(err.contains("cannot depend on itself"), "{}", err);
+    }
+
+    #[test]
+    fn finds_indirect_cycle() {
+        let tasks = [
+            task("a", &[("c", Success)]),
+            task("b", &[("a", Success)]),
+            task("c", &[("b", Always)]),
+            task("d", &[("a", Success)]),
+        ];
+        assert_eq!(find_cycle(&tasks).unwrap(), ["a", "c", "b", "a"]);
+        let err = validate(&tasks).unwrap_err().to_string();
+        assert!(err.contains("a -> c -> b -> a"), "{}", err);
+    }
+
+    #[test]
+    fn ignores_unknown_after_target() {
+        let tasks = [
+            task("a", &[("missing", Success)]),
+            task("b", &[("a", Success)]),
+        ];
+        assert!(find_cycle(&tasks).is_none());
+        assert!(validate(&tasks).is_ok());
+        assert_eq!(names(&plan(&tasks, "a")), ["b"]);
+    }
+
+    #[test]
+    fn plan_orders_downstream_topologically() {
+        let tasks = [
+            task("report", &[("backup", Success), ("verify", Success)]),
+            task("verify", &[("backup", Success)]),
+            task("backup", &[]),
+            task("notify", &[("backup", Failure)]),
+            task("other", &[]),
+        ];
+        assert_eq!(
+            names(&plan(&tasks, "backup")),
+            ["verify", "report", "notify"]
+        );
+        assert_eq!(names(&plan(&tasks, "verify")), ["report"]);
+        assert!(plan(&tasks, "other").is_empty());
+    }
+
+    #[test]
+    fn conditions_gate_downstream_tasks() {
+        assert!(Success.satisfied_by(true) && !Success.satisfied_by(false));
+        assert!(Failure.satisfied_by(false) && !Failure.satisfied_by(true));
+        assert!(Always.satisfied_by(true) && Always.satisfied_by(false));
+
+        let in_chain = HashSet::from(["backup", "verify", "report"]);
+        let report = task("report", &[("backup", Success), ("verify", Always)]);
+        let mut results = HashMap::from([("backup", true)]);
+        // 链内前置任务尚未运行（被跳过）时不运行
+        assert!(!ready(&report, &in_chain, &results));
+        results.insert("verify", false);
+        assert!(ready(&report, &in_chain, &results));
+        results.insert("backup", false);
+        assert!(!ready(&report, &in_chain, &results));
+
+        // 链外的前置任务不参与判断
+        let cleanup = task("cleanup", &[("backup", Failure), ("elsewhere", Success)]);
+        assert!(ready(&cleanup, &in_chain, &results));
+        // 未启用的任务不运行
+        let disabled = TaskSpec {
+            enable: false,
+            ..cleanup
+        };
+        assert!(!ready(&disabled, &in_chain, &results));
+    }
+}
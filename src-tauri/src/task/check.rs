@@ -0,0 +1,297 @@
+//! 校验任务（`check`）：通过 `rclone check` / `cryptcheck` 比对源与目标，
+//! 生成一致、不同、源缺失、目标缺失的文件报告并保存到 `task/reports/`。
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+use serde::{Deserialize, Serialize};
+
+use super::{now_ms, runlog, TaskSpec};
+use crate::rclone::{self, JobOutcome, StorageRef};
+
+/// 校验报告
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckReport {
+    /// 对应的运行 ID
+    pub run_id: String,
+    pub task_name: String,
+    pub created_at: u64,
+    /// check / cryptcheck
+    pub mode: String,
+    /// 是否以下载内容的方式比对（`--download`）
+    pub download: bool,
+    #[serde(rename = "match")]
+    pub matched: Vec<String>,
+    pub differ: Vec<String>,
+    /// 仅存在于目标中
+    pub missing_on_src: Vec<String>,
+    /// 仅存在于源中
+    pub missing_on_dst: Vec<String>,
+    /// 读取或比对出错的文件
+    pub errors: Vec<String>,
+}
+
+impl CheckReport {
+    /// 存在不一致（不同、缺失或出错）的文件数
+    pub fn problems(&self) -> usize {
+        self.differ.len()
+            + self.missing_on_src.len()
+            + self.missing_on_dst.len()
+            + self.errors.len()
+    }
+
+    /// 导出为 CSV：`status,path`
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("status,path\n");
+        let groups: [(&str, &Vec<String>); 5] = [
+            ("match", &self.matched),
+            ("differ", &self.differ),
+            ("missing_on_src", &self.missing_on_src),
+            ("missing_on_dst", &self.missing_on_dst),
+            ("error", &self.errors),
+        ];
+        for (status, paths) in groups {
+            for path in paths {
+                out.push_str(status);
+                out.push(',');
+                out.push_str(&csv_field(path));
+                out.push('\n');
+            }
+        }
+        out
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+pub fn report_dir() -> PathBuf {
+    crate::resolve_data_dir().join("task").join("reports")
+}
+
+fn report_path(run_id: &str) -> anyhow::Result<PathBuf> {
+    runlog::check_run_id(run_id)?;
+    Ok(report_dir().join(format!("{}.json", run_id)))
+}
+
+pub fn load(run_id: &str) -> anyhow::Result<CheckReport> {
+    let content = fs::read(report_path(run_id)?)
+        .map_err(|_| anyhow::anyhow!("Check report not found: {}", run_id))?;
+    Ok(serde_json::from_slice(&content)?)
+}
+
+/// 按扩展名（`.csv` / `.json`）导出报告
+pub fn export(run_id: &str, out_path: &Path) -> anyhow::Result<()> {
+    let report = load(run_id)?;
+    let ext = out_path
+        .extension()
+        .map(|e| e.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    let content = match ext.as_str() {
+        "csv" => report.to_csv(),
+        "json" => serde_json::to_string_pretty(&report)?,
+        _ => return Err(anyhow::anyhow!("Output file must end with .csv or .json")),
+    };
+    if let Some(parent) = out_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(out_path, content)?;
+    Ok(())
+}
+
+/// 删除超过 `max_age_days` 天的报告（0 表示不清理），与运行日志保持相同的保留期
+pub fn cleanup(max_age_days: u64) -> anyhow::Result<usize> {
+    let dir = report_dir();
+    if max_age_days == 0 || !dir.exists() {
+        return Ok(0);
+    }
+    let max_age = std::time::Duration::from_secs(max_age_days * 24 * 60 * 60);
+    let now = std::time::SystemTime::now();
+    let mut removed = 0;
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let expired = entry
+            .metadata()
+            .and_then(|m| m.modified())
+            .map(|t| now.duration_since(t).unwrap_or_default() > max_age)
+            .unwrap_or(false);
+        if expired && fs::remove_file(entry.path()).is_ok() {
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+/// 解析 `--combined` 报告
+fn parse_combined(report: &mut CheckReport, combined: &str) {
+    for line in combined.lines() {
+        let Some((mark, path)) = line.split_once(' ') else {
+            continue;
+        };
+        let list = match mark {
+            "=" => &mut report.matched,
+            "*" => &mut report.differ,
+            "-" => &mut report.missing_on_src,
+            "+" => &mut report.missing_on_dst,
+            "!" => &mut report.errors,
+            _ => continue,
+        };
+        list.push(path.to_string());
+    }
+}
+
+/// 运行校验并保存报告。
+///
+/// `parameters` 支持 `cryptcheck`（目标为 crypt 存储时使用 `rclone cryptcheck`）、
+/// `download`、`oneWay`，以及 `failOnDifference`（默认 true：存在不一致时任务失败）。
+pub async fn run(task: &TaskSpec, run_id: &str) -> anyhow::Result<JobOutcome> {
+    if !task.source.path.ends_with('/') || !task.target.path.ends_with('/') {
+        return Err(anyhow::anyhow!("Check tasks require directory paths"));
+    }
+    let remotes = rclone::list_remotes().await?;
+    let src = StorageRef::resolve(&remotes, &task.source.storage_name);
+    let dst = StorageRef::resolve(&remotes, &task.target.storage_name);
+
+    let mode = if task.bool_param("cryptcheck") {
+        "cryptcheck"
+    } else {
+        "check"
+    };
+    let download = task.bool_param("download");
+    fs::create_dir_all(report_dir())?;
+    let combined_path = report_dir().join(format!("{}.combined", run_id));
+
+    let mut args = vec![
+        mode.to_string(),
+        src.fs(&task.source.path),
+        dst.fs(&task.target.path),
+        format!("--combined={}", combined_path.display()),
+        "--log-level=NOTICE".to_string(),
+    ];
+    // cryptcheck 本身就是按内容校验，不支持 --download
+    if download && mode == "check" {
+        args.push("--download".to_string());
+    }
+    if task.bool_param("oneWay") {
+        args.push("--one-way".to_string());
+    }
+    args.extend(
+        task.filter_rules()
+            .into_iter()
+            .map(|r| format!("--filter={}", r)),
+    );
+
+    let mut cmd = rclone::command(&args)?;
+    cmd.stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    let output = tokio::process::Command::from(cmd)
+        .kill_on_drop(true)
+        .output()
+        .await?;
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    if !stderr.trim().is_empty() {
+        let _ = runlog::append(run_id, stderr.trim_end());
+    }
+
+    let combined = fs::read_to_string(&combined_path).unwrap_or_default();
+    let _ = fs::remove_file(&combined_path);
+    // 差异会使 rclone 以非零状态退出，只有没有生成报告时才视为执行失败
+    if !output.status.success() && combined.trim().is_empty() {
+        let msg = stderr
+            .lines()
+            .rev()
+            .find(|l| !l.trim().is_empty())
+            .unwrap_or("rclone check failed");
+        return Err(anyhow::anyhow!("{}", msg.trim()));
+    }
+
+    let mut report = CheckReport {
+        run_id: run_id.to_string(),
+        task_name: task.name.clone(),
+        created_at: now_ms(),
+        mode: mode.to_string(),
+        download: download && mode == "check",
+        ..Default::default()
+    };
+    parse_combined(&mut report, &combined);
+    fs::write(report_path(run_id)?, serde_json::to_vec_pretty(&report)?)?;
+
+    let problems = report.problems();
+    let fail_on_difference = task
+        .parameters
+        .as_ref()
+        .and_then(|p| p.get("failOnDifference"))
+        .and_then(|v| v.as_bool())
+        .unwrap_or(true);
+    let _ = runlog::append(
+        run_id,
+        &format!(
+            "check: {} match, {} differ, {} missing on source, {} missing on destination, {} errors",
+            report.matched.len(),
+            report.differ.len(),
+            report.missing_on_src.len(),
+            report.missing_on_dst.len(),
+            report.errors.len()
+        ),
+    );
+
+    Ok(JobOutcome {
+        job_id: None,
+        success: problems == 0 || !fail_on_difference,
+        error: if problems > 0 {
+            format!("{} differences found", problems)
+        } else {
+            String::new()
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_combined_sorts_marks_into_groups() {
+        let mut report = CheckReport::default();
+        parse_combined(
+            &mut report,
+            "= same.txt\n* changed.txt\n- only dst.txt\n+ only-src.txt\n! broken.txt\n? odd\nnomark\n",
+        );
+        assert_eq!(report.matched, ["same.txt"]);
+        assert_eq!(report.differ, ["changed.txt"]);
+        assert_eq!(report.missing_on_src, ["only dst.txt"]);
+        assert_eq!(report.missing_on_dst, ["only-src.txt"]);
+        assert_eq!(report.errors, ["broken.txt"]);
+        assert_eq!(report.problems(), 4);
+    }
+
+    #[test]
+    fn csv_quotes_special_paths() {
+        let report = CheckReport {
+            matched: vec!["a.txt".to_string()],
+            differ: vec!["b,c.txt".to_string()],
+            errors: vec!["say \"hi\"".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            report.to_csv(),
+            "status,path\nmatch,a.txt\ndiffer,\"b,c.txt\"\nerror,\"say \"\"hi\"\"\"\n"
+        );
+    }
+
+    #[test]
+    fn report_path_rejects_unsafe_run_ids() {
+        assert!(report_path("20240101-abc").is_ok());
+        assert!(report_path("").is_err());
+        assert!(report_path("../secrets").is_err());
+        assert!(report_path("a/b").is_err());
+    }
+}
@@ -0,0 +1,347 @@
+//! 断点续传：为启用 `resumable` 的目录 copy / move 任务记录文件清单与已完成的文件，
+//! 中断（崩溃、重启或失败）后再次运行时只传输剩余文件，完成后删除检查点。
+//!
+//! 已完成文件取自 `core/transferred`，rclone 只保留最近的记录，少量遗漏的文件
+//! 在续传时会由 rclone 自行比对后跳过，不会重复传输。
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::{now_ms, runlog, TaskSpec};
+use crate::rclone::{self, JobOutcome};
+
+/// 运行中刷新检查点的间隔
+const SAVE_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckpointFile {
+    pub path: String,
+    pub size: u64,
+}
+
+/// 任务检查点
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Checkpoint {
+    pub task_name: String,
+    /// 源、目标与过滤规则，任一变化后检查点作废
+    pub fingerprint: String,
+    pub created_at: u64,
+    pub updated_at: u64,
+    /// 首次运行时列出的源文件
+    pub files: Vec<CheckpointFile>,
+    /// 已完成的文件（相对源目录）
+    pub completed: BTreeSet<String>,
+}
+
+/// 检查点进度，供界面显示续传后的整体进度
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckpointProgress {
+    pub task_name: String,
+    pub created_at: u64,
+    pub updated_at: u64,
+    pub total_files: u64,
+    pub total_bytes: u64,
+    pub completed_files: u64,
+    pub completed_bytes: u64,
+}
+
+impl Checkpoint {
+    pub fn progress(&self) -> CheckpointProgress {
+        let (completed_files, completed_bytes) = self
+            .files
+            .iter()
+            .filter(|f| self.completed.contains(&f.path))
+            .fold((0, 0), |(n, b), f| (n + 1, b + f.size));
+        CheckpointProgress {
+            task_name: self.task_name.clone(),
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            total_files: self.files.len() as u64,
+            total_bytes: self.files.iter().map(|f| f.size).sum(),
+            completed_files,
+            completed_bytes,
+        }
+    }
+
+    fn remaining(&self) -> Vec<&str> {
+        self.files
+            .iter()
+            .map(|f| f.path.as_str())
+            .filter(|p| !self.completed.contains(*p))
+            .collect()
+    }
+}
+
+pub fn checkpoint_dir() -> PathBuf {
+    crate::resolve_data_dir().join("task").join("checkpoints")
+}
+
+fn checkpoint_path(task_name: &str) -> PathBuf {
+    let safe: String = task_name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    checkpoint_dir().join(format!("{}.json", safe))
+}
+
+pub fn load(task_name: &str) -> Option<Checkpoint> {
+    let content = fs::read(checkpoint_path(task_name)).ok()?;
+    serde_json::from_slice::<Checkpoint>(&content)
+        .ok()
+        .filter(|c| c.task_name == task_name)
+}
+
+fn save(checkpoint: &Checkpoint) -> anyhow::Result<()> {
+    fs::create_dir_all(checkpoint_dir())?;
+    let path = checkpoint_path(&checkpoint.task_name);
+    // 先写临时文件再替换，避免中断时留下损坏的检查点
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, serde_json::to_vec(checkpoint)?)?;
+    fs::rename(tmp, path)?;
+    Ok(())
+}
+
+/// 删除检查点，返回是否存在
+pub fn remove(task_name: &str) -> bool {
+    fs::remove_file(checkpoint_path(task_name)).is_ok()
+}
+
+/// 任务启用断点续传且为目录到目录的 copy / move 时可用
+pub fn enabled_for(task: &TaskSpec) -> bool {
+    matches!(task.task_type.as_str(), "copy" | "move")
+        && task.source.path.ends_with('/')
+        && task.target.path.ends_with('/')
+        && task.files_from.is_none()
+        && task.bool_param("resumable")
+}
+
+/// 列出源目录中的全部文件，使用与本次运行相同的过滤规则
+async fn list_source(src_fs: &str, filter: Option<&Value>) -> anyhow::Result<Vec<CheckpointFile>> {
+    let mut body =
+        json!({ "fs": src_fs, "remote": "", "opt": { "recurse": true, "filesOnly": true } });
+    if let Some(filter) = filter {
+        body["_filter"] = filter.clone();
+    }
+    let res = rclone::rc_post("operations/list", body).await?;
+    Ok(res
+        .get("list")
+        .and_then(|v| v.as_array())
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|item| {
+                    Some(CheckpointFile {
+                        path: item.get("Path")?.as_str()?.to_string(),
+                        size: item.get("Size").and_then(|s| s.as_u64()).unwrap_or(0),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+/// 把 `core/transferred` 中成功完成的文件记入检查点，返回是否有新增
+async fn record_transferred(checkpoint: &mut Checkpoint, group: &str) -> bool {
+    let Ok(res) = rclone::rc_post("core/transferred", json!({ "group": group })).await else {
+        return false;
+    };
+    let items = res.get("transferred").and_then(|v| v.as_array());
+    record_items(checkpoint, items.map(Vec::as_slice).unwrap_or_default())
+}
+
+/// 记录没有错误的传输项，返回是否有新增
+fn record_items(checkpoint: &mut Checkpoint, items: &[Value]) -> bool {
+    let mut changed = false;
+    for item in items {
+        let ok = item
+            .get("error")
+            .and_then(|e| e.as_str())
+            .is_none_or(|e| e.is_empty());
+        if let (true, Some(name)) = (ok, item.get("name").and_then(|n| n.as_str())) {
+            changed |= checkpoint.completed.insert(name.to_string());
+        }
+    }
+    changed
+}
+
+/// 以检查点运行目录 copy / move：首次运行先列出源文件，之后只传输剩余文件。
+///
+/// `body` 为已附加过滤规则等参数的 rc 请求体，`fingerprint` 标识源、目标与规则。
+pub async fn run(
+    task: &TaskSpec,
+    op: &str,
+    mut body: Value,
+    src_fs: &str,
+    fingerprint: String,
+    group: &str,
+    run_id: &str,
+) -> anyhow::Result<JobOutcome> {
+    let mut checkpoint = match load(&task.name) {
+        Some(c) if c.fingerprint == fingerprint => {
+            let progress = c.progress();
+            let _ = runlog::append(
+                run_id,
+                &format!(
+                    "resuming from checkpoint: {}/{} files, {}/{} bytes already done",
+                    progress.completed_files,
+                    progress.total_files,
+                    progress.completed_bytes,
+                    progress.total_bytes
+                ),
+            );
+            c
+        }
+        _ => {
+            let files = list_source(src_fs, body.get("_filter")).await?;
+            let _ = runlog::append(
+                run_id,
+                &format!("checkpoint created: {} files to transfer", files.len()),
+            );
+            Checkpoint {
+                task_name: task.name.clone(),
+                fingerprint,
+                created_at: now_ms(),
+                updated_at: now_ms(),
+                files,
+                completed: BTreeSet::new(),
+            }
+        }
+    };
+    save(&checkpoint)?;
+
+    let remaining = checkpoint.remaining();
+    if remaining.is_empty() {
+        remove(&task.name);
+        return Ok(JobOutcome {
+            job_id: None,
+            success: true,
+            error: String::new(),
+        });
+    }
+    let list_file = checkpoint_dir().join(format!("{}.files", run_id));
+    let mut content = remaining.join("\n");
+    content.push('\n');
+    fs::write(&list_file, content)?;
+    body["_filter"]["FilesFromRaw"] = json!([list_file.to_string_lossy()]);
+
+    let result = async {
+        let job_id = rclone::start_job(op, body, group).await?;
+        let mut wait = Box::pin(rclone::wait_job(job_id));
+        loop {
+            tokio::select! {
+                outcome = &mut wait => break outcome,
+                _ = tokio::time::sleep(SAVE_INTERVAL) => {
+                    if record_transferred(&mut checkpoint, group).await {
+                        checkpoint.updated_at = now_ms();
+                        let _ = save(&checkpoint);
+                    }
+                }
+            }
+        }
+    }
+    .await;
+    let _ = fs::remove_file(&list_file);
+
+    if matches!(&result, Ok(outcome) if outcome.success) {
+        remove(&task.name);
+    } else {
+        record_transferred(&mut checkpoint, group).await;
+        checkpoint.updated_at = now_ms();
+        save(&checkpoint)?;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::TaskEndpoint;
+
+    fn checkpoint() -> Checkpoint {
+        Checkpoint {
+            task_name: "t".to_string(),
+            files: [("a.txt", 10), ("dir/b.bin", 20), ("c.txt", 5)]
+                .into_iter()
+                .map(|(path, size)| CheckpointFile {
+                    path: path.to_string(),
+                    size,
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn remaining_excludes_completed_files_in_listing_order() {
+        let mut c = checkpoint();
+        assert_eq!(c.remaining(), ["a.txt", "dir/b.bin", "c.txt"]);
+        c.completed.insert("dir/b.bin".to_string());
+        // 不在清单中的已完成记录不影响剩余文件
+        c.completed.insert("other.txt".to_string());
+        assert_eq!(c.remaining(), ["a.txt", "c.txt"]);
+    }
+
+    #[test]
+    fn progress_counts_only_listed_files() {
+        let mut c = checkpoint();
+        c.completed.insert("a.txt".to_string());
+        c.completed.insert("other.txt".to_string());
+        let p = c.progress();
+        assert_eq!((p.total_files, p.total_bytes), (3, 35));
+        assert_eq!((p.completed_files, p.completed_bytes), (1, 10));
+    }
+
+    #[test]
+    fn record_items_skips_failed_transfers() {
+        let mut c = checkpoint();
+        let items = [
+            json!({ "name": "a.txt", "error": "" }),
+            json!({ "name": "c.txt", "error": "connection reset" }),
+            json!({ "name": "dir/b.bin" }),
+            json!({ "error": "" }),
+        ];
+        assert!(record_items(&mut c, &items));
+        assert_eq!(c.remaining(), ["c.txt"]);
+        // 重复记录不算新增
+        assert!(!record_items(&mut c, &items));
+    }
+
+    #[test]
+    fn enabled_only_for_resumable_directory_copies() {
+        let mut task = TaskSpec {
+            task_type: "copy".to_string(),
+            source: TaskEndpoint {
+                storage_name: "a".to_string(),
+                path: "/src/".to_string(),
+            },
+            target: TaskEndpoint {
+                storage_name: "b".to_string(),
+                path: "/dst/".to_string(),
+            },
+            parameters: Some(json!({ "resumable": true })),
+            ..Default::default()
+        };
+        assert!(enabled_for(&task));
+        task.task_type = "sync".to_string();
+        assert!(!enabled_for(&task));
+        task.task_type = "move".to_string();
+        task.source.path = "/src/file.txt".to_string();
+        assert!(!enabled_for(&task));
+        task.source.path = "/src/".to_string();
+        task.files_from = Some(std::path::PathBuf::from("list.txt"));
+        assert!(!enabled_for(&task));
+    }
+}
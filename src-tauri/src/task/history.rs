@@ -0,0 +1,332 @@
+//! 任务运行历史：数据目录下的追加式 JSONL 文件，每行一条运行记录。
+
+use std::{
+    fs,
+    io::{BufRead, BufReader, Write},
+    path::PathBuf,
+    sync::Mutex,
+};
+
+use serde::{Deserialize, Serialize};
+
+use super::now_ms;
+
+/// 历史文件超过该大小时在写入后自动按保留策略裁剪
+const PRUNE_THRESHOLD_BYTES: u64 = 1024 * 1024;
+/// 默认最多保留的记录数
+const DEFAULT_MAX_RECORDS: usize = 1000;
+/// 默认最多保留的天数
+const DEFAULT_MAX_AGE_DAYS: u64 = 90;
+
+lazy_static::lazy_static! {
+    static ref HISTORY_LOCK: Mutex<()> = Mutex::new(());
+}
+
+/// 单次运行的结果统计（与前端 `TaskResult` 对应）
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskResult {
+    pub success: bool,
+    pub transferred_files: u64,
+    pub transferred_bytes: u64,
+    pub errors: u64,
+    /// 执行耗时（毫秒）
+    pub duration: u64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub error_messages: Vec<String>,
+}
+
+/// 一条运行记录（与前端 `TaskHistory` 对应）
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskHistory {
+    pub id: String,
+    pub task_id: String,
+    pub task_name: String,
+    #[serde(rename = "type")]
+    pub task_type: String,
+    /// success / failed / cancelled
+    pub status: String,
+    /// 开始时间（Unix 毫秒）
+    pub started_at: u64,
+    /// 结束时间（Unix 毫秒）
+    pub completed_at: u64,
+    /// rclone 异步 job ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub job_id: Option<u64>,
+    /// 运行日志文件名（位于 `log/task-runs/`）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_file: Option<String>,
+    /// 所属任务链的 ID（由前置任务触发的一组运行共享）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<TaskResult>,
+}
+
+/// 历史查询条件，所有字段均可选
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryQuery {
+    pub task_name: Option<String>,
+    pub status: Option<String>,
+    pub chain_id: Option<String>,
+    /// 开始时间下限（Unix 毫秒，含）
+    pub from: Option<u64>,
+    /// 开始时间上限（Unix 毫秒，含）
+    pub to: Option<u64>,
+    /// 最多返回条数（按开始时间倒序）
+    pub limit: Option<usize>,
+}
+
+/// 保留策略
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Retention {
+    pub max_records: usize,
+    pub max_age_days: u64,
+}
+
+impl Default for Retention {
+    fn default() -> Self {
+        Self {
+            max_records: DEFAULT_MAX_RECORDS,
+            max_age_days: DEFAULT_MAX_AGE_DAYS,
+        }
+    }
+}
+
+impl Retention {
+    /// 从 `settings.taskHistory` 读取保留策略，缺省项使用默认值
+    pub fn from_settings(settings: &serde_json::Value) -> Self {
+        let default = Self::default();
+        let section = &settings["taskHistory"];
+        Self {
+            max_records: section["maxRecords"]
+                .as_u64()
+                .map(|v| v as usize)
+                .unwrap_or(default.max_records),
+            max_age_days: section["maxAgeDays"]
+                .as_u64()
+                .unwrap_or(default.max_age_days),
+        }
+    }
+}
+
+pub fn history_file() -> PathBuf {
+    crate::resolve_data_dir().join("task").join("history.jsonl")
+}
+
+fn read_all() -> anyhow::Result<Vec<TaskHistory>> {
+    let path = history_file();
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let reader = BufReader::new(fs::File::open(path)?);
+    let mut records = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        // 单行损坏（如写入时崩溃）不影响其余记录
+        if let Ok(record) = serde_json::from_str::<TaskHistory>(&line) {
+            records.push(record);
+        }
+    }
+    Ok(records)
+}
+
+fn write_all(records: &[TaskHistory]) -> anyhow::Result<()> {
+    let path = history_file();
+    let tmp = path.with_extension("jsonl.tmp");
+    {
+        let mut file = fs::File::create(&tmp)?;
+        for record in records {
+            writeln!(file, "{}", serde_json::to_string(record)?)?;
+        }
+        file.sync_all()?;
+    }
+    fs::rename(tmp, path)?;
+    Ok(())
+}
+
+/// 按保留策略筛选记录（`now` 为当前 Unix 毫秒），保留较新的记录
+fn apply_retention(records: Vec<TaskHistory>, retention: &Retention, now: u64) -> Vec<TaskHistory> {
+    let min_started = now.saturating_sub(retention.max_age_days * 24 * 60 * 60 * 1000);
+    let mut kept: Vec<TaskHistory> = records
+        .into_iter()
+        .filter(|r| retention.max_age_days == 0 || r.started_at >= min_started)
+        .collect();
+    if kept.len() > retention.max_records {
+        kept.drain(..kept.len() - retention.max_records);
+    }
+    kept
+}
+
+/// 按条件筛选记录，结果按开始时间倒序
+fn apply_query(records: Vec<TaskHistory>, q: &HistoryQuery) -> Vec<TaskHistory> {
+    let mut records: Vec<TaskHistory> = records
+        .into_iter()
+        .filter(|r| q.task_name.as_ref().is_none_or(|n| &r.task_name == n))
+        .filter(|r| q.status.as_ref().is_none_or(|s| &r.status == s))
+        .filter(|r| q.chain_id.is_none() || r.chain_id == q.chain_id)
+        .filter(|r| q.from.is_none_or(|from| r.started_at >= from))
+        .filter(|r| q.to.is_none_or(|to| r.started_at <= to))
+        .collect();
+    records.sort_by_key(|r| std::cmp::Reverse(r.started_at));
+    if let Some(limit) = q.limit {
+        records.truncate(limit);
+    }
+    records
+}
+
+fn prune_locked(retention: &Retention) -> anyhow::Result<usize> {
+    let records = read_all()?;
+    let before = records.len();
+    let kept = apply_retention(records, retention, now_ms());
+    let removed = before - kept.len();
+    if removed > 0 {
+        write_all(&kept)?;
+    }
+    Ok(removed)
+}
+
+/// 追加一条记录；文件过大时按保留策略裁剪
+pub fn append(record: &TaskHistory, retention: &Retention) -> anyhow::Result<()> {
+    let _guard = HISTORY_LOCK.lock().unwrap();
+    let path = history_file();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)?;
+    writeln!(file, "{}", serde_json::to_string(record)?)?;
+    let len = file.metadata().map(|m| m.len()).unwrap_or(0);
+    drop(file);
+
+    if len > PRUNE_THRESHOLD_BYTES {
+        prune_locked(retention)?;
+    }
+    Ok(())
+}
+
+/// 按条件查询，结果按开始时间倒序
+pub fn query(q: &HistoryQuery) -> anyhow::Result<Vec<TaskHistory>> {
+    let _guard = HISTORY_LOCK.lock().unwrap();
+    Ok(apply_query(read_all()?, q))
+}
+
+/// 按保留策略裁剪，返回删除的记录数
+pub fn prune(retention: &Retention) -> anyhow::Result<usize> {
+    let _guard = HISTORY_LOCK.lock().unwrap();
+    prune_locked(retention)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+    fn record(task: &str, status: &str, started_at: u64) -> TaskHistory {
+        TaskHistory {
+            id: format!("{}-{}", task, started_at),
+            task_name: task.to_string(),
+            status: status.to_string(),
+            started_at,
+            completed_at: started_at + 1000,
+            ..Default::default()
+        }
+    }
+
+    fn ids(records: &[TaskHistory]) -> Vec<&str> {
+        records.iter().map(|r| r.id.as_str()).collect()
+    }
+
+    fn sample() -> Vec<TaskHistory> {
+        vec![
+            record("a", "success", 100),
+            record("b", "failed", 200),
+            record("a", "failed", 300),
+            TaskHistory {
+                chain_id: Some("c1".to_string()),
+                ..record("b", "success", 400)
+            },
+        ]
+    }
+
+    #[test]
+    fn query_without_filters_returns_newest_first() {
+        let records = apply_query(sample(), &HistoryQuery::default());
+        assert_eq!(ids(&records), ["b-400", "a-300", "b-200", "a-100"]);
+    }
+
+    #[test]
+    fn query_filters_by_task_and_status() {
+        let q = HistoryQuery {
+            task_name: Some("a".to_string()),
+            status: Some("failed".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(ids(&apply_query(sample(), &q)), ["a-300"]);
+    }
+
+    #[test]
+    fn query_filters_by_chain() {
+        let q = HistoryQuery {
+            chain_id: Some("c1".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(ids(&apply_query(sample(), &q)), ["b-400"]);
+    }
+
+    #[test]
+    fn query_date_range_is_inclusive_and_limited() {
+        let q = HistoryQuery {
+            from: Some(200),
+            to: Some(400),
+            limit: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(ids(&apply_query(sample(), &q)), ["b-400", "a-300"]);
+    }
+
+    #[test]
+    fn retention_drops_records_older_than_max_age() {
+        let now = 100 * DAY_MS;
+        let records = vec![
+            record("a", "success", now - 31 * DAY_MS),
+            record("a", "success", now - 29 * DAY_MS),
+            record("a", "success", now),
+        ];
+        let retention = Retention {
+            max_records: 10,
+            max_age_days: 30,
+        };
+        let kept = apply_retention(records, &retention, now);
+        assert_eq!(kept.len(), 2);
+        assert!(kept.iter().all(|r| r.started_at >= now - 30 * DAY_MS));
+    }
+
+    #[test]
+    fn retention_keeps_newest_records_up_to_max_records() {
+        let retention = Retention {
+            max_records: 2,
+            max_age_days: 0,
+        };
+        let kept = apply_retention(sample(), &retention, 0);
+        assert_eq!(ids(&kept), ["a-300", "b-400"]);
+    }
+
+    #[test]
+    fn retention_from_settings_uses_defaults_for_missing_fields() {
+        let retention = Retention::from_settings(&serde_json::json!({
+            "taskHistory": { "maxRecords": 5 }
+        }));
+        assert_eq!(retention.max_records, 5);
+        assert_eq!(retention.max_age_days, DEFAULT_MAX_AGE_DAYS);
+    }
+}
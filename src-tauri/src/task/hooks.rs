@@ -0,0 +1,267 @@
+//! 任务前后钩子：在任务运行前后执行本地命令（如备份前导出数据库、成功后请求健康检查地址）。
+//!
+//! 任务上下文通过 `NETMOUNT_TASK_*` 环境变量传入，命令输出写入本次运行的日志。
+
+use std::process::Stdio;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::{runlog, TaskSpec};
+
+/// 默认超时（秒）
+const DEFAULT_TIMEOUT_SECS: u64 = 300;
+
+/// `preHook` / `postHook` 定义
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskHook {
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// 超时（秒），缺省为 300
+    #[serde(default)]
+    pub timeout: Option<u64>,
+    /// 失败（非零退出、超时或无法启动）时是否使任务失败：
+    /// 前置钩子失败将跳过任务，后置钩子失败将把本次运行标记为失败
+    #[serde(default)]
+    pub abort_on_failure: bool,
+}
+
+/// 传给钩子的任务上下文
+pub struct HookContext<'a> {
+    pub task: &'a TaskSpec,
+    pub run_id: &'a str,
+    /// 前置钩子为 running，后置钩子为 success / failed
+    pub status: &'a str,
+    pub transferred_bytes: u64,
+    pub transferred_files: u64,
+    pub errors: u64,
+}
+
+/// 执行钩子，失败时返回错误信息。输出与结果写入运行日志
+pub async fn run(kind: &str, hook: &TaskHook, ctx: &HookContext<'_>) -> Result<(), String> {
+    if hook.command.trim().is_empty() {
+        return Ok(());
+    }
+    let _ = runlog::append(
+        ctx.run_id,
+        &format!(
+            "--- {} hook: {} {} ---",
+            kind,
+            hook.command,
+            hook.args.join(" ")
+        ),
+    );
+
+    let result = spawn(kind, hook, ctx).await;
+    let footer = match &result {
+        Ok(()) => format!("--- {} hook succeeded ---", kind),
+        Err(e) => format!("--- {} hook failed: {} ---", kind, e),
+    };
+    let _ = runlog::append(ctx.run_id, &footer);
+    result
+}
+
+/// 前置钩子的处理结果：要求中止时返回 Err（跳过任务本身），否则失败信息作为运行错误记录
+pub fn pre_outcome(hook: &TaskHook, result: Result<(), String>) -> Result<Option<String>, String> {
+    match result {
+        Ok(()) => Ok(None),
+        Err(e) if hook.abort_on_failure => Err(format!("Pre-hook failed: {}", e)),
+        Err(e) => Ok(Some(format!("Pre-hook failed: {}", e))),
+    }
+}
+
+async fn spawn(kind: &str, hook: &TaskHook, ctx: &HookContext<'_>) -> Result<(), String> {
+    let task = ctx.task;
+    let mut cmd = std::process::Command::new(&hook.command);
+    cmd.args(&hook.args)
+        .env("NETMOUNT_HOOK", kind)
+        .env("NETMOUNT_TASK_NAME", &task.name)
+        .env("NETMOUNT_TASK_TYPE", &task.task_type)
+        .env("NETMOUNT_TASK_RUN_ID", ctx.run_id)
+        .env("NETMOUNT_TASK_STATUS", ctx.status)
+        .env(
+            "NETMOUNT_TASK_SOURCE",
+            format!("{}:{}", task.source.storage_name, task.source.path),
+        )
+        .env(
+            "NETMOUNT_TASK_TARGET",
+            format!("{}:{}", task.target.storage_name, task.target.path),
+        )
+        .env("NETMOUNT_TASK_BYTES", ctx.transferred_bytes.to_string())
+        .env("NETMOUNT_TASK_FILES", ctx.transferred_files.to_string())
+        .env("NETMOUNT_TASK_ERRORS", ctx.errors.to_string())
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        use winapi::um::winbase::CREATE_NO_WINDOW;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let child = tokio::process::Command::from(cmd)
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("failed to start {}: {}", hook.command, e))?;
+
+    let timeout = Duration::from_secs(hook.timeout.unwrap_or(DEFAULT_TIMEOUT_SECS));
+    // 超时后 future 被丢弃，kill_on_drop 负责结束子进程
+    let output = tokio::time::timeout(timeout, child.wait_with_output())
+        .await
+        .map_err(|_| format!("timed out after {}s", timeout.as_secs()))?
+        .map_err(|e| e.to_string())?;
+
+    let mut text = String::from_utf8_lossy(&output.stdout).to_string();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !stderr.trim().is_empty() {
+        text.push_str(&stderr);
+    }
+    if !text.trim().is_empty() {
+        let _ = runlog::append(ctx.run_id, text.trim_end());
+    }
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(match output.status.code() {
+            Some(code) => format!("exited with code {}", code),
+            None => "terminated by signal".to_string(),
+        })
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::super::TaskEndpoint;
+    use super::*;
+
+    fn hook(script: &str, timeout: Option<u64>) -> TaskHook {
+        TaskHook {
+            command: "sh".to_string(),
+            args: vec!["-c".to_string(), script.to_string()],
+            timeout,
+            abort_on_failure: false,
+        }
+    }
+
+    fn task() -> TaskSpec {
+        TaskSpec {
+            name: "backup".to_string(),
+            task_type: "sync".to_string(),
+            source: TaskEndpoint {
+                storage_name: "local".to_string(),
+                path: "/home/me/docs/".to_string(),
+            },
+            target: TaskEndpoint {
+                storage_name: "s3".to_string(),
+                path: "/bucket/docs/".to_string(),
+            },
+            ..Default::default()
+        }
+    }
+
+    fn ctx(task: &TaskSpec) -> HookContext<'_> {
+        HookContext {
+            task,
+            run_id: "20240101-hooktest",
+            status: "success",
+            transferred_bytes: 2048,
+            transferred_files: 3,
+            errors: 1,
+        }
+    }
+
+    fn temp_file(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("netmount-hook-{}-{}", name, std::process::id()))
+    }
+
+    #[tokio::test]
+    async fn passes_task_context_in_env() {
+        let out = temp_file("env");
+        let task = task();
+        let script = format!("env | grep '^NETMOUNT_' | sort > '{}'", out.display());
+        spawn("post", &hook(&script, None), &ctx(&task))
+            .await
+            .unwrap();
+        let env = std::fs::read_to_string(&out).unwrap();
+        let _ = std::fs::remove_file(&out);
+        let lines: Vec<&str> = env.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                "NETMOUNT_HOOK=post",
+                "NETMOUNT_TASK_BYTES=2048",
+                "NETMOUNT_TASK_ERRORS=1",
+                "NETMOUNT_TASK_FILES=3",
+                "NETMOUNT_TASK_NAME=backup",
+                "NETMOUNT_TASK_RUN_ID=20240101-hooktest",
+                "NETMOUNT_TASK_SOURCE=local:/home/me/docs/",
+                "NETMOUNT_TASK_STATUS=success",
+                "NETMOUNT_TASK_TARGET=s3:/bucket/docs/",
+                "NETMOUNT_TASK_TYPE=sync",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn reports_exit_code_and_start_failure() {
+        let task = task();
+        assert_eq!(
+            spawn("pre", &hook("exit 3", None), &ctx(&task)).await,
+            Err("exited with code 3".to_string())
+        );
+        let missing = TaskHook {
+            command: "/nonexistent/netmount-hook".to_string(),
+            ..Default::default()
+        };
+        let err = spawn("pre", &missing, &ctx(&task)).await.unwrap_err();
+        assert!(err.starts_with("failed to start"), "{}", err);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn kills_hook_on_timeout() {
+        let pid_file = temp_file("pid");
+        let task = task();
+        let script = format!("echo $$ > '{}'; exec sleep 30", pid_file.display());
+        let started = std::time::Instant::now();
+        let result = spawn("pre", &hook(&script, Some(1)), &ctx(&task)).await;
+        assert_eq!(result, Err("timed out after 1s".to_string()));
+        assert!(started.elapsed() < Duration::from_secs(10));
+
+        let pid = std::fs::read_to_string(&pid_file).unwrap();
+        let _ = std::fs::remove_file(&pid_file);
+        let stat = format!("/proc/{}/stat", pid.trim());
+        // 进程被结束后可能短暂保留为僵尸进程，等待回收
+        let mut alive = true;
+        for _ in 0..50 {
+            alive = std::fs::read_to_string(&stat).is_ok_and(|s| s.split(' ').nth(2) != Some("Z"));
+            if !alive {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        assert!(!alive, "hook process {} is still running", pid.trim());
+    }
+
+    #[tokio::test]
+    async fn failing_pre_hook_aborts_only_when_requested() {
+        let task = task();
+        let mut failing = hook("exit 1", None);
+        let result = spawn("pre", &failing, &ctx(&task)).await;
+        assert_eq!(
+            pre_outcome(&failing, result.clone()),
+            Ok(Some("Pre-hook failed: exited with code 1".to_string()))
+        );
+        failing.abort_on_failure = true;
+        assert_eq!(
+            pre_outcome(&failing, result),
+            Err("Pre-hook failed: exited with code 1".to_string())
+        );
+        assert_eq!(pre_outcome(&failing, Ok(())), Ok(None));
+    }
+}
@@ -0,0 +1,402 @@
+//! 后端任务执行：直接通过 rclone rc 运行 `config.json` 中定义的任务，并记录运行历史。
+
+use rand::distr::SampleString as _;
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::{AppExt, Runtime};
+
+pub mod bisync;
+pub mod chain;
+pub mod check;
+pub mod checkpoint;
+pub mod history;
+pub mod hooks;
+pub mod plan;
+pub mod queue;
+pub mod runlog;
+mod runner;
+pub mod versions;
+pub mod watch;
+
+use bisync::BisyncStatus;
+use chain::{ChainRun, TaskDependency};
+use check::CheckReport;
+use checkpoint::CheckpointProgress;
+use history::{HistoryQuery, Retention, TaskHistory};
+use hooks::TaskHook;
+use plan::TaskPlan;
+use queue::{ConcurrencyLimits, QueueSnapshot};
+use runlog::LogRetention;
+use versions::{FileVersion, Versioning};
+use watch::WatchStatus;
+
+/// 任务的一端（存储 + 路径）
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskEndpoint {
+    pub storage_name: String,
+    pub path: String,
+}
+
+/// `config.json` 中 `task[]` 的一项（与前端 `TaskListItem` 对应，仅解析后端需要的字段）
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskSpec {
+    pub name: String,
+    pub task_type: String,
+    #[serde(default)]
+    pub source: TaskEndpoint,
+    #[serde(default)]
+    pub target: TaskEndpoint,
+    #[serde(default)]
+    pub parameters: Option<serde_json::Value>,
+    #[serde(default)]
+    pub enable: bool,
+    /// 前置任务，满足条件时在其后运行
+    #[serde(default)]
+    pub after: Vec<TaskDependency>,
+    /// 排队优先级，越大越先运行
+    #[serde(default)]
+    pub priority: i32,
+    /// 任务运行前执行的本地命令
+    #[serde(default)]
+    pub pre_hook: Option<TaskHook>,
+    /// 任务运行后执行的本地命令
+    #[serde(default)]
+    pub post_hook: Option<TaskHook>,
+    /// 覆盖或删除目标文件时保留旧版本
+    #[serde(default)]
+    pub versioning: Option<Versioning>,
+    #[serde(default)]
+    pub run: TaskRun,
+    /// 本次运行只处理列表文件中的路径（相对源目录），由变更监听设置
+    #[serde(skip)]
+    pub files_from: Option<std::path::PathBuf>,
+    /// 本次运行强制 resync，只由确认预览后的 `bisync_resync` 设置
+    #[serde(skip)]
+    pub resync: bool,
+}
+
+/// `run` 中后端需要的字段
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskRun {
+    #[serde(default)]
+    pub mode: String,
+    /// `onChange` 模式下合并变更的时间（秒），缺省为 5
+    #[serde(default)]
+    pub debounce: Option<u64>,
+}
+
+impl TaskSpec {
+    /// `parameters.filterRules`
+    pub fn filter_rules(&self) -> Vec<String> {
+        self.parameters
+            .as_ref()
+            .and_then(|p| p.get("filterRules"))
+            .and_then(|v| v.as_array())
+            .map(|arr| {
+                arr.iter()
+                    .filter_map(|v| v.as_str().map(|s| s.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// 布尔类型的 `parameters.<key>`
+    pub fn bool_param(&self, key: &str) -> bool {
+        self.parameters
+            .as_ref()
+            .and_then(|p| p.get(key))
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false)
+    }
+}
+
+pub(crate) fn now_ms() -> u64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// 生成运行 ID：毫秒时间戳 + 随机后缀，按字典序大致有序
+pub(crate) fn new_run_id() -> String {
+    format!(
+        "{}-{}",
+        now_ms(),
+        rand::distr::Alphanumeric.sample_string(&mut rand::rng(), 6)
+    )
+}
+
+/// 解析配置中的 `task[]`，无法解析的项忽略
+pub(crate) fn parse_tasks(config: &serde_json::Value) -> Vec<TaskSpec> {
+    config["task"]
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .filter_map(|v| serde_json::from_value::<TaskSpec>(v.clone()).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// 从当前配置中读取全部任务
+pub(crate) fn load_tasks(app: &tauri::AppHandle<Runtime>) -> Vec<TaskSpec> {
+    app.with_app_state::<Config, _>(|config| parse_tasks(&config.0))
+}
+
+/// 按名称查找任务
+pub(crate) fn find_task(app: &tauri::AppHandle<Runtime>, name: &str) -> anyhow::Result<TaskSpec> {
+    load_tasks(app)
+        .into_iter()
+        .find(|t| t.name == name)
+        .ok_or_else(|| anyhow::anyhow!("Task not found: {}", name))
+}
+
+pub(crate) fn history_retention(app: &tauri::AppHandle<Runtime>) -> Retention {
+    app.with_app_state::<Config, _>(|config| Retention::from_settings(&config.0["settings"]))
+}
+
+pub(crate) fn concurrency_limits(app: &tauri::AppHandle<Runtime>) -> ConcurrencyLimits {
+    app.with_app_state::<Config, _>(|config| {
+        ConcurrencyLimits::from_settings(&config.0["settings"])
+    })
+}
+
+pub(crate) fn log_retention(app: &tauri::AppHandle<Runtime>) -> LogRetention {
+    app.with_app_state::<Config, _>(|config| LogRetention::from_settings(&config.0["settings"]))
+}
+
+/// 立即运行指定任务及依赖它的下游任务，返回整条链的运行结果
+#[tauri::command]
+pub async fn run_task(
+    app: tauri::AppHandle<Runtime>,
+    name: String,
+) -> anyhow_tauri::TAResult<ChainRun> {
+    let tasks = load_tasks(&app);
+    let task = tasks
+        .iter()
+        .find(|t| t.name == name)
+        .ok_or_else(|| anyhow::anyhow!("Task not found: {}", name))?;
+    Ok(chain::run_chain(&app, &tasks, task, &concurrency_limits(&app)).await?)
+}
+
+/// 查看运行队列（运行中与排队中的任务）
+#[tauri::command]
+pub fn get_task_queue() -> anyhow_tauri::TAResult<QueueSnapshot> {
+    Ok(queue::snapshot())
+}
+
+/// 调整排队项的位置
+#[tauri::command]
+pub fn move_queued_task(id: String, index: usize) -> anyhow_tauri::TAResult<()> {
+    Ok(queue::move_entry(&id, index)?)
+}
+
+/// 取消排队中的任务
+#[tauri::command]
+pub fn cancel_queued_task(id: String) -> anyhow_tauri::TAResult<()> {
+    Ok(queue::cancel_entry(&id)?)
+}
+
+/// 查询任务运行历史
+#[tauri::command]
+pub fn get_task_history(query: Option<HistoryQuery>) -> anyhow_tauri::TAResult<Vec<TaskHistory>> {
+    Ok(history::query(&query.unwrap_or_default())?)
+}
+
+/// 按保留策略裁剪运行历史，未指定时使用 `settings.taskHistory`
+#[tauri::command]
+pub fn prune_task_history(
+    app: tauri::AppHandle<Runtime>,
+    retention: Option<Retention>,
+) -> anyhow_tauri::TAResult<usize> {
+    let retention = retention.unwrap_or_else(|| history_retention(&app));
+    Ok(history::prune(&retention)?)
+}
+
+/// 读取单次运行的日志：指定 `search` 时按关键字过滤，否则返回末尾 `max_bytes`
+#[tauri::command]
+pub fn read_task_run_log(
+    run_id: String,
+    max_bytes: Option<u64>,
+    search: Option<String>,
+) -> anyhow_tauri::TAResult<String> {
+    Ok(runlog::read(&run_id, max_bytes, search.as_deref())?)
+}
+
+/// 按保留策略清理运行日志，未指定时使用 `settings.taskRunLog`
+#[tauri::command]
+pub fn clean_task_run_logs(
+    app: tauri::AppHandle<Runtime>,
+    retention: Option<LogRetention>,
+) -> anyhow_tauri::TAResult<usize> {
+    let retention = retention.unwrap_or_else(|| log_retention(&app));
+    Ok(runlog::cleanup(&retention)?)
+}
+
+/// 以 `--dry-run` 运行任务并返回计划，`save` 为 true 时保存以便确认
+#[tauri::command]
+pub async fn dry_run_task(
+    app: tauri::AppHandle<Runtime>,
+    name: String,
+    save: Option<bool>,
+) -> anyhow_tauri::TAResult<TaskPlan> {
+    let task = find_task(&app, &name)?;
+    let plan = plan::dry_run(&task).await?;
+    if save.unwrap_or(false) {
+        plan::save(&plan)?;
+    }
+    Ok(plan)
+}
+
+/// 列出已保存的计划（不含文件清单）
+#[tauri::command]
+pub fn list_task_plans(task_name: Option<String>) -> anyhow_tauri::TAResult<Vec<TaskPlan>> {
+    Ok(plan::list(task_name.as_deref())?)
+}
+
+/// 读取已保存的计划
+#[tauri::command]
+pub fn get_task_plan(id: String) -> anyhow_tauri::TAResult<TaskPlan> {
+    Ok(plan::load(&id)?)
+}
+
+/// 确认已保存的计划
+#[tauri::command]
+pub fn approve_task_plan(id: String) -> anyhow_tauri::TAResult<TaskPlan> {
+    Ok(plan::approve(&id)?)
+}
+
+/// 读取校验任务的报告
+#[tauri::command]
+pub fn get_check_report(run_id: String) -> anyhow_tauri::TAResult<CheckReport> {
+    Ok(check::load(&run_id)?)
+}
+
+/// 导出校验报告，格式由扩展名（.csv / .json）决定，返回输出路径
+#[tauri::command]
+pub fn export_check_report(
+    app: tauri::AppHandle<Runtime>,
+    run_id: String,
+    out_path: String,
+) -> anyhow_tauri::TAResult<String> {
+    let out_path = crate::fs::resolve_path(&app, out_path.trim())?;
+    check::export(&run_id, &out_path)?;
+    Ok(out_path.to_string_lossy().to_string())
+}
+
+/// 查看双向同步任务的 workdir 状态与锁文件
+#[tauri::command]
+pub fn get_bisync_state(name: String) -> anyhow_tauri::TAResult<BisyncStatus> {
+    Ok(bisync::status(&name))
+}
+
+/// 清理无进程持有的 bisync 锁文件，返回删除数量
+#[tauri::command]
+pub fn clean_bisync_locks(name: String) -> anyhow_tauri::TAResult<usize> {
+    Ok(bisync::clean_task_locks(&name)?)
+}
+
+/// 预览 resync（`--resync --dry-run`），返回的计划 ID 用于确认执行
+#[tauri::command]
+pub async fn preview_bisync_resync(
+    app: tauri::AppHandle<Runtime>,
+    name: String,
+) -> anyhow_tauri::TAResult<TaskPlan> {
+    let task = find_task(&app, &name)?;
+    Ok(bisync::preview_resync(&task).await?)
+}
+
+/// 按预览执行 resync，需要一小时内生成且无错误的预览
+#[tauri::command]
+pub async fn bisync_resync(
+    app: tauri::AppHandle<Runtime>,
+    name: String,
+    preview_id: String,
+) -> anyhow_tauri::TAResult<TaskHistory> {
+    let mut task = find_task(&app, &name)?;
+    if task.task_type != "bisync" {
+        return Err(anyhow::anyhow!("Task {} is not a bisync task", name).into());
+    }
+    bisync::check_preview(&task, &preview_id)?;
+    task.resync = true;
+
+    let _permit = match queue::admit(&task, &concurrency_limits(&app)).await {
+        queue::Admission::Run(permit) => permit,
+        queue::Admission::Coalesced => {
+            return Err(anyhow::anyhow!("Task {} is already running", name).into())
+        }
+        queue::Admission::Cancelled => {
+            return Err(anyhow::anyhow!("Resync of {} was cancelled", name).into())
+        }
+    };
+    Ok(runner::execute(&app, &task, None).await?)
+}
+
+/// 列出目标目录中某个文件（相对任务目标路径）的历史版本
+#[tauri::command]
+pub async fn list_task_versions(
+    app: tauri::AppHandle<Runtime>,
+    name: String,
+    path: String,
+) -> anyhow_tauri::TAResult<Vec<FileVersion>> {
+    let task = find_task(&app, &name)?;
+    Ok(versions::list_versions(&task, &path).await?)
+}
+
+/// 把文件恢复为指定版本，被替换的当前文件同样保留为新版本
+#[tauri::command]
+pub async fn restore_task_version(
+    app: tauri::AppHandle<Runtime>,
+    name: String,
+    path: String,
+    version: String,
+) -> anyhow_tauri::TAResult<()> {
+    let task = find_task(&app, &name)?;
+    Ok(versions::restore(&task, &path, &version).await?)
+}
+
+/// 开始监听 `onChange` 任务的本地源目录
+#[tauri::command]
+pub async fn start_task_watch(
+    app: tauri::AppHandle<Runtime>,
+    name: String,
+) -> anyhow_tauri::TAResult<WatchStatus> {
+    let task = find_task(&app, &name)?;
+    Ok(watch::start(&app, &task).await?)
+}
+
+/// 停止监听，返回此前是否在监听
+#[tauri::command]
+pub fn stop_task_watch(name: String) -> anyhow_tauri::TAResult<bool> {
+    Ok(watch::stop(&name))
+}
+
+/// 查看全部监听状态
+#[tauri::command]
+pub fn list_task_watches() -> anyhow_tauri::TAResult<Vec<WatchStatus>> {
+    Ok(watch::list())
+}
+
+/// 查看任务的断点续传进度，没有检查点时为空
+#[tauri::command]
+pub fn get_task_checkpoint(name: String) -> anyhow_tauri::TAResult<Option<CheckpointProgress>> {
+    Ok(checkpoint::load(&name).map(|c| c.progress()))
+}
+
+/// 丢弃检查点，下次运行从头列出源文件
+#[tauri::command]
+pub fn discard_task_checkpoint(name: String) -> anyhow_tauri::TAResult<bool> {
+    if queue::snapshot()
+        .running
+        .iter()
+        .any(|e| e.task_name == name)
+    {
+        return Err(anyhow::anyhow!("Task {} is running", name).into());
+    }
+    Ok(checkpoint::remove(&name))
+}
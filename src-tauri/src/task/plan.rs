@@ -0,0 +1,667 @@
+//! 运行计划（dry-run）：以 `--dry-run` 运行 copy / move / sync 任务，把 rclone 的输出
+//! 解析为待复制、更新、删除与跳过的文件清单，可保存到 `task/plans/` 供确认后再运行。
+//!
+//! 设置了 `parameters.requireApproval` 的 sync 任务运行前必须有一份已确认、未过期且与当前
+//! 任务参数一致的计划，每份确认的计划只能用于一次运行。
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::{new_run_id, now_ms, runlog, runner, TaskSpec};
+use crate::rclone::{self, file_name, join_remote, StorageRef};
+
+/// 最多保留的已保存计划数
+const MAX_SAVED_PLANS: usize = 100;
+/// 计划生成后可用于确认运行的时长
+const APPROVAL_VALID_MS: u64 = 24 * 60 * 60 * 1000;
+
+lazy_static::lazy_static! {
+    /// 确认与使用计划时的锁，避免同一份计划被两次运行使用
+    static ref APPROVAL_LOCK: Mutex<()> = Mutex::new(());
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PlanAction {
+    /// 目标中不存在，将被复制
+    Copy,
+    /// 目标中存在但不同，将被覆盖
+    Update,
+    /// 仅存在于目标中，将被删除（sync）
+    Delete,
+    /// 两端相同，或仅存在于目标中但不会删除
+    Skip,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanEntry {
+    pub path: String,
+    pub action: PlanAction,
+    /// 文件大小（字节），rclone 未给出时为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActionTotal {
+    pub count: u64,
+    pub bytes: u64,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanTotals {
+    pub copy: ActionTotal,
+    pub update: ActionTotal,
+    pub delete: ActionTotal,
+    pub skip: ActionTotal,
+}
+
+/// 一次 dry-run 的结果
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskPlan {
+    pub id: String,
+    pub task_name: String,
+    pub task_type: String,
+    /// 生成时间（Unix 毫秒）
+    pub created_at: u64,
+    pub entries: Vec<PlanEntry>,
+    pub totals: PlanTotals,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+    /// 确认时间（Unix 毫秒），未确认时为空
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approved_at: Option<u64>,
+    /// 生成计划时的 rclone 参数摘要，任务参数变化后计划不再可用
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub fingerprint: String,
+    /// 被运行使用的时间（Unix 毫秒），使用后不能再次用于运行
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub consumed_at: Option<u64>,
+}
+
+impl TaskPlan {
+    /// 计划是否可用于本次运行：已确认、未使用、未过期且参数一致
+    fn authorizes(&self, task_name: &str, fingerprint: &str, now: u64) -> bool {
+        self.task_name == task_name
+            && self.approved_at.is_some()
+            && self.consumed_at.is_none()
+            && !self.fingerprint.is_empty()
+            && self.fingerprint == fingerprint
+            && self.errors.is_empty()
+            && now.saturating_sub(self.created_at) <= APPROVAL_VALID_MS
+    }
+}
+
+/// rclone 参数的摘要（SHA-256 十六进制）
+fn fingerprint(args: &[String]) -> String {
+    let mut hasher = Sha256::new();
+    for arg in args {
+        hasher.update(arg.as_bytes());
+        hasher.update([0]);
+    }
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// 任务是否要求运行前确认计划（`parameters.requireApproval`，仅 sync 会删除目标文件）
+pub fn approval_required(task: &TaskSpec) -> bool {
+    task.task_type == "sync" && task.bool_param("requireApproval")
+}
+
+pub fn plan_dir() -> PathBuf {
+    crate::resolve_data_dir().join("task").join("plans")
+}
+
+/// 计划 ID 即生成计划时的运行 ID
+fn plan_path(id: &str) -> anyhow::Result<PathBuf> {
+    runlog::check_run_id(id)?;
+    Ok(plan_dir().join(format!("{}.json", id)))
+}
+
+/// 组装 dry-run 的 rclone 命令行参数（不含 `--dry-run` 等通用参数）与用于确认运行的参数摘要
+async fn build_args(task: &TaskSpec) -> anyhow::Result<(Vec<String>, String)> {
+    let remotes = rclone::list_remotes().await?;
+    let src = StorageRef::resolve(&remotes, &task.source.storage_name);
+    let dst = StorageRef::resolve(&remotes, &task.target.storage_name);
+    let src_path = &task.source.path;
+    let dst_path = &task.target.path;
+    let src_is_dir = src_path.ends_with('/');
+    let dst_is_dir = dst_path.ends_with('/');
+
+    let mut args = match (task.task_type.as_str(), src_is_dir, dst_is_dir) {
+        ("copy" | "move" | "sync", true, true) => {
+            vec![task.task_type.clone(), src.fs(src_path), dst.fs(dst_path)]
+        }
+        ("copy" | "move", false, _) => {
+            let dst_remote = if dst_is_dir {
+                join_remote(&dst.remote(dst_path), file_name(src_path))
+            } else {
+                dst.remote(dst_path)
+            };
+            vec![
+                format!("{}to", task.task_type),
+                src.fs(src_path),
+                format!("{}{}", dst.root, dst_remote),
+            ]
+        }
+        ("copy" | "move", true, false) => {
+            return Err(anyhow::anyhow!(
+                "The directory cannot be copied/moved to a file"
+            ))
+        }
+        (other, _, _) => {
+            return Err(anyhow::anyhow!(
+                "Dry-run is only supported for copy/move/sync tasks, got {}",
+                other
+            ))
+        }
+    };
+    // 与实际运行使用相同的过滤规则与版本保留设置
+    let (rules, config) = runner::transfer_options(task).await?;
+    args.extend(rules.iter().map(|r| format!("--filter={}", r)));
+    let config = config.unwrap_or_default();
+    // 变更监听触发的运行只处理列表中的文件，计划与确认都只对同一份列表有效
+    let files = match &task.files_from {
+        Some(file) => Some(fs::read_to_string(file)?),
+        None => None,
+    };
+    let fingerprint = approval_fingerprint(&args, &config, files.as_deref());
+    if let Some(file) = &task.files_from {
+        args.push(format!("--files-from={}", file.display()));
+    }
+    args.extend(config_flags(&config));
+    Ok((args, fingerprint))
+}
+
+/// 确认计划用的参数摘要。版本目录与后缀带有运行时间，只记录设置项；
+/// 变更列表文件的路径每次不同，记录列表内容
+fn approval_fingerprint(
+    args: &[String],
+    config: &serde_json::Value,
+    files: Option<&str>,
+) -> String {
+    let mut keys: Vec<String> = config
+        .as_object()
+        .map(|obj| obj.keys().cloned().collect())
+        .unwrap_or_default();
+    keys.sort();
+    let mut parts = [args, keys.as_slice()].concat();
+    if let Some(files) = files {
+        parts.push(format!("--files-from={}", files));
+    }
+    fingerprint(&parts)
+}
+
+/// 把 `_config` 覆盖项转换为命令行参数，如 `BackupDir` -> `--backup-dir=...`
+fn config_flags(config: &serde_json::Value) -> Vec<String> {
+    let Some(obj) = config.as_object() else {
+        return Vec::new();
+    };
+    let mut flags = Vec::new();
+    for (key, value) in obj {
+        let mut flag = String::from("--");
+        for (i, c) in key.chars().enumerate() {
+            if c.is_ascii_uppercase() && i > 0 {
+                flag.push('-');
+            }
+            flag.push(c.to_ascii_lowercase());
+        }
+        match value {
+            serde_json::Value::Bool(true) => flags.push(flag),
+            serde_json::Value::String(s) => flags.push(format!("{}={}", flag, s)),
+            serde_json::Value::Null => {}
+            other => flags.push(format!("{}={}", flag, other)),
+        }
+    }
+    flags
+}
+
+/// dry-run 日志中被跳过的操作：路径 -> (操作, 大小)
+type SkippedOps = HashMap<String, (String, Option<u64>)>;
+
+/// 解析 `--use-json-log` 输出：返回 dry-run 跳过的操作与错误信息
+pub(super) fn parse_json_log(stderr: &str) -> (SkippedOps, Vec<String>) {
+    let mut skipped = HashMap::new();
+    let mut errors = Vec::new();
+    for line in stderr.lines() {
+        let Ok(v) = serde_json::from_str::<serde_json::Value>(line) else {
+            continue;
+        };
+        if let (Some(action), Some(object)) = (
+            v.get("skipped").and_then(|s| s.as_str()),
+            v.get("object").and_then(|s| s.as_str()),
+        ) {
+            let size = v
+                .get("size")
+                .and_then(|s| s.as_i64())
+                .and_then(|s| u64::try_from(s).ok());
+            skipped.insert(object.to_string(), (action.to_string(), size));
+        } else if v.get("level").and_then(|l| l.as_str()) == Some("error") {
+            if let Some(msg) = v.get("msg").and_then(|m| m.as_str()) {
+                match v.get("object").and_then(|o| o.as_str()) {
+                    Some(object) => errors.push(format!("{}: {}", object, msg)),
+                    None => errors.push(msg.to_string()),
+                }
+            }
+        }
+    }
+    (skipped, errors)
+}
+
+/// 合并 `--combined` 报告与 JSON 日志得到计划条目。
+///
+/// 报告中 `+` 为目标缺失、`*` 为两端不同、`=` 为相同、`-` 为仅目标存在；
+/// rclone 版本过旧未生成报告时，仅根据日志中的 dry-run 跳过记录区分复制与删除。
+pub(super) fn build_entries(
+    combined: &str,
+    skipped: &SkippedOps,
+    deletes_extra: bool,
+) -> Vec<PlanEntry> {
+    let size_of = |path: &str| skipped.get(path).and_then(|(_, size)| *size);
+    let mut entries = Vec::new();
+    for line in combined.lines() {
+        let Some((mark, path)) = line.split_once(' ') else {
+            continue;
+        };
+        let action = match mark {
+            "+" => PlanAction::Copy,
+            "*" => PlanAction::Update,
+            "=" => PlanAction::Skip,
+            "-" if deletes_extra => PlanAction::Delete,
+            "-" => PlanAction::Skip,
+            _ => continue,
+        };
+        entries.push(PlanEntry {
+            path: path.to_string(),
+            action,
+            size: size_of(path),
+        });
+    }
+
+    if entries.is_empty() {
+        for (path, (op, size)) in skipped {
+            let action = match op.as_str() {
+                "copy" | "move" | "server-side copy" | "server-side move" => PlanAction::Copy,
+                "delete" => PlanAction::Delete,
+                _ => continue,
+            };
+            entries.push(PlanEntry {
+                path: path.clone(),
+                action,
+                size: *size,
+            });
+        }
+    }
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+    entries
+}
+
+pub(super) fn totals(entries: &[PlanEntry]) -> PlanTotals {
+    let mut totals = PlanTotals::default();
+    for entry in entries {
+        let total = match entry.action {
+            PlanAction::Copy => &mut totals.copy,
+            PlanAction::Update => &mut totals.update,
+            PlanAction::Delete => &mut totals.delete,
+            PlanAction::Skip => &mut totals.skip,
+        };
+        total.count += 1;
+        total.bytes += entry.size.unwrap_or(0);
+    }
+    totals
+}
+
+/// 以 `--dry-run` 运行任务并生成计划
+pub async fn dry_run(task: &TaskSpec) -> anyhow::Result<TaskPlan> {
+    let id = new_run_id();
+    let dir = plan_dir();
+    fs::create_dir_all(&dir)?;
+    let combined_path = dir.join(format!("{}.combined", id));
+
+    let (mut args, fingerprint) = build_args(task).await?;
+    args.extend([
+        "--dry-run".to_string(),
+        "--use-json-log".to_string(),
+        "--log-level=NOTICE".to_string(),
+        "--stats=0".to_string(),
+        format!("--combined={}", combined_path.display()),
+    ]);
+    let mut cmd = rclone::command(&args)?;
+    cmd.stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    let output = tokio::process::Command::from(cmd)
+        .kill_on_drop(true)
+        .output()
+        .await?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let combined = fs::read_to_string(&combined_path).unwrap_or_default();
+    let _ = fs::remove_file(&combined_path);
+
+    let (skipped, mut errors) = parse_json_log(&stderr);
+    if !output.status.success() && errors.is_empty() {
+        errors.push(stderr.trim().to_string());
+    }
+    let entries = build_entries(&combined, &skipped, task.task_type == "sync");
+    Ok(TaskPlan {
+        id,
+        task_name: task.name.clone(),
+        task_type: task.task_type.clone(),
+        created_at: now_ms(),
+        totals: totals(&entries),
+        entries,
+        errors,
+        approved_at: None,
+        fingerprint,
+        consumed_at: None,
+    })
+}
+
+/// 保存计划，超出数量上限时删除最旧的计划
+pub fn save(plan: &TaskPlan) -> anyhow::Result<()> {
+    let path = plan_path(&plan.id)?;
+    fs::create_dir_all(plan_dir())?;
+    fs::write(path, serde_json::to_vec_pretty(plan)?)?;
+
+    let mut files: Vec<PathBuf> = fs::read_dir(plan_dir())?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    if files.len() > MAX_SAVED_PLANS {
+        // 计划 ID 以毫秒时间戳开头，按文件名排序即按时间排序
+        files.sort();
+        for old in &files[..files.len() - MAX_SAVED_PLANS] {
+            let _ = fs::remove_file(old);
+        }
+    }
+    Ok(())
+}
+
+pub fn load(id: &str) -> anyhow::Result<TaskPlan> {
+    let path = plan_path(id)?;
+    let content = fs::read(&path).map_err(|_| anyhow::anyhow!("Task plan not found: {}", id))?;
+    Ok(serde_json::from_slice(&content)?)
+}
+
+/// 列出已保存计划（不含文件清单），按生成时间倒序
+pub fn list(task_name: Option<&str>) -> anyhow::Result<Vec<TaskPlan>> {
+    let dir = plan_dir();
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut plans = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        let Ok(mut plan) = fs::read(&path)
+            .map_err(anyhow::Error::from)
+            .and_then(|c| Ok(serde_json::from_slice::<TaskPlan>(&c)?))
+        else {
+            continue;
+        };
+        if task_name.is_some_and(|n| n != plan.task_name) {
+            continue;
+        }
+        plan.entries.clear();
+        plans.push(plan);
+    }
+    plans.sort_by_key(|p| std::cmp::Reverse(p.created_at));
+    Ok(plans)
+}
+
+/// 确认计划；有错误、已使用或已过期的计划不能确认
+pub fn approve(id: &str) -> anyhow::Result<TaskPlan> {
+    let _guard = APPROVAL_LOCK.lock().unwrap();
+    let mut plan = load(id)?;
+    if plan.fingerprint.is_empty() {
+        return Err(anyhow::anyhow!("Task plan {} cannot be approved", id));
+    }
+    if !plan.errors.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Task plan {} reported errors: {}",
+            id,
+            plan.errors.join("; ")
+        ));
+    }
+    if plan.consumed_at.is_some() {
+        return Err(anyhow::anyhow!("Task plan {} has already been used", id));
+    }
+    if now_ms().saturating_sub(plan.created_at) > APPROVAL_VALID_MS {
+        return Err(anyhow::anyhow!(
+            "Task plan {} has expired, please run a dry-run again",
+            id
+        ));
+    }
+    plan.approved_at = Some(now_ms());
+    fs::write(plan_path(id)?, serde_json::to_vec_pretty(&plan)?)?;
+    Ok(plan)
+}
+
+/// 运行前使用一份与当前任务参数一致的已确认计划，找不到时拒绝运行
+pub async fn consume_approval(task: &TaskSpec) -> anyhow::Result<TaskPlan> {
+    let (_, fingerprint) = build_args(task).await?;
+    let _guard = APPROVAL_LOCK.lock().unwrap();
+    let now = now_ms();
+    let mut plan = list(Some(&task.name))?
+        .into_iter()
+        .find(|plan| plan.authorizes(&task.name, &fingerprint, now))
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "Task {} requires an approved plan: run a dry-run and approve it first",
+                task.name
+            )
+        })?;
+    // list 不含文件清单，重新读取完整计划后再标记
+    plan = load(&plan.id)?;
+    plan.consumed_at = Some(now);
+    fs::write(plan_path(&plan.id)?, serde_json::to_vec_pretty(&plan)?)?;
+    Ok(plan)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(path: &str, action: PlanAction, size: Option<u64>) -> PlanEntry {
+        PlanEntry {
+            path: path.to_string(),
+            action,
+            size,
+        }
+    }
+
+    fn plan(created_at: u64) -> TaskPlan {
+        TaskPlan {
+            id: "p".to_string(),
+            task_name: "backup".to_string(),
+            task_type: "sync".to_string(),
+            created_at,
+            entries: Vec::new(),
+            totals: PlanTotals::default(),
+            errors: Vec::new(),
+            approved_at: Some(created_at),
+            fingerprint: "abc".to_string(),
+            consumed_at: None,
+        }
+    }
+
+    #[test]
+    fn parse_json_log_collects_skipped_ops_and_errors() {
+        let stderr = concat!(
+            r#"{"level":"notice","msg":"Skipped copy as --dry-run is set (size 12)","object":"a.txt","skipped":"copy","size":12}"#,
+            "\n",
+            r#"{"level":"notice","msg":"Skipped delete","object":"old.txt","skipped":"delete","size":-1}"#,
+            "\n",
+            r#"{"level":"error","msg":"permission denied","object":"b.txt"}"#,
+            "\n",
+            r#"{"level":"error","msg":"directory not found"}"#,
+            "\n",
+            "not json\n",
+        );
+        let (skipped, errors) = parse_json_log(stderr);
+        assert_eq!(skipped["a.txt"], ("copy".to_string(), Some(12)));
+        assert_eq!(skipped["old.txt"], ("delete".to_string(), None));
+        assert_eq!(errors, ["b.txt: permission denied", "directory not found"]);
+    }
+
+    #[test]
+    fn build_entries_reads_combined_marks() {
+        let combined = "+ new.txt\n* changed.txt\n= same.txt\n- extra.txt\n! broken.txt\n";
+        let mut skipped = SkippedOps::new();
+        skipped.insert("new.txt".to_string(), ("copy".to_string(), Some(5)));
+        let entries = build_entries(combined, &skipped, true);
+        assert_eq!(
+            entries,
+            [
+                entry("changed.txt", PlanAction::Update, None),
+                entry("extra.txt", PlanAction::Delete, None),
+                entry("new.txt", PlanAction::Copy, Some(5)),
+                entry("same.txt", PlanAction::Skip, None),
+            ]
+        );
+        // copy/move 不删除目标多余的文件
+        let entries = build_entries(combined, &skipped, false);
+        assert_eq!(entries[1], entry("extra.txt", PlanAction::Skip, None));
+    }
+
+    #[test]
+    fn build_entries_falls_back_to_skipped_ops() {
+        let mut skipped = SkippedOps::new();
+        skipped.insert("b".to_string(), ("server-side copy".to_string(), Some(3)));
+        skipped.insert("a".to_string(), ("delete".to_string(), None));
+        skipped.insert("c".to_string(), ("set modification time".to_string(), None));
+        assert_eq!(
+            build_entries("", &skipped, true),
+            [
+                entry("a", PlanAction::Delete, None),
+                entry("b", PlanAction::Copy, Some(3)),
+            ]
+        );
+    }
+
+    #[test]
+    fn totals_sum_counts_and_bytes() {
+        let totals = totals(&[
+            entry("a", PlanAction::Copy, Some(10)),
+            entry("b", PlanAction::Copy, None),
+            entry("c", PlanAction::Delete, Some(4)),
+        ]);
+        assert_eq!((totals.copy.count, totals.copy.bytes), (2, 10));
+        assert_eq!((totals.delete.count, totals.delete.bytes), (1, 4));
+        assert_eq!(totals.update.count + totals.skip.count, 0);
+    }
+
+    #[test]
+    fn config_flags_match_rclone_options() {
+        let mut flags = config_flags(&serde_json::json!({
+            "BackupDir": "s3:bucket/.netmount-versions/20240101",
+            "Suffix": ".netmount-v20240101",
+            "SuffixKeepExtension": true,
+            "Disabled": false,
+            "Transfers": 4,
+        }));
+        flags.sort();
+        assert_eq!(
+            flags,
+            [
+                "--backup-dir=s3:bucket/.netmount-versions/20240101",
+                "--disabled=false",
+                "--suffix-keep-extension",
+                "--suffix=.netmount-v20240101",
+                "--transfers=4",
+            ]
+        );
+        assert!(config_flags(&serde_json::Value::Null).is_empty());
+    }
+
+    #[test]
+    fn fingerprint_depends_on_every_arg() {
+        let args = |v: &[&str]| v.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        let base = fingerprint(&args(&["sync", "a:", "b:"]));
+        assert_eq!(base, fingerprint(&args(&["sync", "a:", "b:"])));
+        assert_eq!(base.len(), 64);
+        assert_ne!(base, fingerprint(&args(&["sync", "a:b:", ""])));
+        assert_ne!(
+            base,
+            fingerprint(&args(&["sync", "a:", "b:", "--filter=- x"]))
+        );
+    }
+
+    #[test]
+    fn approval_fingerprint_covers_config_keys_and_changed_files() {
+        let args = vec!["sync".to_string(), "a:".to_string(), "b:".to_string()];
+        let config = serde_json::json!({ "BackupDir": "b:/.versions/20240101", "Suffix": "-1" });
+        let full = approval_fingerprint(&args, &config, None);
+        // 带运行时间的取值不影响摘要
+        let later = serde_json::json!({ "BackupDir": "b:/.versions/20240102", "Suffix": "-2" });
+        assert_eq!(full, approval_fingerprint(&args, &later, None));
+        assert_ne!(
+            full,
+            approval_fingerprint(&args, &serde_json::Value::Null, None)
+        );
+
+        // 全量计划的确认不能用于变更触发的运行，不同的变更列表之间也不能互用
+        let changed = approval_fingerprint(&args, &config, Some("docs/a.txt\n"));
+        assert_ne!(full, changed);
+        assert_ne!(
+            changed,
+            approval_fingerprint(&args, &config, Some("docs/b.txt\n"))
+        );
+        assert_eq!(
+            changed,
+            approval_fingerprint(&args, &config, Some("docs/a.txt\n"))
+        );
+    }
+
+    #[test]
+    fn authorizes_only_fresh_unused_matching_approvals() {
+        let now = 1_000;
+        assert!(plan(now).authorizes("backup", "abc", now));
+        assert!(!plan(now).authorizes("other", "abc", now));
+        assert!(!plan(now).authorizes("backup", "changed", now));
+        assert!(!plan(now).authorizes("backup", "abc", now + APPROVAL_VALID_MS + 1));
+
+        let mut unapproved = plan(now);
+        unapproved.approved_at = None;
+        assert!(!unapproved.authorizes("backup", "abc", now));
+
+        let mut consumed = plan(now);
+        consumed.consumed_at = Some(now);
+        assert!(!consumed.authorizes("backup", "abc", now));
+
+        let mut failed = plan(now);
+        failed.errors.push("boom".to_string());
+        assert!(!failed.authorizes("backup", "abc", now));
+    }
+
+    #[test]
+    fn approval_required_only_for_sync() {
+        let mut task = TaskSpec {
+            task_type: "sync".to_string(),
+            parameters: Some(serde_json::json!({ "requireApproval": true })),
+            ..Default::default()
+        };
+        assert!(approval_required(&task));
+        task.task_type = "copy".to_string();
+        assert!(!approval_required(&task));
+        task.task_type = "sync".to_string();
+        task.parameters = None;
+        assert!(!approval_required(&task));
+    }
+}
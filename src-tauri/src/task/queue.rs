@@ -0,0 +1,392 @@
+//! 任务运行队列：限制全局与单个存储的并发任务数。
+//!
+//! 超出限制的任务按优先级排队等待，前端可查看、调整顺序或取消；
+//! 同一任务已在排队或运行时，新的运行请求直接合并而不会重复启动。
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::Notify;
+
+use super::{new_run_id, now_ms, TaskSpec};
+
+/// 默认最多同时运行的任务数
+const DEFAULT_MAX_CONCURRENT: usize = 3;
+
+/// 并发限制，0 表示不限制
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConcurrencyLimits {
+    pub max_concurrent: usize,
+    /// 未在 `per_storage` 中单独配置的存储使用的限制
+    pub per_storage_default: usize,
+    pub per_storage: HashMap<String, usize>,
+}
+
+impl ConcurrencyLimits {
+    /// 从 `settings.taskConcurrency` 读取并发限制，缺省项使用默认值
+    pub fn from_settings(settings: &serde_json::Value) -> Self {
+        let section = &settings["taskConcurrency"];
+        Self {
+            max_concurrent: section["maxConcurrent"]
+                .as_u64()
+                .map(|v| v as usize)
+                .unwrap_or(DEFAULT_MAX_CONCURRENT),
+            per_storage_default: section["perStorageDefault"]
+                .as_u64()
+                .map(|v| v as usize)
+                .unwrap_or(0),
+            per_storage: section["perStorage"]
+                .as_object()
+                .map(|obj| {
+                    obj.iter()
+                        .filter_map(|(k, v)| v.as_u64().map(|v| (k.clone(), v as usize)))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    fn storage_limit(&self, storage: &str) -> usize {
+        self.per_storage
+            .get(storage)
+            .copied()
+            .unwrap_or(self.per_storage_default)
+    }
+}
+
+/// 队列中的一项（排队中或运行中）
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueEntry {
+    pub id: String,
+    pub task_name: String,
+    /// 任务涉及的存储，用于单存储并发限制
+    pub storages: Vec<String>,
+    pub priority: i32,
+    pub enqueued_at: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<u64>,
+}
+
+/// 队列快照
+#[derive(Clone, Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueSnapshot {
+    pub running: Vec<QueueEntry>,
+    /// 按启动顺序排列
+    pub waiting: Vec<QueueEntry>,
+}
+
+#[derive(Default)]
+struct QueueState {
+    running: Vec<QueueEntry>,
+    waiting: Vec<QueueEntry>,
+    /// 被取消、尚未被等待方取走的排队项
+    cancelled: Vec<String>,
+}
+
+impl QueueState {
+    fn contains_task(&self, name: &str) -> bool {
+        self.running
+            .iter()
+            .chain(self.waiting.iter())
+            .any(|e| e.task_name == name)
+    }
+
+    /// 插在第一个优先级更低的排队项之前，同优先级先到先运行
+    fn enqueue(&mut self, entry: QueueEntry) {
+        let index = self
+            .waiting
+            .iter()
+            .position(|e| e.priority < entry.priority)
+            .unwrap_or(self.waiting.len());
+        self.waiting.insert(index, entry);
+    }
+
+    /// 按队列顺序模拟分配，返回此刻可以启动的排队项 ID
+    fn startable(&self, limits: &ConcurrencyLimits) -> Vec<String> {
+        let mut total = self.running.len();
+        let mut per_storage: HashMap<&str, usize> = HashMap::new();
+        for entry in &self.running {
+            for s in &entry.storages {
+                *per_storage.entry(s.as_str()).or_default() += 1;
+            }
+        }
+
+        let mut out = Vec::new();
+        for entry in &self.waiting {
+            if limits.max_concurrent > 0 && total >= limits.max_concurrent {
+                break;
+            }
+            let fits = entry.storages.iter().all(|s| {
+                let limit = limits.storage_limit(s);
+                limit == 0 || per_storage.get(s.as_str()).copied().unwrap_or(0) < limit
+            });
+            if !fits {
+                continue;
+            }
+            total += 1;
+            for s in &entry.storages {
+                *per_storage.entry(s.as_str()).or_default() += 1;
+            }
+            out.push(entry.id.clone());
+        }
+        out
+    }
+}
+
+lazy_static::lazy_static! {
+    static ref QUEUE: Mutex<QueueState> = Mutex::new(QueueState::default());
+    static ref QUEUE_CHANGED: Notify = Notify::new();
+}
+
+/// 运行许可，释放时让出并发名额并唤醒排队中的任务
+pub struct Permit {
+    id: String,
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        QUEUE.lock().unwrap().running.retain(|e| e.id != self.id);
+        QUEUE_CHANGED.notify_waiters();
+    }
+}
+
+/// 排队结果
+pub enum Admission {
+    Run(Permit),
+    /// 同一任务已在排队或运行
+    Coalesced,
+    /// 排队期间被取消
+    Cancelled,
+}
+
+fn task_storages(task: &TaskSpec) -> Vec<String> {
+    let mut storages = vec![task.source.storage_name.clone()];
+    if task.task_type != "delete" && task.target.storage_name != task.source.storage_name {
+        storages.push(task.target.storage_name.clone());
+    }
+    storages.retain(|s| !s.is_empty());
+    storages
+}
+
+/// 申请运行名额，超出限制时排队等待
+pub async fn admit(task: &TaskSpec, limits: &ConcurrencyLimits) -> Admission {
+    let id = new_run_id();
+    {
+        let mut state = QUEUE.lock().unwrap();
+        if state.contains_task(&task.name) {
+            return Admission::Coalesced;
+        }
+        let entry = QueueEntry {
+            id: id.clone(),
+            task_name: task.name.clone(),
+            storages: task_storages(task),
+            priority: task.priority,
+            enqueued_at: now_ms(),
+            started_at: None,
+        };
+        state.enqueue(entry);
+    }
+
+    loop {
+        // 先注册通知再检查，避免检查与等待之间错过唤醒
+        let notified = QUEUE_CHANGED.notified();
+        {
+            let mut state = QUEUE.lock().unwrap();
+            if let Some(pos) = state.cancelled.iter().position(|c| *c == id) {
+                state.cancelled.remove(pos);
+                return Admission::Cancelled;
+            }
+            if state.startable(limits).contains(&id) {
+                if let Some(pos) = state.waiting.iter().position(|e| e.id == id) {
+                    let mut entry = state.waiting.remove(pos);
+                    entry.started_at = Some(now_ms());
+                    state.running.push(entry);
+                    return Admission::Run(Permit { id });
+                }
+            }
+        }
+        notified.await;
+    }
+}
+
+/// 等待任务既不在运行也不在排队
+pub async fn wait_idle(task_name: &str) {
+    loop {
+        let notified = QUEUE_CHANGED.notified();
+        if !QUEUE.lock().unwrap().contains_task(task_name) {
+            return;
+        }
+        notified.await;
+    }
+}
+
+pub fn snapshot() -> QueueSnapshot {
+    let state = QUEUE.lock().unwrap();
+    QueueSnapshot {
+        running: state.running.clone(),
+        waiting: state.waiting.clone(),
+    }
+}
+
+/// 把排队项移动到 `index` 处（超出范围时移到队尾）
+pub fn move_entry(id: &str, index: usize) -> anyhow::Result<()> {
+    let mut state = QUEUE.lock().unwrap();
+    let pos = state
+        .waiting
+        .iter()
+        .position(|e| e.id == id)
+        .ok_or_else(|| anyhow::anyhow!("Queued task not found: {}", id))?;
+    let entry = state.waiting.remove(pos);
+    let index = index.min(state.waiting.len());
+    state.waiting.insert(index, entry);
+    drop(state);
+    QUEUE_CHANGED.notify_waiters();
+    Ok(())
+}
+
+/// 取消排队项，运行中的任务不受影响
+pub fn cancel_entry(id: &str) -> anyhow::Result<()> {
+    let mut state = QUEUE.lock().unwrap();
+    let pos = state
+        .waiting
+        .iter()
+        .position(|e| e.id == id)
+        .ok_or_else(|| anyhow::anyhow!("Queued task not found: {}", id))?;
+    state.waiting.remove(pos);
+    state.cancelled.push(id.to_string());
+    drop(state);
+    QUEUE_CHANGED.notify_waiters();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::TaskEndpoint;
+
+    fn entry(id: &str, storages: &[&str], priority: i32) -> QueueEntry {
+        QueueEntry {
+            id: id.to_string(),
+            task_name: id.to_string(),
+            storages: storages.iter().map(|s| s.to_string()).collect(),
+            priority,
+            enqueued_at: 0,
+            started_at: None,
+        }
+    }
+
+    fn waiting_ids(state: &QueueState) -> Vec<&str> {
+        state.waiting.iter().map(|e| e.id.as_str()).collect()
+    }
+
+    fn limits(max_concurrent: usize, per_storage_default: usize) -> ConcurrencyLimits {
+        ConcurrencyLimits {
+            max_concurrent,
+            per_storage_default,
+            per_storage: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn enqueue_orders_by_priority_then_arrival() {
+        let mut state = QueueState::default();
+        state.enqueue(entry("a", &["s"], 0));
+        state.enqueue(entry("b", &["s"], 5));
+        state.enqueue(entry("c", &["s"], 0));
+        state.enqueue(entry("d", &["s"], 5));
+        state.enqueue(entry("e", &["s"], -1));
+        assert_eq!(waiting_ids(&state), ["b", "d", "a", "c", "e"]);
+    }
+
+    #[test]
+    fn startable_respects_global_limit() {
+        let mut state = QueueState::default();
+        state.running.push(entry("r", &["x"], 0));
+        for id in ["a", "b", "c"] {
+            state.enqueue(entry(id, &[id], 0));
+        }
+        assert_eq!(state.startable(&limits(3, 0)), ["a", "b"]);
+        assert_eq!(state.startable(&limits(0, 0)), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn startable_skips_entries_over_storage_limit() {
+        let mut state = QueueState::default();
+        state.running.push(entry("r", &["s3"], 0));
+        state.enqueue(entry("a", &["s3", "local"], 0));
+        state.enqueue(entry("b", &["local"], 0));
+        state.enqueue(entry("c", &["local"], 0));
+        // s3 已满，a 被跳过，后面的任务仍可启动；local 限 1 个
+        assert_eq!(state.startable(&limits(0, 1)), ["b"]);
+
+        let mut custom = limits(0, 1);
+        custom.per_storage.insert("s3".to_string(), 2);
+        custom.per_storage.insert("local".to_string(), 0);
+        assert_eq!(state.startable(&custom), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn contains_task_checks_running_and_waiting() {
+        let mut state = QueueState::default();
+        state.running.push(entry("r", &[], 0));
+        state.enqueue(entry("w", &[], 0));
+        assert!(state.contains_task("r"));
+        assert!(state.contains_task("w"));
+        assert!(!state.contains_task("x"));
+    }
+
+    #[tokio::test]
+    async fn wait_idle_returns_after_permit_released() {
+        let task = TaskSpec {
+            name: "netmount-queue-wait-idle".to_string(),
+            ..Default::default()
+        };
+        let Admission::Run(permit) = admit(&task, &limits(0, 0)).await else {
+            panic!("task should start immediately");
+        };
+        assert!(matches!(
+            admit(&task, &limits(0, 0)).await,
+            Admission::Coalesced
+        ));
+        let waiter = tokio::spawn(async move { wait_idle(&task.name).await });
+        tokio::task::yield_now().await;
+        assert!(!waiter.is_finished());
+        drop(permit);
+        waiter.await.unwrap();
+    }
+
+    #[test]
+    fn task_storages_deduplicate_and_ignore_delete_target() {
+        let mut task = TaskSpec {
+            task_type: "copy".to_string(),
+            source: TaskEndpoint {
+                storage_name: "a".to_string(),
+                path: String::new(),
+            },
+            target: TaskEndpoint {
+                storage_name: "a".to_string(),
+                path: String::new(),
+            },
+            ..Default::default()
+        };
+        assert_eq!(task_storages(&task), ["a"]);
+        task.target.storage_name = "b".to_string();
+        assert_eq!(task_storages(&task), ["a", "b"]);
+        task.task_type = "delete".to_string();
+        assert_eq!(task_storages(&task), ["a"]);
+    }
+
+    #[test]
+    fn limits_from_settings() {
+        let limits = ConcurrencyLimits::from_settings(&serde_json::json!({
+            "taskConcurrency": { "perStorageDefault": 2, "perStorage": { "s3": 1 } }
+        }));
+        assert_eq!(limits.max_concurrent, DEFAULT_MAX_CONCURRENT);
+        assert_eq!(limits.storage_limit("s3"), 1);
+        assert_eq!(limits.storage_limit("other"), 2);
+    }
+}
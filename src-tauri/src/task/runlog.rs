@@ -0,0 +1,268 @@
+//! 每次任务运行的独立日志：`log/task-runs/<runId>.log`。
+//!
+//! 经 rc 执行的任务与其它 job 共用 `rclone.log`，运行结束后从运行期间新增的日志中
+//! 挑出与本次运行的传输对象、源/目标相关的行；单独启动 rclone 进程的运行则直接把
+//! `--log-file` 指向运行日志。
+
+use std::{
+    fs,
+    io::{Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+/// 单次截取 rclone.log 的上限，避免运行期间日志暴涨时占用过多内存
+const MAX_SLICE_BYTES: u64 = 16 * 1024 * 1024;
+const DEFAULT_MAX_AGE_DAYS: u64 = 30;
+const DEFAULT_MAX_TOTAL_MB: u64 = 200;
+
+/// 运行日志保留策略
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogRetention {
+    pub max_age_days: u64,
+    pub max_total_mb: u64,
+}
+
+impl Default for LogRetention {
+    fn default() -> Self {
+        Self {
+            max_age_days: DEFAULT_MAX_AGE_DAYS,
+            max_total_mb: DEFAULT_MAX_TOTAL_MB,
+        }
+    }
+}
+
+impl LogRetention {
+    /// 从 `settings.taskRunLog` 读取保留策略，缺省项使用默认值
+    pub fn from_settings(settings: &serde_json::Value) -> Self {
+        let default = Self::default();
+        let section = &settings["taskRunLog"];
+        Self {
+            max_age_days: section["maxAgeDays"]
+                .as_u64()
+                .unwrap_or(default.max_age_days),
+            max_total_mb: section["maxTotalMb"]
+                .as_u64()
+                .unwrap_or(default.max_total_mb),
+        }
+    }
+}
+
+pub fn run_log_dir() -> PathBuf {
+    crate::resolve_data_dir().join("log").join("task-runs")
+}
+
+/// 运行 ID 只允许字母数字与 `-`，防止通过文件名访问其它路径
+pub(super) fn check_run_id(run_id: &str) -> anyhow::Result<()> {
+    if run_id.is_empty()
+        || !run_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-')
+    {
+        return Err(anyhow::anyhow!("Invalid run id: {}", run_id));
+    }
+    Ok(())
+}
+
+pub fn run_log_path(run_id: &str) -> anyhow::Result<PathBuf> {
+    check_run_id(run_id)?;
+    Ok(run_log_dir().join(format!("{}.log", run_id)))
+}
+
+/// rclone.log 当前长度，作为本次运行截取的起点
+pub fn rclone_log_offset() -> Option<u64> {
+    let path = crate::rclone::endpoint()?.log_file?;
+    fs::metadata(path).ok().map(|m| m.len())
+}
+
+/// 向运行日志追加文本
+pub fn append(run_id: &str, text: &str) -> anyhow::Result<()> {
+    let path = run_log_path(run_id)?;
+    fs::create_dir_all(run_log_dir())?;
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    file.write_all(text.as_bytes())?;
+    if !text.ends_with('\n') {
+        file.write_all(b"\n")?;
+    }
+    Ok(())
+}
+
+fn read_from(path: &Path, start: u64) -> anyhow::Result<String> {
+    let mut file = fs::File::open(path)?;
+    let len = file.metadata()?.len();
+    // 日志被轮转或清空时从头读取
+    let start = if start > len { 0 } else { start };
+    let start = start.max(len.saturating_sub(MAX_SLICE_BYTES));
+    file.seek(SeekFrom::Start(start))?;
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf)?;
+    Ok(String::from_utf8_lossy(&buf).to_string())
+}
+
+/// rclone.log 中 `start` 之后的内容（最多 MAX_SLICE_BYTES）
+pub fn rclone_log_since(start: u64) -> anyhow::Result<String> {
+    let log_file = crate::rclone::endpoint()
+        .and_then(|ep| ep.log_file)
+        .ok_or_else(|| anyhow::anyhow!("rclone log file is not configured"))?;
+    read_from(&log_file, start)
+}
+
+/// 从 rclone.log 的 `start` 处截取与本次运行相关的行追加到运行日志。
+///
+/// `names` 为本次运行传输过的对象，`roots` 为源/目标 fs；运行失败时额外保留
+/// 期间的 ERROR/NOTICE 行，以免遗漏未关联到具体对象的错误。
+pub fn capture_rclone_slice(
+    run_id: &str,
+    start: u64,
+    names: &[String],
+    roots: &[String],
+    include_errors: bool,
+) -> anyhow::Result<()> {
+    let Some(log_file) = crate::rclone::endpoint().and_then(|ep| ep.log_file) else {
+        return Ok(());
+    };
+    let content = read_from(&log_file, start)?;
+    let out = related_lines(&content, names, roots, include_errors);
+    if !out.is_empty() {
+        append(run_id, &out)?;
+    }
+    Ok(())
+}
+
+/// 挑出与传输对象或源/目标 fs 相关的行（`include_errors` 时额外保留 ERROR/NOTICE 行）
+fn related_lines(
+    content: &str,
+    names: &[String],
+    roots: &[String],
+    include_errors: bool,
+) -> String {
+    let mut out = String::new();
+    for line in content.lines() {
+        let related = names
+            .iter()
+            .any(|n| !n.is_empty() && line.contains(n.as_str()))
+            || roots
+                .iter()
+                .any(|r| !r.is_empty() && line.contains(r.as_str()))
+            || (include_errors && (line.contains("ERROR") || line.contains("NOTICE")));
+        if related {
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// 读取运行日志：`search` 非空时返回包含该关键字的行（不区分大小写），否则返回末尾 `max_bytes`
+pub fn read(run_id: &str, max_bytes: Option<u64>, search: Option<&str>) -> anyhow::Result<String> {
+    let path = run_log_path(run_id)?;
+    let len = fs::metadata(&path)?.len();
+    let max_bytes = max_bytes.unwrap_or(256 * 1024).max(1024);
+    let content = match search.map(|s| s.trim()).filter(|s| !s.is_empty()) {
+        Some(keyword) => {
+            let keyword = keyword.to_lowercase();
+            read_from(&path, 0)?
+                .lines()
+                .filter(|line| line.to_lowercase().contains(&keyword))
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+        None => read_from(&path, len.saturating_sub(max_bytes))?,
+    };
+    Ok(content)
+}
+
+/// 按保留策略清理运行日志：先删除过期文件，再从最旧的开始删除直到总大小不超限。返回删除的文件数
+pub fn cleanup(retention: &LogRetention) -> anyhow::Result<usize> {
+    let dir = run_log_dir();
+    if !dir.exists() {
+        return Ok(0);
+    }
+
+    let now = std::time::SystemTime::now();
+    let max_age = std::time::Duration::from_secs(retention.max_age_days * 24 * 60 * 60);
+    let mut files = Vec::new();
+    for entry in fs::read_dir(&dir)? {
+        let entry = entry?;
+        let meta = entry.metadata()?;
+        if !meta.is_file() {
+            continue;
+        }
+        let modified = meta.modified().unwrap_or(now);
+        files.push((entry.path(), modified, meta.len()));
+    }
+    files.sort_by_key(|(_, modified, _)| *modified);
+
+    let mut removed = 0;
+    let mut total: u64 = files.iter().map(|(_, _, len)| len).sum();
+    let max_total = retention.max_total_mb * 1024 * 1024;
+    for (path, modified, len) in files {
+        let expired = retention.max_age_days > 0
+            && now.duration_since(modified).unwrap_or_default() > max_age;
+        let over_size = retention.max_total_mb > 0 && total > max_total;
+        if (expired || over_size) && fs::remove_file(&path).is_ok() {
+            total = total.saturating_sub(len);
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOG: &str = "\
+2024/01/01 10:00:00 INFO  : docs/a.txt: Copied (new)
+2024/01/01 10:00:01 INFO  : other/b.txt: Copied (new)
+2024/01/01 10:00:02 ERROR : Attempt 1/3 failed with 1 errors
+2024/01/01 10:00:03 NOTICE: .netmount-openlist.:/alist/photos: 2 differences found
+2024/01/01 10:00:04 DEBUG : unrelated line
+";
+
+    fn strings(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn keeps_lines_for_transferred_objects() {
+        let out = related_lines(LOG, &strings(&["docs/a.txt"]), &[], false);
+        assert_eq!(out.lines().count(), 1);
+        assert!(out.contains("docs/a.txt: Copied"));
+    }
+
+    #[test]
+    fn keeps_lines_for_source_and_target_fs() {
+        let roots = strings(&[".netmount-openlist.:/alist/photos"]);
+        let out = related_lines(LOG, &[], &roots, false);
+        assert_eq!(out.lines().count(), 1);
+        assert!(out.contains("differences found"));
+    }
+
+    #[test]
+    fn includes_errors_only_when_requested() {
+        assert!(related_lines(LOG, &[], &[], false).is_empty());
+        let out = related_lines(LOG, &[], &[], true);
+        assert_eq!(out.lines().count(), 2);
+        assert!(!out.contains("unrelated"));
+    }
+
+    #[test]
+    fn empty_names_and_roots_match_nothing() {
+        let out = related_lines(LOG, &strings(&[""]), &strings(&[""]), false);
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn run_ids_are_restricted() {
+        assert!(check_run_id("20240101-abc").is_ok());
+        assert!(check_run_id("").is_err());
+        assert!(check_run_id("../x").is_err());
+        assert!(check_run_id("a/b").is_err());
+    }
+}
@@ -0,0 +1,395 @@
+use std::path::Path;
+
+use serde_json::{json, Value};
+
+use super::history::{self, TaskHistory, TaskResult};
+use super::hooks::{self, HookContext};
+use super::{bisync, check, checkpoint, plan, versions};
+use super::{history_retention, log_retention, new_run_id, now_ms, runlog, TaskSpec};
+use crate::rclone::{self, file_name, join_remote, JobOutcome, StorageRef};
+use crate::Runtime;
+
+/// 把 `filterRules` 与变更列表附加为 rc 的 `_filter` 参数，`config` 附加为 `_config`
+fn with_options(
+    mut body: Value,
+    rules: &[String],
+    files_from: Option<&Path>,
+    config: Option<&Value>,
+) -> Value {
+    if !rules.is_empty() {
+        body["_filter"] = json!({ "FilterRule": rules });
+    }
+    if let Some(file) = files_from {
+        body["_filter"]["FilesFromRaw"] = json!([file.to_string_lossy()]);
+    }
+    if let Some(config) = config {
+        body["_config"] = config.clone();
+    }
+    body
+}
+
+/// 运行日志中用于关联本次运行的源/目标 fs（与传给 rc 的 fs 字符串一致）。
+/// OpenList 存储位于 OpenList remote 之下，rclone 日志中可能带前导 `/`，两种形式都匹配
+fn log_roots(task: &TaskSpec, remotes: &[String]) -> Vec<String> {
+    let mut roots = Vec::new();
+    for side in [&task.source, &task.target] {
+        if side.storage_name.is_empty() {
+            continue;
+        }
+        let storage = StorageRef::resolve(remotes, &side.storage_name);
+        roots.push(storage.fs(&side.path));
+        if !storage.prefix.is_empty() {
+            roots.push(format!("{}/{}", storage.root, storage.remote(&side.path)));
+        }
+    }
+    roots
+}
+
+/// 传输使用的过滤规则与 `_config` 覆盖项，运行与 dry-run 计划共用。
+/// 启用版本保留时通过 `_config` 设置 BackupDir / Suffix，并排除已有版本
+pub(super) async fn transfer_options(
+    task: &TaskSpec,
+) -> anyhow::Result<(Vec<String>, Option<Value>)> {
+    let mut rules = task.filter_rules();
+    let mut config = None;
+    if let Some(versioning) = versions::enabled_for(task) {
+        let (overrides, exclude) = versions::run_options(task, versioning).await?;
+        rules.splice(0..0, exclude);
+        config = Some(overrides);
+    }
+    Ok((rules, config))
+}
+
+/// 按任务类型调用对应的 rc 接口（语义与前端原 `runTask` 一致）
+async fn dispatch(task: &TaskSpec, group: &str, run_id: &str) -> anyhow::Result<JobOutcome> {
+    let remotes = rclone::list_remotes().await?;
+    let src = StorageRef::resolve(&remotes, &task.source.storage_name);
+    let dst = StorageRef::resolve(&remotes, &task.target.storage_name);
+    let src_path = &task.source.path;
+    let dst_path = &task.target.path;
+    let src_is_dir = src_path.ends_with('/');
+    let dst_is_dir = dst_path.ends_with('/');
+    let (rules, config) = transfer_options(task).await?;
+
+    match task.task_type.as_str() {
+        "copy" | "move" => {
+            let (dir_op, file_op) = if task.task_type == "copy" {
+                ("sync/copy", "operations/copyfile")
+            } else {
+                ("sync/move", "operations/movefile")
+            };
+            match (src_is_dir, dst_is_dir) {
+                (true, true) => {
+                    let body = json!({ "srcFs": src.fs(src_path), "dstFs": dst.fs(dst_path) });
+                    let body =
+                        with_options(body, &rules, task.files_from.as_deref(), config.as_ref());
+                    if checkpoint::enabled_for(task) {
+                        let fingerprint = format!(
+                            "{} {} -> {} [{}]",
+                            task.task_type,
+                            src.fs(src_path),
+                            dst.fs(dst_path),
+                            rules.join("; ")
+                        );
+                        let src_fs = src.fs(src_path);
+                        checkpoint::run(task, dir_op, body, &src_fs, fingerprint, group, run_id)
+                            .await
+                    } else {
+                        rclone::run_job(dir_op, body, group).await
+                    }
+                }
+                (false, _) => {
+                    let dst_remote = if dst_is_dir {
+                        join_remote(&dst.remote(dst_path), file_name(src_path))
+                    } else {
+                        dst.remote(dst_path)
+                    };
+                    let body = json!({
+                        "srcFs": src.root,
+                        "srcRemote": src.remote(src_path),
+                        "dstFs": dst.root,
+                        "dstRemote": dst_remote,
+                    });
+                    rclone::run_job(file_op, body, group).await
+                }
+                (true, false) => Err(anyhow::anyhow!(
+                    "The directory cannot be copied/moved to a file"
+                )),
+            }
+        }
+        "delete" => {
+            let op = if src_is_dir {
+                "operations/purge"
+            } else {
+                "operations/deletefile"
+            };
+            let body = json!({ "fs": src.root, "remote": src.remote(src_path) });
+            rclone::run_job(op, body, group).await
+        }
+        "sync" => {
+            let body = json!({ "srcFs": src.fs(src_path), "dstFs": dst.fs(dst_path) });
+            rclone::run_job(
+                "sync/sync",
+                with_options(body, &rules, task.files_from.as_deref(), config.as_ref()),
+                group,
+            )
+            .await
+        }
+        "bisync" => bisync::run(task, group).await,
+        other => Err(anyhow::anyhow!("Invalid task type: {}", other)),
+    }
+}
+
+/// 运行任务并写入运行历史；`chain_id` 为所属任务链
+pub async fn execute(
+    app: &tauri::AppHandle<Runtime>,
+    task: &TaskSpec,
+    chain_id: Option<&str>,
+) -> anyhow::Result<TaskHistory> {
+    let run_id = new_run_id();
+    let group = format!("task/{}", run_id);
+    let started_at = now_ms();
+    let log_offset = runlog::rclone_log_offset();
+    let _ = runlog::append(
+        &run_id,
+        &format!(
+            "=== task {} ({}) run {} ===\nsource: {}:{}\ntarget: {}:{}",
+            task.name,
+            task.task_type,
+            run_id,
+            task.source.storage_name,
+            task.source.path,
+            task.target.storage_name,
+            task.target.path
+        ),
+    );
+
+    if let Some(file) = &task.files_from {
+        let count = std::fs::read_to_string(file)
+            .map(|c| c.lines().filter(|l| !l.is_empty()).count())
+            .unwrap_or(0);
+        let _ = runlog::append(&run_id, &format!("triggered by {} changed files", count));
+    }
+
+    // 要求确认计划的 sync 先使用一份已确认的计划，没有时不运行前置钩子与任务本身
+    let mut aborted = None;
+    if plan::approval_required(task) {
+        match plan::consume_approval(task).await {
+            Ok(approved) => {
+                let _ = runlog::append(&run_id, &format!("approved plan: {}", approved.id));
+            }
+            Err(e) => aborted = Some(e.to_string()),
+        }
+    }
+
+    // 前置钩子失败且要求中止时跳过任务本身
+    let mut hook_errors = Vec::new();
+    if let (Some(hook), None) = (&task.pre_hook, &aborted) {
+        let ctx = HookContext {
+            task,
+            run_id: &run_id,
+            status: "running",
+            transferred_bytes: 0,
+            transferred_files: 0,
+            errors: 0,
+        };
+        match hooks::pre_outcome(hook, hooks::run("pre", hook, &ctx).await) {
+            Ok(error) => hook_errors.extend(error),
+            Err(msg) => aborted = Some(msg),
+        }
+    }
+
+    let outcome = match aborted {
+        Some(msg) => Err(anyhow::anyhow!(msg)),
+        // 校验任务单独启动 rclone check 以获取逐文件报告
+        None if task.task_type == "check" => check::run(task, &run_id).await,
+        None => dispatch(task, &group, &run_id).await,
+    };
+
+    let stats = rclone::group_stats(&group).await.unwrap_or(Value::Null);
+    let transferred = rclone::group_transferred(&group).await.unwrap_or_default();
+    let _ = rclone::rc_post("core/stats-delete", json!({ "group": group })).await;
+
+    let mut error_messages = hook_errors;
+    let (mut success, job_id) = match &outcome {
+        Ok(o) => {
+            if !o.error.is_empty() {
+                error_messages.push(o.error.clone());
+            }
+            (o.success, o.job_id)
+        }
+        Err(e) => {
+            error_messages.push(e.to_string());
+            (false, None)
+        }
+    };
+    if let Some(last) = stats.get("lastError").and_then(|v| v.as_str()) {
+        if !last.is_empty() && !error_messages.iter().any(|m| m == last) {
+            error_messages.push(last.to_string());
+        }
+    }
+
+    let transferred_files = stats.get("transfers").and_then(|v| v.as_u64()).unwrap_or(0);
+    let transferred_bytes = stats.get("bytes").and_then(|v| v.as_u64()).unwrap_or(0);
+    let errors = stats.get("errors").and_then(|v| v.as_u64()).unwrap_or(0);
+
+    if let Some(offset) = log_offset {
+        let remotes = rclone::list_remotes().await.unwrap_or_default();
+        let roots = log_roots(task, &remotes);
+        if let Err(e) =
+            runlog::capture_rclone_slice(&run_id, offset, &transferred, &roots, !success)
+        {
+            eprintln!("Failed to capture task run log: {}", e);
+        }
+    }
+    if let Some(hook) = &task.post_hook {
+        let ctx = HookContext {
+            task,
+            run_id: &run_id,
+            status: if success { "success" } else { "failed" },
+            transferred_bytes,
+            transferred_files,
+            errors,
+        };
+        if let Err(e) = hooks::run("post", hook, &ctx).await {
+            error_messages.push(format!("Post-hook failed: {}", e));
+            if hook.abort_on_failure {
+                success = false;
+            }
+        }
+    }
+    if success {
+        if let Some(versioning) = versions::enabled_for(task) {
+            match versions::prune(task, versioning).await {
+                Ok(0) => {}
+                Ok(n) => {
+                    let _ =
+                        runlog::append(&run_id, &format!("versioning: removed {} old versions", n));
+                }
+                Err(e) => {
+                    let _ = runlog::append(&run_id, &format!("versioning: cleanup failed: {}", e));
+                }
+            }
+        }
+    }
+    let completed_at = now_ms();
+
+    let _ = runlog::append(
+        &run_id,
+        &format!(
+            "=== {} in {}ms{} ===",
+            if success { "success" } else { "failed" },
+            completed_at.saturating_sub(started_at),
+            if error_messages.is_empty() {
+                String::new()
+            } else {
+                format!(": {}", error_messages.join("; "))
+            }
+        ),
+    );
+    let retention = log_retention(app);
+    if let Err(e) = runlog::cleanup(&retention) {
+        eprintln!("Failed to clean task run logs: {}", e);
+    }
+    if let Err(e) = check::cleanup(retention.max_age_days) {
+        eprintln!("Failed to clean check reports: {}", e);
+    }
+
+    let record = TaskHistory {
+        log_file: Some(format!("{}.log", run_id)),
+        id: run_id,
+        task_id: task.name.clone(),
+        task_name: task.name.clone(),
+        task_type: task.task_type.clone(),
+        status: if success { "success" } else { "failed" }.to_string(),
+        started_at,
+        completed_at,
+        job_id,
+        chain_id: chain_id.map(|id| id.to_string()),
+        result: Some(TaskResult {
+            success,
+            transferred_files,
+            transferred_bytes,
+            errors: errors.max(if success { 0 } else { 1 }),
+            duration: completed_at.saturating_sub(started_at),
+            error_messages,
+        }),
+    };
+
+    if let Err(e) = history::append(&record, &history_retention(app)) {
+        eprintln!("Failed to write task history: {}", e);
+    }
+    Ok(record)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::TaskEndpoint;
+
+    fn task(source: &str, target: &str) -> TaskSpec {
+        TaskSpec {
+            name: "t".to_string(),
+            task_type: "sync".to_string(),
+            source: TaskEndpoint {
+                storage_name: source.to_string(),
+                path: "/docs/".to_string(),
+            },
+            target: TaskEndpoint {
+                storage_name: target.to_string(),
+                path: "/backup/".to_string(),
+            },
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn transfer_options_prepend_versioning_exclude() {
+        let mut task = task("local", "s3");
+        task.parameters = Some(json!({ "filterRules": ["- *.tmp"] }));
+        let (rules, config) = transfer_options(&task).await.unwrap();
+        assert_eq!(rules, ["- *.tmp"]);
+        assert!(config.is_none());
+
+        task.versioning = Some(versions::Versioning {
+            enabled: true,
+            mode: versions::VersionMode::Suffix,
+            ..Default::default()
+        });
+        let (rules, config) = transfer_options(&task).await.unwrap();
+        assert_eq!(rules.len(), 2);
+        assert!(rules[0].starts_with("- *"));
+        assert_eq!(rules[1], "- *.tmp");
+        let config = config.unwrap();
+        assert!(config["Suffix"].as_str().is_some());
+        assert_eq!(config["SuffixKeepExtension"], true);
+    }
+
+    #[test]
+    fn log_roots_use_rclone_remote_fs() {
+        let remotes = vec!["local".to_string(), "s3".to_string()];
+        assert_eq!(
+            log_roots(&task("local", "s3"), &remotes),
+            ["local:docs", "s3:backup"]
+        );
+    }
+
+    #[test]
+    fn log_roots_place_openlist_storages_under_openlist_remote() {
+        let remotes = vec!["local".to_string()];
+        assert_eq!(
+            log_roots(&task("local", "alist"), &remotes),
+            [
+                "local:docs",
+                ".netmount-openlist.:alist/backup",
+                ".netmount-openlist.:/alist/backup"
+            ]
+        );
+    }
+
+    #[test]
+    fn log_roots_skip_empty_storage() {
+        let remotes = vec!["local".to_string()];
+        assert_eq!(log_roots(&task("local", ""), &remotes), ["local:docs"]);
+    }
+}
@@ -0,0 +1,517 @@
+//! 任务版本保留：覆盖或删除目标文件前，把旧文件移入带时间戳的版本目录
+//! （`--backup-dir`）或加上时间戳后缀（`--suffix`），并按数量/时间清理旧版本。
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::{now_ms, TaskSpec};
+use crate::rclone::{self, file_name, join_remote, StorageRef};
+
+/// 目标目录下存放版本的子目录
+pub const VERSIONS_DIR: &str = ".netmount-versions";
+/// 后缀模式下插入文件名的标记，如 `a.nmv-20240101-120000.txt`
+const SUFFIX_MARK: &str = ".nmv-";
+
+/// `versioning` 配置
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Versioning {
+    #[serde(default)]
+    pub enabled: bool,
+    /// dir：移入 `.netmount-versions/<时间戳>/`；suffix：原地加时间戳后缀
+    #[serde(default)]
+    pub mode: VersionMode,
+    /// 每个文件（后缀模式）或版本目录（目录模式）最多保留的份数，0 表示不限制
+    #[serde(default)]
+    pub keep_count: usize,
+    /// 版本最长保留天数，0 表示不限制
+    #[serde(default)]
+    pub keep_days: u64,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum VersionMode {
+    #[default]
+    Dir,
+    Suffix,
+}
+
+/// 某个文件的一个历史版本
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileVersion {
+    /// 版本时间戳（`YYYYMMDD-HHMMSS`，UTC）
+    pub version: String,
+    /// 版本创建时间（Unix 毫秒）
+    pub created_at: u64,
+    /// 版本文件在目标存储中的路径
+    pub path: String,
+    pub size: i64,
+    pub mod_time: String,
+}
+
+/// 把 Unix 毫秒格式化为 `YYYYMMDD-HHMMSS`（UTC）
+fn format_stamp(ms: u64) -> String {
+    let secs = ms / 1000;
+    let days = (secs / 86400) as i64;
+    let rem = secs % 86400;
+    // 由天数推算公历日期（Howard Hinnant 的 civil_from_days）
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}{:02}{:02}-{:02}{:02}{:02}",
+        year,
+        month,
+        day,
+        rem / 3600,
+        rem % 3600 / 60,
+        rem % 60
+    )
+}
+
+/// 解析 `YYYYMMDD-HHMMSS` 为 Unix 毫秒
+fn parse_stamp(stamp: &str) -> Option<u64> {
+    let (date, time) = stamp.split_once('-')?;
+    if date.len() != 8
+        || time.len() != 6
+        || !date.chars().chain(time.chars()).all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+    let year: i64 = date[0..4].parse().ok()?;
+    let month: i64 = date[4..6].parse().ok()?;
+    let day: i64 = date[6..8].parse().ok()?;
+    let (h, m, s): (u64, u64, u64) = (
+        time[0..2].parse().ok()?,
+        time[2..4].parse().ok()?,
+        time[4..6].parse().ok()?,
+    );
+    // days_from_civil
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y.rem_euclid(400);
+    let mp = if month > 2 { month - 3 } else { month + 9 };
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146097 + doe - 719468;
+    let secs = u64::try_from(days).ok()? * 86400 + h * 3600 + m * 60 + s;
+    Some(secs * 1000)
+}
+
+/// 拆分文件名为（主名, 扩展名含点），与 rclone `--suffix-keep-extension` 一致
+fn split_ext(name: &str) -> (&str, &str) {
+    match name.rfind('.') {
+        Some(i) if i > 0 => (&name[..i], &name[i..]),
+        _ => (name, ""),
+    }
+}
+
+/// 解析后缀模式的版本文件路径，返回（原文件路径, 版本时间）
+fn parse_suffixed(path: &str) -> Option<(String, u64)> {
+    let pos = path.rfind(SUFFIX_MARK)?;
+    let rest = &path[pos + SUFFIX_MARK.len()..];
+    let (stamp, ext) = rest.split_at(rest.find('.').unwrap_or(rest.len()));
+    let created = parse_stamp(stamp)?;
+    Some((format!("{}{}", &path[..pos], ext), created))
+}
+
+/// 按保留策略选出要删除的版本：从新到旧排列，超出保留份数或早于保留天数的版本
+fn select_expired(
+    mut versions: Vec<(u64, String)>,
+    versioning: &Versioning,
+    now: u64,
+) -> Vec<String> {
+    let min_time = now.saturating_sub(versioning.keep_days * 24 * 60 * 60 * 1000);
+    versions.sort_by_key(|(t, _)| std::cmp::Reverse(*t));
+    versions
+        .into_iter()
+        .enumerate()
+        .filter(|(i, (created, _))| {
+            (versioning.keep_count > 0 && *i >= versioning.keep_count)
+                || (versioning.keep_days > 0 && *created < min_time)
+        })
+        .map(|(_, (_, name))| name)
+        .collect()
+}
+
+/// 版本相关的路径
+struct Layout {
+    target: StorageRef,
+    /// 目标目录在 remote 中的路径
+    target_dir: String,
+    /// 版本目录在 remote 中的路径
+    versions_dir: String,
+}
+
+async fn layout(task: &TaskSpec) -> anyhow::Result<Layout> {
+    let remotes = rclone::list_remotes().await?;
+    let target = StorageRef::resolve(&remotes, &task.target.storage_name);
+    let target_dir = target.remote(&task.target.path);
+    let versions_dir = join_remote(&target_dir, VERSIONS_DIR);
+    Ok(Layout {
+        target,
+        target_dir,
+        versions_dir,
+    })
+}
+
+/// 任务启用了版本保留且为目录到目录的 copy / move / sync 时返回配置
+pub fn enabled_for(task: &TaskSpec) -> Option<&Versioning> {
+    let versioning = task.versioning.as_ref().filter(|v| v.enabled)?;
+    let supported = matches!(task.task_type.as_str(), "copy" | "move" | "sync")
+        && task.source.path.ends_with('/')
+        && task.target.path.ends_with('/');
+    supported.then_some(versioning)
+}
+
+/// 本次运行使用的 `_config` 覆盖项与需要追加的过滤规则
+pub async fn run_options(
+    task: &TaskSpec,
+    versioning: &Versioning,
+) -> anyhow::Result<(Value, Vec<String>)> {
+    let stamp = format_stamp(now_ms());
+    match versioning.mode {
+        VersionMode::Dir => {
+            let layout = layout(task).await?;
+            let backup_dir = format!(
+                "{}{}",
+                layout.target.root,
+                join_remote(&layout.versions_dir, &stamp)
+            );
+            // 版本目录位于目标目录内，需排除以免被 sync 删除或与目标重叠
+            Ok((
+                json!({ "BackupDir": backup_dir }),
+                vec![format!("- /{}/**", VERSIONS_DIR)],
+            ))
+        }
+        VersionMode::Suffix => Ok((
+            json!({
+                "Suffix": format!("{}{}", SUFFIX_MARK, stamp),
+                "SuffixKeepExtension": true,
+            }),
+            vec![format!("- *{}*", SUFFIX_MARK)],
+        )),
+    }
+}
+
+/// `operations/list` 的结果项
+async fn list(fs: &str, remote: &str, opt: Value) -> anyhow::Result<Vec<Value>> {
+    let res = rclone::rc_post(
+        "operations/list",
+        json!({ "fs": fs, "remote": remote, "opt": opt }),
+    )
+    .await?;
+    Ok(res
+        .get("list")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default())
+}
+
+fn item_str<'a>(item: &'a Value, key: &str) -> &'a str {
+    item.get(key).and_then(|v| v.as_str()).unwrap_or_default()
+}
+
+/// 按保留策略删除旧版本，返回删除的版本数
+pub async fn prune(task: &TaskSpec, versioning: &Versioning) -> anyhow::Result<usize> {
+    if versioning.keep_count == 0 && versioning.keep_days == 0 {
+        return Ok(0);
+    }
+    let layout = layout(task).await?;
+    let now = now_ms();
+    let mut removed = 0;
+
+    match versioning.mode {
+        VersionMode::Dir => {
+            let dirs = list(
+                &layout.target.root,
+                &layout.versions_dir,
+                json!({ "dirsOnly": true }),
+            )
+            .await
+            .unwrap_or_default();
+            let stamps: Vec<(u64, String)> = dirs
+                .iter()
+                .filter_map(|d| {
+                    let name = item_str(d, "Name");
+                    parse_stamp(name).map(|t| (t, name.to_string()))
+                })
+                .collect();
+            for name in select_expired(stamps, versioning, now) {
+                rclone::rc_post(
+                    "operations/purge",
+                    json!({
+                        "fs": layout.target.root,
+                        "remote": join_remote(&layout.versions_dir, &name),
+                    }),
+                )
+                .await?;
+                removed += 1;
+            }
+        }
+        VersionMode::Suffix => {
+            let files = list(
+                &layout.target.root,
+                &layout.target_dir,
+                json!({ "recurse": true, "filesOnly": true }),
+            )
+            .await?;
+            // 按原文件分组：原路径 -> [(时间, 版本路径)]
+            let mut groups: std::collections::HashMap<String, Vec<(u64, String)>> =
+                std::collections::HashMap::new();
+            for file in &files {
+                let path = item_str(file, "Path");
+                if let Some((original, created)) = parse_suffixed(path) {
+                    groups
+                        .entry(original)
+                        .or_default()
+                        .push((created, path.to_string()));
+                }
+            }
+            for versions in groups.into_values() {
+                for path in select_expired(versions, versioning, now) {
+                    rclone::rc_post(
+                        "operations/deletefile",
+                        json!({
+                            "fs": layout.target.root,
+                            "remote": join_remote(&layout.target_dir, &path),
+                        }),
+                    )
+                    .await?;
+                    removed += 1;
+                }
+            }
+        }
+    }
+    Ok(removed)
+}
+
+/// 列出目标目录中 `path`（相对目标目录）的历史版本，按时间倒序
+pub async fn list_versions(task: &TaskSpec, path: &str) -> anyhow::Result<Vec<FileVersion>> {
+    let versioning = task
+        .versioning
+        .as_ref()
+        .ok_or_else(|| anyhow::anyhow!("Versioning is not configured for {}", task.name))?;
+    let layout = layout(task).await?;
+    let path = rclone::format_path(path);
+    if path.is_empty() {
+        return Err(anyhow::anyhow!("A file path is required"));
+    }
+    let mut versions = Vec::new();
+
+    match versioning.mode {
+        VersionMode::Dir => {
+            let dirs = list(
+                &layout.target.root,
+                &layout.versions_dir,
+                json!({ "dirsOnly": true }),
+            )
+            .await
+            .unwrap_or_default();
+            for dir in &dirs {
+                let stamp = item_str(dir, "Name");
+                let Some(created_at) = parse_stamp(stamp) else {
+                    continue;
+                };
+                let remote = join_remote(&join_remote(&layout.versions_dir, stamp), &path);
+                let res = rclone::rc_post(
+                    "operations/stat",
+                    json!({ "fs": layout.target.root, "remote": remote }),
+                )
+                .await?;
+                let Some(item) = res.get("item").filter(|i| !i.is_null()) else {
+                    continue;
+                };
+                versions.push(FileVersion {
+                    version: stamp.to_string(),
+                    created_at,
+                    path: remote,
+                    size: item.get("Size").and_then(|v| v.as_i64()).unwrap_or(0),
+                    mod_time: item_str(item, "ModTime").to_string(),
+                });
+            }
+        }
+        VersionMode::Suffix => {
+            let (parent, name) = match path.rfind('/') {
+                Some(i) => (&path[..i], &path[i + 1..]),
+                None => ("", path.as_str()),
+            };
+            let (stem, ext) = split_ext(name);
+            let prefix = format!("{}{}", stem, SUFFIX_MARK);
+            let dir = if parent.is_empty() {
+                layout.target_dir.clone()
+            } else {
+                join_remote(&layout.target_dir, parent)
+            };
+            for file in list(&layout.target.root, &dir, json!({ "filesOnly": true })).await? {
+                let file_name = item_str(&file, "Name");
+                let Some(stamp) = file_name
+                    .strip_prefix(&prefix)
+                    .and_then(|rest| rest.strip_suffix(ext))
+                else {
+                    continue;
+                };
+                let Some(created_at) = parse_stamp(stamp) else {
+                    continue;
+                };
+                versions.push(FileVersion {
+                    version: stamp.to_string(),
+                    created_at,
+                    path: join_remote(&dir, file_name),
+                    size: file.get("Size").and_then(|v| v.as_i64()).unwrap_or(0),
+                    mod_time: item_str(&file, "ModTime").to_string(),
+                });
+            }
+        }
+    }
+    versions.sort_by_key(|v| std::cmp::Reverse(v.created_at));
+    Ok(versions)
+}
+
+/// 把 `path` 恢复为指定版本；被替换的当前文件同样按版本规则保留
+pub async fn restore(task: &TaskSpec, path: &str, version: &str) -> anyhow::Result<()> {
+    let versions = list_versions(task, path).await?;
+    let found = versions
+        .iter()
+        .find(|v| v.version == version)
+        .ok_or_else(|| anyhow::anyhow!("Version {} of {} not found", version, path))?;
+    let layout = layout(task).await?;
+    let mut body = json!({
+        "srcFs": layout.target.root,
+        "srcRemote": found.path,
+        "dstFs": layout.target.root,
+        "dstRemote": join_remote(&layout.target_dir, &rclone::format_path(path)),
+    });
+    if let Some(versioning) = task.versioning.as_ref().filter(|v| v.enabled) {
+        body["_config"] = run_options(task, versioning).await?.0;
+    }
+    let outcome = rclone::run_job(
+        "operations/copyfile",
+        body,
+        &format!("restore/{}", file_name(path)),
+    )
+    .await?;
+    if !outcome.success {
+        return Err(anyhow::anyhow!("Restore failed: {}", outcome.error));
+    }
+    let _ = rclone::rc_post(
+        "core/stats-delete",
+        json!({ "group": format!("restore/{}", file_name(path)) }),
+    )
+    .await;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+    #[test]
+    fn stamps_round_trip() {
+        for (stamp, secs) in [
+            ("19700101-000000", 0u64),
+            ("19991231-235959", 946_684_799),
+            ("20000101-000000", 946_684_800),
+            // 2000 年为闰年（能被 400 整除）
+            ("20000229-000000", 951_782_400),
+            ("20231231-235959", 1_704_067_199),
+            ("20240229-235959", 1_709_251_199),
+            // 2100 年不是闰年，2 月 28 日后即 3 月 1 日
+            ("21000301-000000", 4_107_542_400),
+        ] {
+            assert_eq!(format_stamp(secs * 1000), stamp);
+            assert_eq!(parse_stamp(stamp), Some(secs * 1000), "{}", stamp);
+        }
+        // 毫秒部分被截断
+        assert_eq!(format_stamp(1_709_251_199_999), "20240229-235959");
+        assert_eq!(format_stamp(4_107_542_400_000 - 1000), "21000228-235959");
+    }
+
+    #[test]
+    fn parse_stamp_rejects_malformed_input() {
+        for stamp in [
+            "",
+            "20240101",
+            "20240101120000",
+            "2024011-120000",
+            "20240101-12000",
+            "2024a101-120000",
+            "20240101-12000x",
+            "+2024010-120000",
+            // 早于 1970 年
+            "19691231-235959",
+        ] {
+            assert_eq!(parse_stamp(stamp), None, "{}", stamp);
+        }
+    }
+
+    #[test]
+    fn split_extension_like_rclone() {
+        assert_eq!(split_ext("a.txt"), ("a", ".txt"));
+        assert_eq!(split_ext("archive.tar.gz"), ("archive.tar", ".gz"));
+        assert_eq!(split_ext("README"), ("README", ""));
+        assert_eq!(split_ext(".bashrc"), (".bashrc", ""));
+    }
+
+    #[test]
+    fn parse_suffixed_version_paths() {
+        assert_eq!(
+            parse_suffixed("docs/a.nmv-20240101-120000.txt"),
+            Some(("docs/a.txt".to_string(), 1_704_110_400_000))
+        );
+        assert_eq!(
+            parse_suffixed("a.tar.nmv-20240101-120000.gz"),
+            Some(("a.tar.gz".to_string(), 1_704_110_400_000))
+        );
+        assert_eq!(
+            parse_suffixed("Makefile.nmv-20240101-120000"),
+            Some(("Makefile".to_string(), 1_704_110_400_000))
+        );
+        assert_eq!(parse_suffixed("docs/a.txt"), None);
+        assert_eq!(parse_suffixed("a.nmv-latest.txt"), None);
+    }
+
+    #[test]
+    fn prune_keeps_newest_versions_within_limits() {
+        let now = 100 * DAY_MS;
+        let versions = || {
+            vec![
+                (now - 3 * DAY_MS, "d3".to_string()),
+                (now - DAY_MS / 2, "d0".to_string()),
+                (now - 10 * DAY_MS, "d10".to_string()),
+                (now - 2 * DAY_MS, "d2".to_string()),
+            ]
+        };
+        let policy = |keep_count, keep_days| Versioning {
+            enabled: true,
+            mode: VersionMode::Dir,
+            keep_count,
+            keep_days,
+        };
+        assert_eq!(
+            select_expired(versions(), &policy(2, 0), now),
+            ["d3", "d10"]
+        );
+        assert_eq!(
+            select_expired(versions(), &policy(0, 2), now),
+            ["d3", "d10"]
+        );
+        // 两个条件满足其一即删除
+        assert_eq!(
+            select_expired(versions(), &policy(3, 1), now),
+            ["d2", "d3", "d10"]
+        );
+        assert!(select_expired(versions(), &policy(0, 0), now).is_empty());
+        assert!(select_expired(versions(), &policy(10, 30), now).is_empty());
+    }
+}
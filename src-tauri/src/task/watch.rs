@@ -0,0 +1,393 @@
+//! 变更触发（`onChange` 运行模式）：监听本地源目录，合并一段时间内的变更后，
+//! 以 `--files-from` 只传输变更过的文件。
+//!
+//! inotify 监听数达到上限（`fs.inotify.max_user_watches`）时退回定时轮询。
+
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use lazy_static::lazy_static;
+use notify::{EventKind, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tauri::Emitter;
+use tokio::sync::mpsc;
+
+use super::{chain, concurrency_limits, find_task, load_tasks, now_ms, queue, TaskSpec};
+use crate::rclone::{self, format_path};
+use crate::Runtime;
+
+/// 默认合并时间（秒）
+const DEFAULT_DEBOUNCE_SECS: u64 = 5;
+/// 持续有变更时，距第一次变更最多等待的时间
+const MAX_DEBOUNCE_SECS: u64 = 60;
+/// 退回轮询时的扫描间隔
+const POLL_INTERVAL_SECS: u64 = 30;
+
+/// 监听状态
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchStatus {
+    pub task_name: String,
+    /// 监听的本地目录
+    pub path: String,
+    /// inotify（系统通知）/ poll（轮询）
+    pub backend: String,
+    /// 退回轮询的原因
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub degraded: Option<String>,
+    /// 等待触发的变更数
+    pub pending: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_triggered_at: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+struct ActiveWatch {
+    /// 丢弃监听器会关闭事件通道，合并循环随之退出
+    _watcher: Box<dyn Watcher + Send>,
+    status: WatchStatus,
+}
+
+lazy_static! {
+    static ref WATCHES: Mutex<HashMap<String, ActiveWatch>> = Mutex::new(HashMap::new());
+}
+
+/// 监听器发给合并循环的消息
+enum Change {
+    Paths(Vec<PathBuf>),
+    /// 事件丢失（队列溢出等），需要完整运行一次
+    Rescan,
+}
+
+fn update_status(app: &tauri::AppHandle<Runtime>, name: &str, f: impl FnOnce(&mut WatchStatus)) {
+    let status = {
+        let mut watches = WATCHES.lock().unwrap();
+        let Some(watch) = watches.get_mut(name) else {
+            return;
+        };
+        f(&mut watch.status);
+        watch.status.clone()
+    };
+    let _ = app.emit("task-watch", status);
+}
+
+/// 解析本地源目录：源存储必须是 rclone 的 local 存储
+async fn local_source(task: &TaskSpec) -> anyhow::Result<PathBuf> {
+    let config = rclone::rc_post("config/get", json!({ "name": task.source.storage_name })).await?;
+    if config.get("type").and_then(|t| t.as_str()) != Some("local") {
+        return Err(anyhow::anyhow!(
+            "onChange requires a local source storage, {} is not local",
+            task.source.storage_name
+        ));
+    }
+    let path = format_path(&task.source.path);
+    #[cfg(target_os = "windows")]
+    let path = PathBuf::from(path);
+    #[cfg(not(target_os = "windows"))]
+    let path = PathBuf::from(format!("/{}", path));
+    if !path.is_dir() {
+        return Err(anyhow::anyhow!(
+            "Source directory does not exist: {}",
+            path.display()
+        ));
+    }
+    Ok(path)
+}
+
+/// 创建监听器；inotify 监听数耗尽时退回轮询，返回（监听器, 后端, 退回原因）
+fn create_watcher(
+    root: &Path,
+    tx: mpsc::UnboundedSender<Change>,
+) -> anyhow::Result<(Box<dyn Watcher + Send>, &'static str, Option<String>)> {
+    let handler = move |res: notify::Result<notify::Event>| {
+        let change = match res {
+            Ok(event) if event.need_rescan() => Change::Rescan,
+            Ok(event) => match event.kind {
+                EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_) => {
+                    Change::Paths(event.paths)
+                }
+                _ => return,
+            },
+            Err(_) => Change::Rescan,
+        };
+        let _ = tx.send(change);
+    };
+
+    let mut native = notify::recommended_watcher(handler.clone())?;
+    let err = match native.watch(root, RecursiveMode::Recursive) {
+        Ok(()) => return Ok((Box::new(native), "inotify", None)),
+        Err(e) => e,
+    };
+    let limited = matches!(err.kind, notify::ErrorKind::MaxFilesWatch)
+        || matches!(&err.kind, notify::ErrorKind::Io(io) if io.raw_os_error() == Some(28));
+    if !limited {
+        return Err(err.into());
+    }
+    drop(native);
+
+    let config =
+        notify::Config::default().with_poll_interval(Duration::from_secs(POLL_INTERVAL_SECS));
+    let mut poll = notify::PollWatcher::new(handler, config)?;
+    poll.watch(root, RecursiveMode::Recursive)?;
+    Ok((
+        Box::new(poll),
+        "poll",
+        Some(format!(
+            "inotify watch limit reached, polling every {}s (raise fs.inotify.max_user_watches to restore instant triggers)",
+            POLL_INTERVAL_SECS
+        )),
+    ))
+}
+
+/// 开始监听 `onChange` 任务的源目录，已在监听时先停止旧的监听
+pub async fn start(
+    app: &tauri::AppHandle<Runtime>,
+    task: &TaskSpec,
+) -> anyhow::Result<WatchStatus> {
+    if task.run.mode != "onChange" {
+        return Err(anyhow::anyhow!(
+            "Task {} is not an onChange task",
+            task.name
+        ));
+    }
+    if !matches!(task.task_type.as_str(), "copy" | "move" | "sync")
+        || !task.source.path.ends_with('/')
+    {
+        return Err(anyhow::anyhow!(
+            "onChange only supports copy/move/sync tasks with a source directory"
+        ));
+    }
+    let root = local_source(task).await?;
+    stop(&task.name);
+
+    let (tx, rx) = mpsc::unbounded_channel();
+    let (watcher, backend, degraded) = create_watcher(&root, tx)?;
+    let status = WatchStatus {
+        task_name: task.name.clone(),
+        path: root.to_string_lossy().to_string(),
+        backend: backend.to_string(),
+        degraded,
+        ..Default::default()
+    };
+    WATCHES.lock().unwrap().insert(
+        task.name.clone(),
+        ActiveWatch {
+            _watcher: watcher,
+            status: status.clone(),
+        },
+    );
+    let _ = app.emit("task-watch", status.clone());
+
+    let debounce = Duration::from_secs(task.run.debounce.unwrap_or(DEFAULT_DEBOUNCE_SECS).max(1));
+    tauri::async_runtime::spawn(debounce_loop(
+        app.clone(),
+        task.name.clone(),
+        root,
+        debounce,
+        rx,
+    ));
+    Ok(status)
+}
+
+/// 停止监听
+pub fn stop(name: &str) -> bool {
+    WATCHES.lock().unwrap().remove(name).is_some()
+}
+
+pub fn list() -> Vec<WatchStatus> {
+    let mut list: Vec<WatchStatus> = WATCHES
+        .lock()
+        .unwrap()
+        .values()
+        .map(|w| w.status.clone())
+        .collect();
+    list.sort_by(|a, b| a.task_name.cmp(&b.task_name));
+    list
+}
+
+/// 合并变更：最后一次变更后安静 `debounce`，或距第一次变更超过上限时触发
+async fn debounce_loop(
+    app: tauri::AppHandle<Runtime>,
+    name: String,
+    root: PathBuf,
+    debounce: Duration,
+    mut rx: mpsc::UnboundedReceiver<Change>,
+) {
+    let max_wait = Duration::from_secs(MAX_DEBOUNCE_SECS).max(debounce);
+    let mut pending: BTreeSet<String> = BTreeSet::new();
+    let mut full = false;
+
+    loop {
+        // 等待第一次变更；上次因任务正在运行而未触发时，等待运行结束后直接进入合并等待
+        if pending.is_empty() && !full {
+            match rx.recv().await {
+                Some(change) => collect(&root, change, &mut pending, &mut full),
+                None => return,
+            }
+        }
+        let first = tokio::time::Instant::now();
+        loop {
+            let deadline = (tokio::time::Instant::now() + debounce).min(first + max_wait);
+            match tokio::time::timeout_at(deadline, rx.recv()).await {
+                Ok(Some(change)) => collect(&root, change, &mut pending, &mut full),
+                Ok(None) => return,
+                Err(_) => break,
+            }
+        }
+        if pending.is_empty() && !full {
+            continue;
+        }
+        let count = pending.len();
+        update_status(&app, &name, |s| s.pending = count);
+
+        match trigger(&app, &name, &pending, full).await {
+            // 任务正在运行时本次变更被合并：保留变更，等运行结束后再触发一次
+            Ok(false) => {
+                let idle = queue::wait_idle(&name);
+                tokio::pin!(idle);
+                loop {
+                    tokio::select! {
+                        _ = &mut idle => break,
+                        change = rx.recv() => match change {
+                            Some(change) => collect(&root, change, &mut pending, &mut full),
+                            None => return,
+                        },
+                    }
+                }
+            }
+            Ok(true) => {
+                pending.clear();
+                full = false;
+                update_status(&app, &name, |s| {
+                    s.pending = 0;
+                    s.last_triggered_at = Some(now_ms());
+                    s.last_error = None;
+                });
+            }
+            Err(e) => {
+                pending.clear();
+                full = false;
+                update_status(&app, &name, |s| {
+                    s.pending = 0;
+                    s.last_triggered_at = Some(now_ms());
+                    s.last_error = Some(e.to_string());
+                });
+            }
+        }
+    }
+}
+
+/// 把变更路径转换为相对源目录、以 `/` 分隔的路径，目录本身不计入
+fn collect(root: &Path, change: Change, pending: &mut BTreeSet<String>, full: &mut bool) {
+    let paths = match change {
+        Change::Rescan => {
+            *full = true;
+            return;
+        }
+        Change::Paths(paths) => paths,
+    };
+    for path in paths {
+        if path.is_dir() {
+            continue;
+        }
+        let Ok(rel) = path.strip_prefix(root) else {
+            continue;
+        };
+        let rel = format_path(&rel.to_string_lossy());
+        if !rel.is_empty() {
+            pending.insert(rel);
+        }
+    }
+}
+
+/// `--files-from` 列表：每行一个路径
+fn files_list(pending: &BTreeSet<String>) -> String {
+    let mut content = pending.iter().cloned().collect::<Vec<_>>().join("\n");
+    content.push('\n');
+    content
+}
+
+/// 触发一次运行，返回是否实际运行（false 表示已在运行而被合并）
+async fn trigger(
+    app: &tauri::AppHandle<Runtime>,
+    name: &str,
+    pending: &BTreeSet<String>,
+    full: bool,
+) -> anyhow::Result<bool> {
+    let mut task = find_task(app, name)?;
+    if !task.enable || task.run.mode != "onChange" {
+        stop(name);
+        return Ok(true);
+    }
+    let list_file = (!full)
+        .then(|| -> anyhow::Result<PathBuf> {
+            let dir = crate::resolve_data_dir().join("task").join("watch");
+            fs::create_dir_all(&dir)?;
+            let file = dir.join(format!("{}.files", super::new_run_id()));
+            fs::write(&file, files_list(pending))?;
+            Ok(file)
+        })
+        .transpose()?;
+    task.files_from = list_file.clone();
+
+    let result = chain::run_chain(app, &load_tasks(app), &task, &concurrency_limits(app)).await;
+    if let Some(file) = list_file {
+        let _ = fs::remove_file(file);
+    }
+    Ok(result?.status != "coalesced")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collect_keeps_files_relative_to_root() {
+        let root = std::env::temp_dir().join(format!("netmount-watch-{}", std::process::id()));
+        fs::create_dir_all(root.join("sub")).unwrap();
+        fs::write(root.join("a.txt"), "a").unwrap();
+
+        let mut pending = BTreeSet::new();
+        let mut full = false;
+        collect(
+            &root,
+            Change::Paths(vec![
+                root.join("a.txt"),
+                // 目录本身不计入
+                root.join("sub"),
+                root.clone(),
+                // 已删除的文件仍需传给 rclone
+                root.join("sub").join("gone.txt"),
+                std::env::temp_dir().join("outside.txt"),
+            ]),
+            &mut pending,
+            &mut full,
+        );
+        collect(
+            &root,
+            Change::Paths(vec![root.join("a.txt")]),
+            &mut pending,
+            &mut full,
+        );
+        assert_eq!(
+            pending.iter().map(String::as_str).collect::<Vec<_>>(),
+            ["a.txt", "sub/gone.txt"]
+        );
+        assert!(!full);
+
+        collect(&root, Change::Rescan, &mut pending, &mut full);
+        assert!(full);
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn files_list_one_path_per_line() {
+        let pending: BTreeSet<String> = ["b/c.txt", "a.txt"].map(String::from).into();
+        assert_eq!(files_list(&pending), "a.txt\nb/c.txt\n");
+    }
+}
@@ -5,12 +5,17 @@ import { stopComponentWatchdog } from './componentWatchdog'
 import { exit as tauriExit } from '@tauri-apps/plugin-process'
 import { logger } from '../services/LoggerService'
 import { cleanupTempOnExit } from '../utils/tempCleanup'
+import { invoke } from '@tauri-apps/api/core'
 
 export async function exit(isRestartSelf: boolean = false) {
   stopComponentWatchdog()
   
   try {
     await saveNmConfig()
+    // 等待写回缓存上传完成并卸载全部挂载，避免留下失效的挂载点
+    await invoke('unmount_all').catch(e => {
+      logger.error('Failed to unmount before exit', e instanceof Error ? e : new Error(String(e)), 'Lifecycle')
+    })
     await stopRclone()
     await stopOpenlist()
     await saveNmConfig()
@@ -15,6 +15,9 @@ import { t } from 'i18next'
 import { logger } from '../services/LoggerService'
 import { cleanupTempFiles, startPeriodicCleanup } from '../utils/tempCleanup'
 import { invoke } from '@tauri-apps/api/core'
+import { listenMountExitProgress, listenMountHealth } from './storage/mount/mount'
+import { promptRcloneConfigUnlock } from './setting/rcloneConfigPassword'
+import { promptConfigUnlock } from './setting/configPassphrase'
 
 type SetStartStrFn = (str: string) => void
 
@@ -72,6 +75,10 @@ export async function init(setStartStr: SetStartStrFn) {
   await getOsInfo()
 
   setStartStr(t('read_config'))
+  // 配置使用口令加密时先输入口令，否则读取到的敏感字段仍是密文
+  await promptConfigUnlock().catch(e => {
+    logger.warn('Failed to check config encryption', 'MainInit', { error: e })
+  })
   await readNmConfig()
 
   // 验证并修复可能失效的路径（如 Windows 用户名变更后）
@@ -104,6 +111,11 @@ export async function init(setStartStr: SetStartStrFn) {
 
   setStartStr(t('start_framework'))
 
+  // rclone 配置加密时先输入密码
+  await promptRcloneConfigUnlock().catch(e => {
+    logger.warn('Failed to check rclone config encryption', 'MainInit', { error: e })
+  })
+
   // 启动组件时使用独立的 try-catch，避免单个组件失败导致整个初始化失败
   const rcloneError = await startRclone().catch(e => {
     logger.error('Failed to start rclone', e instanceof Error ? e : new Error(String(e)))
@@ -117,6 +129,12 @@ export async function init(setStartStr: SetStartStrFn) {
   startUpdateCont()
 
   startComponentWatchdog()
+  listenMountHealth().catch(e => {
+    logger.warn('Failed to listen for mount health events', 'MainInit', { error: e })
+  })
+  listenMountExitProgress().catch(e => {
+    logger.warn('Failed to listen for mount exit progress', 'MainInit', { error: e })
+  })
   runStartupTasksInBackground()
 
   // 启动后清理过期临时文件（非阻塞）
@@ -0,0 +1,58 @@
+/**
+ * config.json 加密口令
+ *
+ * 使用口令加密时，读取配置前提示输入口令；取消时敏感字段保持密文，
+ * 相关功能（如 rclone/OpenList 的认证）可能无法使用。
+ */
+
+import { Input, Message, Modal } from '@arco-design/web-react'
+import { t } from 'i18next'
+import { getConfigEncryption, unlockConfig } from '../../utils/configEncryption'
+import { window as appWindow } from '../window'
+
+/**
+ * 配置已锁定时提示输入口令
+ * @returns 配置已解锁时为 true，用户取消时为 false
+ */
+async function promptConfigUnlock(): Promise<boolean> {
+  const status = await getConfigEncryption()
+  if (!status.locked) {
+    return true
+  }
+
+  // 隐藏启动时也需要显示窗口，否则无法输入口令
+  await appWindow.show()
+  await appWindow.setFocus()
+
+  return new Promise(resolve => {
+    let passphrase = ''
+    Modal.confirm({
+      title: t('config_locked_title'),
+      content: (
+        <div>
+          <p>{t(status.keySource === 'passphrase' ? 'config_locked' : 'config_key_missing')}</p>
+          {status.keySource === 'passphrase' && (
+            <Input.Password autoFocus onChange={value => (passphrase = value)} />
+          )}
+        </div>
+      ),
+      okText: t('config_unlock'),
+      okButtonProps: { disabled: status.keySource !== 'passphrase' },
+      maskClosable: false,
+      closable: false,
+      onOk: async () => {
+        try {
+          await unlockConfig(passphrase)
+          resolve(true)
+        } catch (e) {
+          Message.error(e instanceof Error ? e.message : String(e))
+          // 保持对话框打开，重新输入
+          throw e
+        }
+      },
+      onCancel: () => resolve(false),
+    })
+  })
+}
+
+export { promptConfigUnlock }
@@ -0,0 +1,87 @@
+/**
+ * rclone 配置密码
+ *
+ * 配置加密时，启动 rclone 前提示输入密码；修改密码需要先停止 rclone，
+ * 否则 rclone 会用旧的密钥写回配置。
+ */
+
+import { Input, Message, Modal } from '@arco-design/web-react'
+import { t } from 'i18next'
+import {
+  getRcloneConfigEncryption,
+  setRcloneConfigPassword,
+  unlockRcloneConfig,
+} from '../../utils/rclone/configPassword'
+import { startRclone, stopRclone } from '../../utils/rclone/process'
+import { addOpenlistInRclone } from '../../utils/openlist/openlist'
+import { openlistInfo } from '../../services/openlist'
+import { logger } from '../../services/LoggerService'
+import { window as appWindow } from '../window'
+
+/**
+ * 配置已加密且未解锁时提示输入密码
+ * @returns 可以启动 rclone 时为 true，用户取消时为 false
+ */
+async function promptRcloneConfigUnlock(): Promise<boolean> {
+  const status = await getRcloneConfigEncryption()
+  if (!status.encrypted || status.unlocked) {
+    return true
+  }
+
+  // 隐藏启动时也需要显示窗口，否则无法输入密码
+  await appWindow.show()
+  await appWindow.setFocus()
+
+  return new Promise(resolve => {
+    let password = ''
+    Modal.confirm({
+      title: t('rclone_config_locked_title'),
+      content: (
+        <div>
+          <p>{t('rclone_config_locked')}</p>
+          <Input.Password autoFocus onChange={value => (password = value)} />
+        </div>
+      ),
+      okText: t('rclone_config_unlock'),
+      maskClosable: false,
+      closable: false,
+      onOk: async () => {
+        try {
+          await unlockRcloneConfig(password)
+          resolve(true)
+        } catch (e) {
+          Message.error(e instanceof Error ? e.message : String(e))
+          // 保持对话框打开，重新输入
+          throw e
+        }
+      },
+      onCancel: () => resolve(false),
+    })
+  })
+}
+
+/**
+ * 设置、修改或移除配置密码：停止 rclone，改写配置后重新启动
+ * @param current - 当前密码
+ * @param password - 新密码，为空时移除加密
+ */
+async function changeRcloneConfigPassword(current: string, password: string): Promise<boolean> {
+  await stopRclone()
+  let success = false
+  try {
+    await setRcloneConfigPassword(current, password)
+    success = true
+  } catch (e) {
+    logger.error('Failed to change rclone config password', e as Error, 'RcloneConfig')
+    Message.error(e instanceof Error ? e.message : String(e))
+  }
+
+  // 修改失败时配置未变化，仍使用原来的密码启动
+  await startRclone()
+  if (openlistInfo.process.child) {
+    await addOpenlistInRclone()
+  }
+  return success
+}
+
+export { promptRcloneConfigUnlock, changeRcloneConfigPassword }
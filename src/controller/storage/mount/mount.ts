@@ -6,10 +6,18 @@
  */
 
 import { invoke } from '@tauri-apps/api/core'
+import { listen } from '@tauri-apps/api/event'
+import { webviewWindow } from '@tauri-apps/api'
+import { t } from 'i18next'
 import { Notification } from '@arco-design/web-react'
 import { mountRepository } from '../../../repositories/mount/MountRepository'
 import { MountListItem } from '../../../type/config'
 import { logger } from '../../../services/LoggerService'
+import type {
+  MountExitProgress,
+  MountHealth,
+  MountPointCheckOptions,
+} from '../../../type/mount/mount'
 
 import type {
   MountOptions,
@@ -139,6 +147,69 @@ async function unmountStorage(mountPath: string): Promise<boolean> {
   }
 }
 
+/**
+ * 退出前等待写回缓存上传时显示窗口并提示剩余文件数
+ */
+async function listenMountExitProgress() {
+  await listen<MountExitProgress>('mount-exit-progress', async ({ payload }) => {
+    if (payload.pendingUploads === 0) {
+      Notification.remove('mount_exit_uploading')
+      return
+    }
+    const appWindow = webviewWindow.getCurrentWebviewWindow()
+    await appWindow.show().catch(() => {})
+    Notification.info({
+      id: 'mount_exit_uploading',
+      title: t('mount_exit_uploading_title'),
+      content: t('mount_exit_uploading', {
+        count: payload.pendingUploads,
+        secs: payload.remainingSecs,
+      }),
+      duration: 0,
+    })
+  })
+}
+
+/**
+ * 挂载前检查挂载点，存在错误时提示并返回 false，仅有警告时提示后继续
+ */
+async function checkMountPoint(mountPath: string, opts?: MountPointCheckOptions): Promise<boolean> {
+  try {
+    const result = await mountRepository.validateMountPoint(mountPath, opts)
+    const errors = result.problems.filter(p => p.severity === 'error')
+    const warnings = result.problems.filter(p => p.severity === 'warning')
+    if (errors.length > 0) {
+      Notification.error({
+        title: t('mount_point_invalid'),
+        content: errors.map(p => p.message).join('; '),
+        duration: 10000,
+      })
+    } else if (warnings.length > 0) {
+      Notification.warning({
+        title: t('warning'),
+        content: warnings.map(p => p.message).join('; '),
+      })
+    }
+    return result.valid
+  } catch (error) {
+    // 检查本身失败时不阻止挂载，由 rclone 报告实际错误
+    mountLogger.warn(`Mount point check failed for ${mountPath}`, { error })
+    return true
+  }
+}
+
+/**
+ * 推荐挂载目录（Linux），失败时返回空字符串
+ */
+async function getSuggestedMountPath(storageName: string): Promise<string> {
+  try {
+    return (await mountRepository.suggestMountPath(storageName)).path
+  } catch (error) {
+    mountLogger.warn(`Failed to suggest mount path for ${storageName}`, { error })
+    return ''
+  }
+}
+
 /**
  * 获取可用驱动器字母（Windows）
  */
@@ -146,6 +217,33 @@ async function getAvailableDriveLetter(): Promise<string> {
   return await invoke('get_available_drive_letter')
 }
 
+/**
+ * 监听后端挂载健康监测的状态变化，重新挂载或恢复失败时刷新列表并提示
+ */
+async function listenMountHealth() {
+  await listen<MountHealth>('mount-health', ({ payload }) => {
+    if (payload.state === 'remounted') {
+      mountLogger.info(`Stale mount remounted: ${payload.mountPath}`)
+      Notification.info({
+        title: t('mount'),
+        content: t('mount_health_remounted', { path: payload.mountPath }),
+      })
+      void reupMount()
+    } else if (payload.state === 'failed') {
+      mountLogger.warn(`Mount unavailable: ${payload.mountPath}`, { error: payload.lastError })
+      Notification.error({
+        title: t('mount'),
+        content: t('mount_health_failed', {
+          path: payload.mountPath,
+          error: payload.lastError || '',
+        }),
+        duration: 10000,
+      })
+      void reupMount()
+    }
+  })
+}
+
 // ==========================================
 // 导出（向后兼容）
 // ==========================================
@@ -161,4 +259,8 @@ export {
   getMountStorage,
   isMounted,
   getAvailableDriveLetter,
+  getSuggestedMountPath,
+  listenMountHealth,
+  listenMountExitProgress,
+  checkMountPoint,
 }
\ No newline at end of file
@@ -1,62 +1,19 @@
+import { invoke } from '@tauri-apps/api/core'
 import type { TaskListItem } from '../../type/config'
-import { copyDir, copyFile, delDir, delFile, moveDir, moveFile, sync } from '../../services/storage'
+import type { ChainRun } from '../../type/task/task'
 import { logger } from '../../services/LoggerService'
 
 async function runTask(task: TaskListItem): Promise<TaskListItem> {
   const executeTask = async (t: TaskListItem) => {
-    const srcIsDir = t.source.path.endsWith('/')
-    const targetIsDir = t.target.path.endsWith('/')
-
-    switch (t.taskType) {
-      case 'copy': {
-        const copyFilterRules = t.parameters?.filterRules as string[] | undefined
-        if (srcIsDir && targetIsDir) {
-          await copyDir(t.source.storageName, t.source.path, t.target.storageName, t.target.path, copyFilterRules)
-        } else if (!srcIsDir && !targetIsDir) {
-          await copyFile(t.source.storageName, t.source.path, t.target.storageName, t.target.path, true)
-        } else if (!srcIsDir && targetIsDir) {
-          await copyFile(t.source.storageName, t.source.path, t.target.storageName, t.target.path)
-        } else {
-          throw new Error('The directory cannot be copied/moved to a file')
-        }
-        break
-      }
-      case 'move': {
-        const moveFilterRules = t.parameters?.filterRules as string[] | undefined
-        if (srcIsDir && targetIsDir) {
-          await moveDir(t.source.storageName, t.source.path, t.target.storageName, t.target.path, undefined, moveFilterRules)
-        } else if (!srcIsDir && targetIsDir) {
-          await moveFile(t.source.storageName, t.source.path, t.target.storageName, t.target.path)
-        } else if (!srcIsDir && !targetIsDir) {
-          await moveFile(t.source.storageName, t.source.path, t.target.storageName, t.target.path, undefined, true)
-        } else {
-          throw new Error('The directory cannot be copied/moved to a file')
-        }
-        break
-      }
-      case 'delete': {
-        if (srcIsDir) {
-          await delDir(t.source.storageName, t.source.path)
-        } else {
-          await delFile(t.source.storageName, t.source.path)
-        }
-        break
-      }
-      case 'sync': {
-        const syncFilterRules = t.parameters?.filterRules as string[] | undefined
-        await sync(t.source.storageName, t.source.path, t.target.storageName, t.target.path, false, false, syncFilterRules)
-        break
-      }
-      case 'bisync': {
-        // 使用resync参数处理首次同步或同步状态丢失的情况
-        const useResync = t.parameters?.resync === true
-        const bisyncFilterRules = t.parameters?.filterRules as string[] | undefined
-        await sync(t.source.storageName, t.source.path, t.target.storageName, t.target.path, true, useResync, bisyncFilterRules)
-        break
-      }
-      default: {
-        throw new Error('Invalid task type')
-      }
+    // 由后端通过 rclone rc 执行并写入运行历史，下游任务按依赖在其后运行
+    const chain = await invoke<ChainRun>('run_task', { name: t.name })
+    // 已在运行队列中（合并）或排队时被取消，本次不计为失败
+    const record = chain.runs[0]
+    if (!record) {
+      return
+    }
+    if (record.status !== 'success') {
+      throw new Error(record.result?.errorMessages?.join('\n') || `Task ${t.name} ${record.status}`)
     }
 
     if (t.run.mode === 'disposable') {
@@ -1,3 +1,4 @@
+import { invoke } from '@tauri-apps/api/core'
 import type { TaskListItem } from '../../type/config'
 import { runTask } from './runner'
 import { delTask } from './task'
@@ -54,6 +55,15 @@ class TaskScheduler {
       case 'interval':
         task.run.runId = window.setInterval(async () => await this.executeTask(task), task.run.interval)
         break
+      case 'after':
+        // 仅由前置任务在后端按依赖触发
+        break
+      case 'onChange':
+        // 由后端监听本地源目录，变更合并后触发
+        invoke('start_task_watch', { name: task.name }).catch(error =>
+          logger.error(`Failed to watch task ${task.name}`, error as Error, 'TaskScheduler')
+        )
+        break
       default:
         logger.error(`Invalid task mode: ${task.run.mode}`, undefined, 'TaskScheduler')
     }
@@ -82,6 +92,9 @@ class TaskScheduler {
 
   cancelTask(taskName: string) {
     const task = this.tasks.find(t => t.name === taskName)
+    if (task?.run.mode === 'onChange') {
+      void invoke('stop_task_watch', { name: taskName }).catch(() => undefined)
+    }
     if (task && task.run.runId !== undefined) {
       window.clearInterval(task.run.runId)
       window.clearTimeout(task.run.runId)
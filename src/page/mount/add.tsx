@@ -28,9 +28,11 @@ import {
 import { rcloneInfo } from '../../services/rclone'
 import {
   addMountStorage,
+  checkMountPoint,
   editMountStorage,
   getAvailableDriveLetter,
   getMountStorage,
+  getSuggestedMountPath,
   mountStorage,
 } from '../../controller/storage/mount/mount'
 import { osInfo } from '../../services/ConfigService'
@@ -172,6 +174,11 @@ export default function AddMount_page() {
           if (isMacOS) {
             // macOS: 使用 ~/Mounts/ 而非 ~/Desktop/，避免桌面权限问题
             setMountPath('~/Mounts/' + storageName)
+          } else if (rcloneInfo.endpoint.isLocal) {
+            // Linux: 由后端推荐未被占用的目录（默认 ~/NetMount/<存储名>）
+            getSuggestedMountPath(storageName).then(path => {
+              setMountPath(path || '/mnt/' + storageName)
+            })
           } else {
             setMountPath('/mnt/' + storageName)
           }
@@ -568,6 +575,20 @@ export default function AddMount_page() {
 
               mountPathTemp = formatPath(mountPathTemp, isWindows)
 
+              // 本地挂载时检查挂载点，编辑且路径未改变时跳过（挂载中的路径本身就是挂载点）
+              if (
+                rcloneInfo.endpoint.isLocal &&
+                (!isEditMode || formatPath(originalMountPath, isWindows) !== mountPathTemp)
+              ) {
+                const ok = await checkMountPoint(mountPathTemp, {
+                  allowNonEmpty: Boolean(parameters.mountOpt.AllowNonEmpty),
+                  ignoreMountPath: isEditMode ? originalMountPath : undefined,
+                })
+                if (!ok) {
+                  return
+                }
+              }
+
               if (isEditMode) {
                 await editMountStorage(
                   {
@@ -22,9 +22,10 @@ import {
 } from '../../controller/storage/mount/mount'
 import { useTranslation } from 'react-i18next'
 import { useNavigate } from 'react-router-dom'
-import { osInfo, roConfig } from '../../services/ConfigService'
+import { nmConfig, osInfo, roConfig } from '../../services/ConfigService'
 import { NoData_module } from '../other/noData'
 import {
+  getFuseReadiness,
   getWinFspInstallState,
   installWinFsp,
   openUrlInBrowser,
@@ -33,6 +34,7 @@ import {
 } from '../../utils'
 import { IconEye, IconQuestionCircle } from '@arco-design/web-react/icon'
 import { exit } from '../../controller/main'
+import type { FuseReadiness } from '../../type/mount/mount'
 const Row = Grid.Row
 const Col = Grid.Col
 
@@ -43,6 +45,7 @@ function Mount_page() {
   const refreshMounts = useMountStore((state) => state.refreshMounts)
   const [winFspInstallState, setWinFspInstallState] = useState<boolean>()
   const [winFspInstalling, setWinFspInstalling] = useState<boolean>()
+  const [fuseReadiness, setFuseReadiness] = useState<FuseReadiness>()
 
   const columns: TableColumnProps[] = [
     {
@@ -79,6 +82,14 @@ function Mount_page() {
     setWinFspInstallState(state)
   }
 
+  const getFuseState = async () => {
+    const allowOther = nmConfig.mount.lists.some(item => item.parameters?.mountOpt?.AllowOther)
+    const readiness = await getFuseReadiness(allowOther)
+    logger.debug('FUSE readiness', 'Mount', { readiness })
+
+    setFuseReadiness(readiness)
+  }
+
   useEffect(() => {
     // Initial load of mounts
     refreshMounts()
@@ -90,6 +101,10 @@ function Mount_page() {
     ) {
       getWinFspState()
     }
+
+    if (osInfo.osType === 'linux' && rcloneInfo.endpoint.isLocal) {
+      getFuseState().catch(e => logger.warn('Failed to check FUSE readiness', 'Mount', { error: e }))
+    }
   }, [])
 
   return (
@@ -157,6 +172,28 @@ function Mount_page() {
             <br />
           </>
         )}
+        {fuseReadiness && !fuseReadiness.ready && (
+          <>
+            <Alert
+              type="warning"
+              title={t('fuse_not_ready')}
+              content={fuseReadiness.checks
+                .filter(check => check.status === 'error')
+                .map(check => (
+                  <div key={check.id}>
+                    {check.message}
+                    {check.hint && <Typography.Text type="secondary"> — {check.hint}</Typography.Text>}
+                  </div>
+                ))}
+              action={
+                <Button size="small" onClick={() => getFuseState()}>
+                  {t('refresh')}
+                </Button>
+              }
+            />
+            <br />
+          </>
+        )}
         {osInfo.osType === 'windows' && (
           <Alert
             type="info"
@@ -1,13 +1,15 @@
 /**
  * Advanced Settings Component
- * 高级设置组件（网络代理、启动参数）
+ * 高级设置组件（网络代理、启动参数、rc 监听方式、凭据轮换、rclone 配置加密）
  */
 
-import { Button, Collapse, Form, Input, InputNumber, Message, Modal, Select } from '@arco-design/web-react'
+import { Button, Collapse, Form, Input, InputNumber, Message, Modal, Select, Switch } from '@arco-design/web-react'
 import { useTranslation } from 'react-i18next'
-import { nmConfig, saveNmConfig } from '../../../services/ConfigService'
+import { nmConfig, osInfo, saveNmConfig } from '../../../services/ConfigService'
 import { useSettingsStore } from '../../../stores/useSettingsStore'
 import { clearAllCache } from '../../../utils/tempCleanup'
+import { RcloneConfigEncryption } from './RcloneConfigEncryption'
+import { ConfigEncryption } from './ConfigEncryption'
 
 const FormItem = Form.Item
 
@@ -146,6 +148,46 @@ export function AdvancedSettings(): JSX.Element {
             />
           </FormItem>
         </Collapse.Item>
+
+        {osInfo.osType === 'linux' && (
+          <Collapse.Item name="rc_unix_socket" header={t('rc_unix_socket')}>
+            <FormItem label={t('rc_unix_socket_enable')}>
+              <Switch
+                checked={nmConfig.settings.rcUnixSocket ?? true}
+                onChange={value => {
+                  nmConfig.settings.rcUnixSocket = value
+                  incrementSettings()
+                }}
+              />
+            </FormItem>
+            <div style={{ fontSize: '0.75rem', color: 'var(--color-text-3)', marginTop: '0.25rem', marginBottom: '0.75rem' }}>
+              {t('rc_unix_socket_hint')}
+            </div>
+          </Collapse.Item>
+        )}
+
+        <Collapse.Item name="rotate_credentials" header={t('rotate_credentials')}>
+          <FormItem label={t('rotate_credentials_enable')}>
+            <Switch
+              checked={nmConfig.settings.rotateCredentials ?? false}
+              onChange={value => {
+                nmConfig.settings.rotateCredentials = value
+                incrementSettings()
+              }}
+            />
+          </FormItem>
+          <div style={{ fontSize: '0.75rem', color: 'var(--color-text-3)', marginTop: '0.25rem', marginBottom: '0.75rem' }}>
+            {t('rotate_credentials_hint')}
+          </div>
+        </Collapse.Item>
+
+        <Collapse.Item name="rclone_config_encryption" header={t('rclone_config_encryption')}>
+          <RcloneConfigEncryption />
+        </Collapse.Item>
+
+        <Collapse.Item name="config_encryption" header={t('config_encryption')}>
+          <ConfigEncryption />
+        </Collapse.Item>
       </Collapse>
 
       <div style={{ marginBottom: '0.75rem' }}>
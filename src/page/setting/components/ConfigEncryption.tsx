@@ -0,0 +1,114 @@
+/**
+ * Config Encryption Component
+ * 设置、修改或移除 config.json 敏感字段的加密口令
+ */
+
+import { useEffect, useState } from 'react'
+import { Button, Form, Input, Message, Popconfirm, Space, Tag } from '@arco-design/web-react'
+import { useTranslation } from 'react-i18next'
+import {
+  ConfigEncryption as EncryptionStatus,
+  getConfigEncryption,
+  setConfigPassphrase,
+  unlockConfig,
+} from '../../../utils/configEncryption'
+import { readNmConfig } from '../../../services/ConfigService'
+
+const FormItem = Form.Item
+
+export function ConfigEncryption(): JSX.Element {
+  const { t } = useTranslation()
+  const [status, setStatus] = useState<EncryptionStatus>()
+  const [current, setCurrent] = useState('')
+  const [passphrase, setPassphrase] = useState('')
+  const [confirm, setConfirm] = useState('')
+  const [saving, setSaving] = useState(false)
+
+  const refresh = async () => {
+    setStatus(await getConfigEncryption())
+  }
+
+  useEffect(() => {
+    refresh()
+  }, [])
+
+  const apply = async (newPassphrase: string) => {
+    setSaving(true)
+    try {
+      // 启动时未输入口令的，先解锁并重新读取解密后的配置
+      if (status?.locked) {
+        await unlockConfig(current)
+        await readNmConfig()
+      }
+      await setConfigPassphrase(newPassphrase)
+      Message.success(t(newPassphrase ? 'config_passphrase_set' : 'config_passphrase_removed'))
+      setCurrent('')
+      setPassphrase('')
+      setConfirm('')
+    } catch (e) {
+      Message.error(e instanceof Error ? e.message : String(e))
+    } finally {
+      setSaving(false)
+      refresh()
+    }
+  }
+
+  const handleSave = () => {
+    if (!passphrase) {
+      Message.warning(t('config_passphrase_empty'))
+      return
+    }
+    if (passphrase !== confirm) {
+      Message.error(t('config_passphrase_mismatch'))
+      return
+    }
+    apply(passphrase)
+  }
+
+  const usePassphrase = status?.keySource === 'passphrase'
+
+  return (
+    <>
+      <FormItem label={t('config_key_source')}>
+        {usePassphrase ? (
+          <Tag color="green">{t('config_key_source_passphrase')}</Tag>
+        ) : (
+          <Tag>{t('config_key_source_key_file')}</Tag>
+        )}
+        {status?.locked && <Tag color="orange">{t('config_locked_tag')}</Tag>}
+      </FormItem>
+      {usePassphrase && status?.locked && (
+        <FormItem label={t('config_current_passphrase')}>
+          <Input.Password value={current} onChange={setCurrent} />
+        </FormItem>
+      )}
+      <FormItem label={t('config_new_passphrase')}>
+        <Input.Password value={passphrase} onChange={setPassphrase} />
+      </FormItem>
+      <FormItem label={t('config_confirm_passphrase')}>
+        <Input.Password value={confirm} onChange={setConfirm} />
+      </FormItem>
+      <FormItem>
+        <Space>
+          <Button type="primary" loading={saving} onClick={handleSave}>
+            {t(usePassphrase ? 'config_change_passphrase' : 'config_set_passphrase')}
+          </Button>
+          {usePassphrase && (
+            <Popconfirm
+              focusLock
+              title={t('config_remove_passphrase_confirm')}
+              onOk={() => apply('')}
+            >
+              <Button status="danger" loading={saving}>
+                {t('config_remove_passphrase')}
+              </Button>
+            </Popconfirm>
+          )}
+        </Space>
+      </FormItem>
+      <div style={{ fontSize: '0.75rem', color: 'var(--color-text-3)', marginBottom: '0.75rem' }}>
+        {t('config_encryption_hint')}
+      </div>
+    </>
+  )
+}
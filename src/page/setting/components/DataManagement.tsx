@@ -1,19 +1,42 @@
 /**
  * Data Management Component
- * 数据管理组件（导入/导出配置、导入 alist 配置）
+ * 数据管理组件（导入/导出配置、导入 alist 配置、导入/导出 rclone.conf）
  */
 
 import { useState } from 'react'
-import { Button, Message, Modal, Space, Tooltip, Progress } from '@arco-design/web-react'
+import {
+  Button,
+  Checkbox,
+  Message,
+  Modal,
+  Space,
+  Tooltip,
+  Progress,
+} from '@arco-design/web-react'
 import { useTranslation } from 'react-i18next'
 import * as dialog from '@tauri-apps/plugin-dialog'
 import { invoke } from '@tauri-apps/api/core'
 import { importAlistConfig } from '../../../services/storage/AlistImportService'
+import { RcloneConfImport } from './RcloneConfImport'
+import { RcloneConfExport } from './RcloneConfExport'
+import { getConfigEncryption } from '../../../utils/configEncryption'
 
 export function DataManagement(): JSX.Element {
   const { t } = useTranslation()
   const [importing, setImporting] = useState(false)
   const [importProgress, setImportProgress] = useState({ current: 0, total: 0 })
+  const [rcloneConfVisible, setRcloneConfVisible] = useState(false)
+  const [rcloneConfExportVisible, setRcloneConfExportVisible] = useState(false)
+
+  const exportTo = async (path: string, includeKey: boolean) => {
+    try {
+      const out = await invoke<string>('export_config', { outPath: path, includeKey })
+      Message.success(`${t('config_exported')}: ${out}`)
+    } catch (e) {
+      const msg = e instanceof Error ? e.message : String(e)
+      Message.error(msg)
+    }
+  }
 
   const handleExport = async () => {
     try {
@@ -24,8 +47,29 @@ export function DataManagement(): JSX.Element {
         filters: [{ name: 'Zip', extensions: ['zip'] }],
       })
       if (!path) return
-      const out = await invoke<string>('export_config', { outPath: path })
-      Message.success(`${t('config_exported')}: ${out}`)
+      // 口令模式的备份可在任意设备上用口令解密，不需要密钥文件
+      const { keySource } = await getConfigEncryption()
+      if (keySource === 'passphrase') {
+        await exportTo(path, false)
+        return
+      }
+      let includeKey = false
+      Modal.confirm({
+        title: t('export_config'),
+        content: (
+          <Space direction="vertical">
+            <span>{t('export_config_key_warning')}</span>
+            <Checkbox
+              onChange={checked => {
+                includeKey = checked
+              }}
+            >
+              {t('export_config_include_key')}
+            </Checkbox>
+          </Space>
+        ),
+        onOk: () => exportTo(path, includeKey),
+      })
     } catch (e) {
       const msg = e instanceof Error ? e.message : String(e)
       Message.error(msg)
@@ -174,6 +218,30 @@ export function DataManagement(): JSX.Element {
         </Space>
       </Space>
 
+      {/* rclone.conf 导入导出 */}
+      <Space style={{ width: '100%', justifyContent: 'space-between' }} align="center">
+        <div style={{ display: 'flex', alignItems: 'center' }}>
+          <span style={{ fontWeight: 500 }}>{t('import_rclone_conf_description')}</span>
+        </div>
+        <Space>
+          <Tooltip content={t('export_rclone_conf_tip')}>
+            <Button type="text" status="success" onClick={() => setRcloneConfExportVisible(true)}>
+              {t('export_rclone_conf')}
+            </Button>
+          </Tooltip>
+          <Tooltip content={t('import_rclone_conf_tip')}>
+            <Button type="text" status="warning" onClick={() => setRcloneConfVisible(true)}>
+              {t('import_rclone_conf')}
+            </Button>
+          </Tooltip>
+        </Space>
+      </Space>
+      <RcloneConfImport visible={rcloneConfVisible} onClose={() => setRcloneConfVisible(false)} />
+      <RcloneConfExport
+        visible={rcloneConfExportVisible}
+        onClose={() => setRcloneConfExportVisible(false)}
+      />
+
       {/* 导入进度 */}
       {importing && importProgress.total > 0 && (
         <div style={{ width: '100%' }}>
@@ -0,0 +1,152 @@
+/**
+ * rclone.conf Export Modal
+ * 选择存储并导出为独立的 rclone.conf
+ */
+
+import { useEffect, useState } from 'react'
+import {
+  Alert,
+  Input,
+  Message,
+  Modal,
+  Radio,
+  Space,
+  Table,
+  TableColumnProps,
+  Tag,
+  Typography,
+} from '@arco-design/web-react'
+import { useTranslation } from 'react-i18next'
+import * as dialog from '@tauri-apps/plugin-dialog'
+import { useStorageStore } from '../../../stores'
+import { filterHideStorage } from '../../../services/storage/StorageManager'
+import {
+  exportRcloneConf,
+  RcloneConfSecretPolicy,
+} from '../../../services/storage/RcloneConfExportService'
+
+const RadioGroup = Radio.Group
+
+interface RcloneConfExportProps {
+  visible: boolean
+  onClose: () => void
+}
+
+export function RcloneConfExport({ visible, onClose }: RcloneConfExportProps): JSX.Element {
+  const { t } = useTranslation()
+  const { storageList } = useStorageStore()
+  const storages = filterHideStorage(storageList)
+  const [selected, setSelected] = useState<string[]>([])
+  const [secrets, setSecrets] = useState<RcloneConfSecretPolicy>('keep')
+  const [password, setPassword] = useState<string>('')
+  const [exporting, setExporting] = useState(false)
+
+  // 默认选中全部 rclone 存储
+  useEffect(() => {
+    if (visible) {
+      setSelected(storages.filter(s => s.framework === 'rclone').map(s => s.name))
+      setPassword('')
+    }
+  }, [visible])
+
+  const handleExport = async () => {
+    if (selected.length === 0) {
+      Message.warning(t('rclone_conf_export_none_selected'))
+      return
+    }
+    const path = await dialog.save({
+      title: t('export_rclone_conf'),
+      defaultPath: 'rclone.conf',
+      filters: [{ name: 'rclone.conf', extensions: ['conf'] }],
+    })
+    if (!path) return
+
+    setExporting(true)
+    try {
+      const report = await exportRcloneConf(path, selected, secrets, password)
+      Message.success(
+        t('rclone_conf_export_success', { count: report.exported.length, path: report.path })
+      )
+      if (report.unsupported.length > 0) {
+        Modal.warning({
+          title: t('rclone_conf_export_unsupported'),
+          content: report.unsupported
+            .map(item => `${item.name}: ${t('rclone_conf_export_reason_' + item.reason)}`)
+            .join('; '),
+        })
+      }
+      onClose()
+    } catch (e) {
+      const msg = e instanceof Error ? e.message : String(e)
+      Message.error(msg)
+    } finally {
+      setExporting(false)
+    }
+  }
+
+  const columns: TableColumnProps[] = [
+    {
+      title: t('name'),
+      dataIndex: 'name',
+    },
+    {
+      title: t('type'),
+      dataIndex: 'typeLabel',
+    },
+  ]
+
+  return (
+    <Modal
+      title={t('export_rclone_conf')}
+      visible={visible}
+      onOk={handleExport}
+      onCancel={onClose}
+      okText={t('export')}
+      cancelText={t('step_back')}
+      okButtonProps={{ loading: exporting }}
+      style={{ width: '40rem' }}
+    >
+      <Space direction="vertical" size="medium" style={{ width: '100%' }}>
+        <Table
+          columns={columns}
+          pagination={false}
+          scroll={{ y: 280 }}
+          rowKey="name"
+          rowSelection={{
+            type: 'checkbox',
+            selectedRowKeys: selected,
+            onChange: keys => setSelected(keys as string[]),
+          }}
+          data={storages.map(storage => ({
+            name: storage.name,
+            typeLabel:
+              storage.framework === 'openlist' ? (
+                <Space>
+                  {storage.type}
+                  <Tag color="orange">{t('rclone_conf_export_openlist')}</Tag>
+                </Space>
+              ) : (
+                storage.type
+              ),
+          }))}
+        />
+        <Space>
+          <Typography.Text>{t('rclone_conf_export_secrets')}</Typography.Text>
+          <RadioGroup value={secrets} onChange={value => setSecrets(value)}>
+            <Radio value="keep">{t('rclone_conf_secrets_keep')}</Radio>
+            <Radio value="reveal">{t('rclone_conf_secrets_reveal')}</Radio>
+            <Radio value="strip">{t('rclone_conf_secrets_strip')}</Radio>
+          </RadioGroup>
+        </Space>
+        {secrets === 'reveal' && (
+          <Alert type="warning" content={t('rclone_conf_secrets_reveal_warning')} />
+        )}
+        <Input.Password
+          value={password}
+          onChange={value => setPassword(value)}
+          placeholder={t('rclone_conf_export_password_placeholder')}
+        />
+      </Space>
+    </Modal>
+  )
+}
@@ -0,0 +1,210 @@
+/**
+ * rclone.conf Import Modal
+ * 预览 rclone.conf 中的 remote，选择后导入
+ */
+
+import { useEffect, useState } from 'react'
+import {
+  Button,
+  Input,
+  Message,
+  Modal,
+  Radio,
+  Space,
+  Table,
+  TableColumnProps,
+  Tag,
+  Typography,
+} from '@arco-design/web-react'
+import { useTranslation } from 'react-i18next'
+import * as dialog from '@tauri-apps/plugin-dialog'
+import {
+  importRcloneConf,
+  previewRcloneConf,
+  RcloneConfConflictPolicy,
+  RcloneConfPreview,
+} from '../../../services/storage/RcloneConfImportService'
+
+const RadioGroup = Radio.Group
+
+interface RcloneConfImportProps {
+  visible: boolean
+  onClose: () => void
+}
+
+export function RcloneConfImport({ visible, onClose }: RcloneConfImportProps): JSX.Element {
+  const { t } = useTranslation()
+  const [path, setPath] = useState<string>('')
+  const [password, setPassword] = useState<string>('')
+  const [preview, setPreview] = useState<RcloneConfPreview>()
+  const [selected, setSelected] = useState<string[]>([])
+  const [onConflict, setOnConflict] = useState<RcloneConfConflictPolicy>('rename')
+  const [loading, setLoading] = useState(false)
+
+  const loadPreview = async (confPath: string, confPassword: string) => {
+    setLoading(true)
+    try {
+      const result = await previewRcloneConf(confPath, confPassword)
+      setPreview(result)
+      setPath(result.path)
+      setSelected(result.remotes.filter(remote => remote.type).map(remote => remote.name))
+    } catch (e) {
+      const msg = e instanceof Error ? e.message : String(e)
+      Message.error(msg)
+    } finally {
+      setLoading(false)
+    }
+  }
+
+  // 打开时先尝试 rclone 的默认配置文件
+  useEffect(() => {
+    if (visible) {
+      setPassword('')
+      setPreview(undefined)
+      loadPreview('', '')
+    }
+  }, [visible])
+
+  const handleChooseFile = async () => {
+    const file = await dialog.open({
+      title: t('import_rclone_conf'),
+      multiple: false,
+      filters: [{ name: 'rclone.conf', extensions: ['conf'] }],
+    })
+    if (!file) return
+    setPassword('')
+    await loadPreview(file, '')
+  }
+
+  const handleImport = async () => {
+    if (selected.length === 0) {
+      Message.warning(t('rclone_conf_import_none_selected'))
+      return
+    }
+    setLoading(true)
+    try {
+      const report = await importRcloneConf(selected, onConflict, path, password)
+      if (report.failed.length > 0) {
+        Message.warning(
+          t('rclone_conf_import_partial', {
+            imported: report.imported.length,
+            skipped: report.skipped.length,
+            failed: report.failed.length,
+          })
+        )
+        console.warn(
+          'rclone.conf import errors:',
+          report.failed.map(e => `${e.name}: ${e.error}`).join('\n')
+        )
+      } else {
+        Message.success(
+          t('rclone_conf_import_success', {
+            imported: report.imported.length,
+            skipped: report.skipped.length,
+          })
+        )
+      }
+      onClose()
+    } catch (e) {
+      const msg = e instanceof Error ? e.message : String(e)
+      Message.error(msg)
+    } finally {
+      setLoading(false)
+    }
+  }
+
+  const columns: TableColumnProps[] = [
+    {
+      title: t('name'),
+      dataIndex: 'name',
+    },
+    {
+      title: t('type'),
+      dataIndex: 'typeLabel',
+    },
+    {
+      title: t('rclone_conf_import_as'),
+      dataIndex: 'importAs',
+    },
+  ]
+
+  return (
+    <Modal
+      title={t('import_rclone_conf')}
+      visible={visible}
+      onOk={handleImport}
+      onCancel={onClose}
+      okText={t('import')}
+      cancelText={t('step_back')}
+      okButtonProps={{ disabled: !preview || preview.locked, loading }}
+      style={{ width: '48rem' }}
+    >
+      <Space direction="vertical" size="medium" style={{ width: '100%' }}>
+        <Space style={{ width: '100%' }}>
+          <Input
+            value={path}
+            onChange={value => setPath(value)}
+            onPressEnter={() => loadPreview(path, password)}
+            placeholder={t('rclone_conf_path_placeholder')}
+            style={{ width: '32rem' }}
+          />
+          <Button onClick={handleChooseFile}>{t('select')}</Button>
+        </Space>
+
+        {preview?.encrypted && (
+          <Space>
+            <Input.Password
+              value={password}
+              onChange={value => setPassword(value)}
+              onPressEnter={() => loadPreview(path, password)}
+              placeholder={t('rclone_conf_password_placeholder')}
+              style={{ width: '24rem' }}
+            />
+            <Button type="primary" loading={loading} onClick={() => loadPreview(path, password)}>
+              {t('rclone_conf_unlock')}
+            </Button>
+          </Space>
+        )}
+
+        {preview && !preview.locked && (
+          <>
+            <Space>
+              <Typography.Text>{t('rclone_conf_on_conflict')}</Typography.Text>
+              <RadioGroup value={onConflict} onChange={value => setOnConflict(value)}>
+                <Radio value="rename">{t('rclone_conf_conflict_rename')}</Radio>
+                <Radio value="skip">{t('rclone_conf_conflict_skip')}</Radio>
+              </RadioGroup>
+            </Space>
+            <Table
+              columns={columns}
+              pagination={false}
+              scroll={{ y: 320 }}
+              rowKey="name"
+              loading={loading}
+              rowSelection={{
+                type: 'checkbox',
+                selectedRowKeys: selected,
+                onChange: keys => setSelected(keys as string[]),
+                checkboxProps: record => ({ disabled: !record.type }),
+              }}
+              data={preview.remotes.map(remote => ({
+                ...remote,
+                typeLabel: remote.type || <Tag color="red">{t('rclone_conf_missing_type')}</Tag>,
+                importAs: !remote.conflict ? (
+                  remote.name
+                ) : onConflict === 'skip' ? (
+                  <Tag color="gray">{t('rclone_conf_conflict_skip')}</Tag>
+                ) : (
+                  <Space>
+                    {remote.renameTo}
+                    <Tag color="orange">{t('rclone_conf_renamed')}</Tag>
+                  </Space>
+                ),
+              }))}
+            />
+          </>
+        )}
+      </Space>
+    </Modal>
+  )
+}
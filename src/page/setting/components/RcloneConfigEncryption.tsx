@@ -0,0 +1,105 @@
+/**
+ * rclone Config Encryption Component
+ * 设置、修改或移除 NetMount 的 rclone 配置密码
+ */
+
+import { useEffect, useState } from 'react'
+import { Button, Form, Input, Message, Popconfirm, Space, Tag } from '@arco-design/web-react'
+import { useTranslation } from 'react-i18next'
+import {
+  getRcloneConfigEncryption,
+  RcloneConfigEncryption as EncryptionStatus,
+} from '../../../utils/rclone/configPassword'
+import { changeRcloneConfigPassword } from '../../../controller/setting/rcloneConfigPassword'
+
+const FormItem = Form.Item
+
+export function RcloneConfigEncryption(): JSX.Element {
+  const { t } = useTranslation()
+  const [status, setStatus] = useState<EncryptionStatus>()
+  const [current, setCurrent] = useState('')
+  const [password, setPassword] = useState('')
+  const [confirm, setConfirm] = useState('')
+  const [saving, setSaving] = useState(false)
+
+  const refresh = async () => {
+    setStatus(await getRcloneConfigEncryption())
+  }
+
+  useEffect(() => {
+    refresh()
+  }, [])
+
+  const apply = async (newPassword: string) => {
+    setSaving(true)
+    try {
+      if (await changeRcloneConfigPassword(current, newPassword)) {
+        Message.success(
+          t(newPassword ? 'rclone_config_password_set' : 'rclone_config_password_removed')
+        )
+        setCurrent('')
+        setPassword('')
+        setConfirm('')
+      }
+    } finally {
+      setSaving(false)
+      refresh()
+    }
+  }
+
+  const handleSave = () => {
+    if (!password) {
+      Message.warning(t('rclone_config_password_empty'))
+      return
+    }
+    if (password !== confirm) {
+      Message.error(t('rclone_config_password_mismatch'))
+      return
+    }
+    apply(password)
+  }
+
+  return (
+    <>
+      <FormItem label={t('rclone_config_status')}>
+        {status?.encrypted ? (
+          <Tag color="green">{t('rclone_config_encrypted')}</Tag>
+        ) : (
+          <Tag>{t('rclone_config_not_encrypted')}</Tag>
+        )}
+      </FormItem>
+      {status?.encrypted && !status.unlocked && (
+        <FormItem label={t('rclone_config_current_password')}>
+          <Input.Password value={current} onChange={setCurrent} />
+        </FormItem>
+      )}
+      <FormItem label={t('rclone_config_new_password')}>
+        <Input.Password value={password} onChange={setPassword} />
+      </FormItem>
+      <FormItem label={t('rclone_config_confirm_password')}>
+        <Input.Password value={confirm} onChange={setConfirm} />
+      </FormItem>
+      <FormItem>
+        <Space>
+          <Button type="primary" loading={saving} onClick={handleSave}>
+            {t(status?.encrypted ? 'rclone_config_change_password' : 'rclone_config_set_password')}
+          </Button>
+          {status?.encrypted && (
+            <Popconfirm
+              focusLock
+              title={t('rclone_config_remove_password_confirm')}
+              onOk={() => apply('')}
+            >
+              <Button status="danger" loading={saving}>
+                {t('rclone_config_remove_password')}
+              </Button>
+            </Popconfirm>
+          )}
+        </Space>
+      </FormItem>
+      <div style={{ fontSize: '0.75rem', color: 'var(--color-text-3)', marginBottom: '0.75rem' }}>
+        {t('rclone_config_encryption_hint')}
+      </div>
+    </>
+  )
+}
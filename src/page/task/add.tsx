@@ -13,7 +13,7 @@ import {
 import { useReducer, useEffect, useState } from 'react'
 import { useTranslation } from 'react-i18next'
 import { nmConfig, roConfig } from '../../services/ConfigService'
-import { TaskListItem } from '../../type/config'
+import { TaskDependency, TaskListItem, TaskVersioning } from '../../type/config'
 import { rcloneInfo } from '../../services/rclone'
 import { IconQuestionCircle } from '@arco-design/web-react/icon'
 import { filterHideStorage } from '../../services/storage/StorageManager'
@@ -36,7 +36,10 @@ type Action =
   | { type: 'setTargetPath'; payload: string }
   | { type: 'setIntervalDays'; payload: number }
   | { type: 'setRunTime'; payload: { h: number; m: number; s: number } }
-  | { type: 'setResync'; payload: boolean }
+  | { type: 'setDebounce'; payload: number }
+  | { type: 'setParameter'; payload: { key: string; value: unknown } }
+  | { type: 'setAfter'; payload: TaskDependency[] }
+  | { type: 'setVersioning'; payload: Partial<TaskVersioning> }
   | { type: 'setWhole'; payload: TaskListItem }
 
 // 定义 reducer 函数
@@ -63,8 +66,20 @@ const reducer = (state: TaskInfoState, action: Action): TaskInfoState => {
       }
     case 'setRunTime':
       return { ...state, run: { ...state.run, time: { ...state.run.time, ...action.payload } } }
-    case 'setResync':
-      return { ...state, parameters: { ...state.parameters, resync: action.payload } }
+    case 'setDebounce':
+      return { ...state, run: { ...state.run, debounce: action.payload } }
+    case 'setParameter':
+      return {
+        ...state,
+        parameters: { ...state.parameters, [action.payload.key]: action.payload.value },
+      }
+    case 'setAfter':
+      return { ...state, after: action.payload }
+    case 'setVersioning':
+      return {
+        ...state,
+        versioning: { enabled: false, ...state.versioning, ...action.payload },
+      }
     case 'setWhole':
       return action.payload
     default:
@@ -115,6 +130,8 @@ function AddTask_page() {
   })
 
   const isEditMode = getURLSearchParam('edit') === 'true'
+  // 启动时、一次性、仅由前置任务触发与变更触发的模式不需要时间设置
+  const isTimedMode = !['start', 'disposable', 'after', 'onChange'].includes(taskInfo.run.mode)
 
   const editMode = () => {
     const name = getURLSearchParam('taskName')
@@ -175,7 +192,72 @@ function AddTask_page() {
             ))}
           </Select>
         </Form.Item>
-        {taskInfo.run.mode != 'start' && taskInfo.run.mode !== 'disposable' && (
+        <Form.Item
+          label={
+            <Tooltip content={t('task_after_tip')}>
+              {t('task_after')} <IconQuestionCircle />
+            </Tooltip>
+          }
+        >
+          <Row gutter={10}>
+            <Col flex={'auto'}>
+              <Select
+                mode="multiple"
+                allowClear
+                placeholder={t('task_after_none')}
+                value={(taskInfo.after || []).map(dep => dep.task)}
+                onChange={(value: string[]) => {
+                  const on = taskInfo.after?.[0]?.on || 'success'
+                  dispatch({
+                    type: 'setAfter',
+                    payload: value.map(task => ({ task, on })),
+                  } as Action)
+                }}
+              >
+                {(nmConfig.task || [])
+                  .filter(item => item.name !== taskInfo.name)
+                  .map(item => (
+                    <Select.Option key={item.name} value={item.name}>
+                      {item.name}
+                    </Select.Option>
+                  ))}
+              </Select>
+            </Col>
+            <Col flex={'10rem'}>
+              <Select
+                disabled={!taskInfo.after || taskInfo.after.length === 0}
+                value={taskInfo.after?.[0]?.on || 'success'}
+                onChange={(on: TaskDependency['on']) =>
+                  dispatch({
+                    type: 'setAfter',
+                    payload: (taskInfo.after || []).map(dep => ({ ...dep, on })),
+                  } as Action)
+                }
+              >
+                {(['success', 'failure', 'always'] as const).map(on => (
+                  <Select.Option key={on} value={on}>
+                    {t(`task_after_on_${on}`)}
+                  </Select.Option>
+                ))}
+              </Select>
+            </Col>
+          </Row>
+        </Form.Item>
+        {taskInfo.run.mode === 'onChange' && (
+          <Form.Item label={t('task_debounce')}>
+            <InputNumber
+              style={{ width: '12rem' }}
+              min={1}
+              suffix={t('second')}
+              value={taskInfo.run.debounce ?? 5}
+              onChange={value => dispatch({ type: 'setDebounce', payload: value } as Action)}
+            />
+            <div style={{ fontSize: '12px', color: '#999', marginTop: '4px' }}>
+              {t('task_debounce_tip')}
+            </div>
+          </Form.Item>
+        )}
+        {isTimedMode && (
           <>
             <Form.Item label={t('interval')}>
               <Row>
@@ -286,21 +368,155 @@ function AddTask_page() {
         </Form.Item>
 
         {taskInfo.taskType === 'bisync' && (
-          <Form.Item label={t('resync')}>
+          <Form.Item label={t('conflict_resolve')}>
+            <Select
+              value={(taskInfo.parameters?.conflictResolve as string | undefined) || 'none'}
+              onChange={value =>
+                dispatch({
+                  type: 'setParameter',
+                  payload: { key: 'conflictResolve', value },
+                } as Action)
+              }
+            >
+              {['none', 'newer', 'older', 'larger', 'path1', 'path2'].map(item => (
+                <Select.Option key={item} value={item}>
+                  {t(`conflict_resolve_${item}`)}
+                </Select.Option>
+              ))}
+            </Select>
+          </Form.Item>
+        )}
+
+        {taskInfo.taskType === 'check' && (
+          <Form.Item label={t('check_options')}>
+            <Space direction="vertical">
+              <Checkbox
+                checked={taskInfo.parameters?.cryptcheck === true}
+                onChange={checked =>
+                  dispatch({
+                    type: 'setParameter',
+                    payload: { key: 'cryptcheck', value: checked },
+                  } as Action)
+                }
+              >
+                {t('check_cryptcheck')}
+              </Checkbox>
+              <Checkbox
+                disabled={taskInfo.parameters?.cryptcheck === true}
+                checked={taskInfo.parameters?.download === true}
+                onChange={checked =>
+                  dispatch({
+                    type: 'setParameter',
+                    payload: { key: 'download', value: checked },
+                  } as Action)
+                }
+              >
+                {t('check_download')}
+              </Checkbox>
+              <Checkbox
+                checked={taskInfo.parameters?.failOnDifference !== false}
+                onChange={checked =>
+                  dispatch({
+                    type: 'setParameter',
+                    payload: { key: 'failOnDifference', value: checked },
+                  } as Action)
+                }
+              >
+                {t('check_fail_on_difference')}
+              </Checkbox>
+            </Space>
+          </Form.Item>
+        )}
+
+        {['copy', 'move'].includes(taskInfo.taskType) && (
+          <Form.Item label={t('resumable')}>
+            <Checkbox
+              checked={taskInfo.parameters?.resumable === true}
+              onChange={checked =>
+                dispatch({
+                  type: 'setParameter',
+                  payload: { key: 'resumable', value: checked },
+                } as Action)
+              }
+            >
+              {t('resumable_enable')}
+            </Checkbox>
+            <div style={{ fontSize: '12px', color: '#999', marginTop: '4px' }}>
+              {t('resumable_tip')}
+            </div>
+          </Form.Item>
+        )}
+
+        {taskInfo.taskType === 'sync' && (
+          <Form.Item label={t('require_approval')}>
             <Checkbox
-              checked={taskInfo.parameters?.resync === true}
+              checked={taskInfo.parameters?.requireApproval === true}
               onChange={checked =>
-                dispatch({ type: 'setResync', payload: checked } as Action)
+                dispatch({
+                  type: 'setParameter',
+                  payload: { key: 'requireApproval', value: checked },
+                } as Action)
               }
             >
-              {t('force_resync')}
+              {t('require_approval_enable')}
             </Checkbox>
             <div style={{ fontSize: '12px', color: '#999', marginTop: '4px' }}>
-              {t('force_resync_tip')}
+              {t('require_approval_tip')}
             </div>
           </Form.Item>
         )}
 
+        {['copy', 'move', 'sync'].includes(taskInfo.taskType) && (
+          <Form.Item label={t('versioning')}>
+            <Space direction="vertical">
+              <Checkbox
+                checked={taskInfo.versioning?.enabled === true}
+                onChange={checked =>
+                  dispatch({ type: 'setVersioning', payload: { enabled: checked } } as Action)
+                }
+              >
+                {t('versioning_enable')}
+              </Checkbox>
+              {taskInfo.versioning?.enabled && (
+                <Space wrap>
+                  <Select
+                    style={{ width: '14rem' }}
+                    value={taskInfo.versioning.mode || 'dir'}
+                    onChange={value =>
+                      dispatch({ type: 'setVersioning', payload: { mode: value } } as Action)
+                    }
+                  >
+                    {['dir', 'suffix'].map(item => (
+                      <Select.Option key={item} value={item}>
+                        {t(`versioning_mode_${item}`)}
+                      </Select.Option>
+                    ))}
+                  </Select>
+                  <InputNumber
+                    style={{ width: '10rem' }}
+                    min={0}
+                    prefix={t('versioning_keep_count')}
+                    value={taskInfo.versioning.keepCount ?? 0}
+                    onChange={value =>
+                      dispatch({ type: 'setVersioning', payload: { keepCount: value } } as Action)
+                    }
+                  />
+                  <InputNumber
+                    style={{ width: '10rem' }}
+                    min={0}
+                    prefix={t('versioning_keep_days')}
+                    value={taskInfo.versioning.keepDays ?? 0}
+                    onChange={value =>
+                      dispatch({ type: 'setVersioning', payload: { keepDays: value } } as Action)
+                    }
+                  />
+                </Space>
+              )}
+              <div style={{ fontSize: '12px', color: '#999' }}>{t('versioning_tip')}</div>
+            </Space>
+          </Form.Item>
+        )}
+
         <Form.Item label={t('source_path')}>
           <Row>
             <Col flex={'7rem'}>
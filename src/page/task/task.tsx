@@ -3,6 +3,7 @@ import {
   Button,
   Grid,
   Link,
+  Message,
   Modal,
   Space,
   Table,
@@ -16,8 +17,11 @@ import { useNavigate } from 'react-router-dom'
 import { delTask, taskScheduler } from '../../controller/task/task'
 import { NoData_module } from '../other/noData'
 import { IconQuestionCircle } from '@arco-design/web-react/icon'
-import { openUrlInBrowser } from '../../utils'
+import { formatSize, openUrlInBrowser } from '../../utils'
 import { showLog } from '../other/modal'
+import { useEffect, useState } from 'react'
+import { taskRepository } from '../../repositories'
+import type { TaskCheckpoint, TaskPlan } from '../../type/task/task'
 
 const Row = Grid.Row
 const Col = Grid.Col
@@ -27,6 +31,100 @@ function Task_page() {
   const navigate = useNavigate()
   const { increment: incrementTask } = useTaskStore()
   const [modal, contextHolder] = Modal.useModal()
+  const [checkpoints, setCheckpoints] = useState<Record<string, TaskCheckpoint>>({})
+
+  // 断点续传任务的整体进度（包含中断前已完成的部分）
+  useEffect(() => {
+    const refresh = async () => {
+      const next: Record<string, TaskCheckpoint> = {}
+      for (const task of nmConfig.task.filter(item => item.parameters?.resumable === true)) {
+        const checkpoint = await taskRepository.getTaskCheckpoint(task.name).catch(() => null)
+        if (checkpoint) {
+          next[task.name] = checkpoint
+        }
+      }
+      setCheckpoints(next)
+    }
+    void refresh()
+    const timer = window.setInterval(() => void refresh(), 3000)
+    return () => window.clearInterval(timer)
+  }, [])
+
+  const checkpointText = (checkpoint: TaskCheckpoint) =>
+    t('task_checkpoint_progress', {
+      files: `${checkpoint.completedFiles}/${checkpoint.totalFiles}`,
+      size: `${formatSize(checkpoint.completedBytes)}/${formatSize(checkpoint.totalBytes)}`,
+      percent: checkpoint.totalBytes
+        ? Math.floor((checkpoint.completedBytes / checkpoint.totalBytes) * 100)
+        : 0,
+    })
+
+  const planSummary = (plan: TaskPlan) => (
+    <div style={{ whiteSpace: 'pre-wrap' }}>
+      {plan.errors?.length
+        ? plan.errors.join('\n')
+        : (['copy', 'update', 'delete', 'skip'] as const)
+            .map(
+              action =>
+                `${t('task_plan_' + action)}: ${plan.totals[action].count} (${formatSize(plan.totals[action].bytes)})`
+            )
+            .join('\n')}
+    </div>
+  )
+
+  // 要求确认的 sync 任务：运行 dry-run 并在确认后批准本次计划
+  const previewPlan = async (taskName: string) => {
+    let plan: TaskPlan
+    try {
+      plan = await taskRepository.dryRunTask(taskName, true)
+    } catch (e) {
+      Message.error(String(e))
+      return
+    }
+    modal.confirm!({
+      title: t('task_plan'),
+      content: planSummary(plan),
+      okText: t('approve'),
+      okButtonProps: { disabled: !!plan.errors?.length },
+      onOk: async () => {
+        try {
+          await taskRepository.approveTaskPlan(plan.id)
+          Message.success(t('task_plan_approved'))
+        } catch (e) {
+          Message.error(String(e))
+        }
+      },
+    })
+  }
+
+  // bisync 任务：预览 resync 的结果，确认后按该预览执行 resync
+  const previewResync = async (taskName: string) => {
+    let preview: TaskPlan
+    try {
+      preview = await taskRepository.previewBisyncResync(taskName)
+    } catch (e) {
+      Message.error(String(e))
+      return
+    }
+    modal.confirm!({
+      title: t('resync'),
+      content: planSummary(preview),
+      okText: t('resync'),
+      okButtonProps: { disabled: !!preview.errors?.length },
+      onOk: async () => {
+        try {
+          const record = await taskRepository.bisyncResync(taskName, preview.id)
+          if (record.result?.success) {
+            Message.success(t('bisync_resync_done'))
+          } else {
+            Message.error(record.result?.errorMessages?.join('\n') || t('resync'))
+          }
+        } catch (e) {
+          Message.error(String(e))
+        }
+      },
+    })
+  }
 
   const columns: TableColumnProps[] = [
     {
@@ -110,7 +208,9 @@ function Task_page() {
                 >
                   <Typography.Ellipsis>
                     {' '}
-                    {(taskItem.runInfo?.msg || t('none')).split('\n').pop()}
+                    {checkpoints[taskItem.name]
+                      ? checkpointText(checkpoints[taskItem.name]!)
+                      : (taskItem.runInfo?.msg || t('none')).split('\n').pop()}
                   </Typography.Ellipsis>
                 </Link>
               ),
@@ -141,6 +241,17 @@ function Task_page() {
                       >
                         {t('trigger')}
                       </Button>
+                      {taskItem.taskType === 'sync' &&
+                        taskItem.parameters?.requireApproval === true && (
+                          <Button onClick={() => void previewPlan(taskItem.name)}>
+                            {t('task_plan')}
+                          </Button>
+                        )}
+                      {taskItem.taskType === 'bisync' && (
+                        <Button onClick={() => void previewResync(taskItem.name)}>
+                          {t('resync')}
+                        </Button>
+                      )}
                     </>
                   ) : (
                     <>
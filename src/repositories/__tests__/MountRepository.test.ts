@@ -238,4 +238,89 @@ describe('MountRepository', () => {
       expect(result).toBeUndefined()
     })
   })
-})
\ No newline at end of file
+  describe('mount health', () => {
+    it('should invoke backend health commands', async () => {
+      const { invoke } = await import('@tauri-apps/api/core')
+      const health = {
+        mountPath: '/mnt/test',
+        storageName: 'test-storage',
+        state: 'remounted',
+        retries: 1,
+        checkedAt: 1,
+      }
+      vi.mocked(invoke).mockResolvedValueOnce([health])
+      vi.mocked(invoke).mockResolvedValueOnce(true)
+
+      const result = await repository.checkMountHealth()
+      const reset = await repository.resetMountHealth('/mnt/test')
+
+      expect(result).toEqual([health])
+      expect(reset).toBe(true)
+      expect(invoke).toHaveBeenCalledWith('check_mount_health')
+      expect(invoke).toHaveBeenCalledWith('reset_mount_health', { mountPath: '/mnt/test' })
+    })
+  })
+  describe('validateMountPoint', () => {
+    it('should pass options to the backend check', async () => {
+      const { invoke } = await import('@tauri-apps/api/core')
+      const check = {
+        path: '/mnt/test',
+        valid: false,
+        created: false,
+        problems: [{ code: 'notEmpty', severity: 'error', message: '/mnt/test is not empty' }],
+      }
+      vi.mocked(invoke).mockResolvedValueOnce(check)
+
+      const result = await repository.validateMountPoint('/mnt/test', { allowNonEmpty: false })
+
+      expect(result.valid).toBe(false)
+      expect(result.problems[0]!.code).toBe('notEmpty')
+      expect(invoke).toHaveBeenCalledWith('validate_mount_point', {
+        path: '/mnt/test',
+        opts: { allowNonEmpty: false },
+      })
+    })
+  })
+  describe('mountStorage readiness', () => {
+    it('should mount through the backend and wait until ready for local rclone', async () => {
+      const { invoke } = await import('@tauri-apps/api/core')
+      const { rclone_api_post } = await import('../../utils/rclone/request')
+      vi.mocked(invoke).mockResolvedValueOnce({
+        mountPath: '/mnt/test',
+        mountMs: 120,
+        readyMs: 80,
+        latencyMs: 200,
+      })
+
+      const mountInfo: MountListItem = {
+        storageName: 'test-storage',
+        mountPath: '/mnt/test',
+        parameters: { vfsOpt: {}, mountOpt: {} },
+        autoMount: false,
+      }
+
+      await repository.mountStorage(mountInfo)
+
+      expect(invoke).toHaveBeenCalledWith('mount_and_wait', { mount: mountInfo, timeoutMs: undefined })
+      expect(rclone_api_post).not.toHaveBeenCalledWith('/mount/mount', expect.anything())
+    })
+  })
+  describe('suggestMountPath', () => {
+    it('should return the backend suggestion', async () => {
+      const { invoke } = await import('@tauri-apps/api/core')
+      vi.mocked(invoke).mockResolvedValueOnce({
+        path: '~/NetMount/my_drive-2',
+        local: '/home/user/NetMount/my_drive-2',
+        created: false,
+      })
+
+      const result = await repository.suggestMountPath('my/drive')
+
+      expect(result.path).toBe('~/NetMount/my_drive-2')
+      expect(invoke).toHaveBeenCalledWith('suggest_mount_path', {
+        storageName: 'my/drive',
+        create: undefined,
+      })
+    })
+  })
+})
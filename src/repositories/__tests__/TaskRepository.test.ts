@@ -521,4 +521,140 @@ describe('TaskRepository', () => {
       expect(result[0]!.name).toBe('pending1')
     })
   })
-})
\ No newline at end of file
+
+  describe('getTaskHistory', () => {
+    it('should query run history from the backend', async () => {
+      const { invoke } = await import('@tauri-apps/api/core')
+      vi.mocked(invoke).mockResolvedValueOnce([
+        { id: 'r1', taskId: 't1', taskName: 't1', type: 'copy', status: 'success', startedAt: 1, completedAt: 2 },
+      ])
+
+      const result = await repository.getTaskHistory({ taskName: 't1', limit: 10 })
+
+      expect(invoke).toHaveBeenCalledWith('get_task_history', { query: { taskName: 't1', limit: 10 } })
+      expect(result).toHaveLength(1)
+      expect(result[0]!.status).toBe('success')
+    })
+  })
+
+  describe('task queue', () => {
+    it('should read the run queue from the backend', async () => {
+      const { invoke } = await import('@tauri-apps/api/core')
+      vi.mocked(invoke).mockResolvedValueOnce({
+        running: [],
+        waiting: [{ id: 'q1', taskName: 't1', storages: ['s1'], priority: 0, enqueuedAt: 1 }],
+      })
+
+      const snapshot = await repository.getTaskQueue()
+
+      expect(invoke).toHaveBeenCalledWith('get_task_queue')
+      expect(snapshot.waiting[0]!.taskName).toBe('t1')
+    })
+
+    it('should reorder and cancel queued tasks', async () => {
+      const { invoke } = await import('@tauri-apps/api/core')
+      vi.mocked(invoke).mockResolvedValue(undefined)
+
+      await repository.moveQueuedTask('q1', 0)
+      await repository.cancelQueuedTask('q2')
+
+      expect(invoke).toHaveBeenCalledWith('move_queued_task', { id: 'q1', index: 0 })
+      expect(invoke).toHaveBeenCalledWith('cancel_queued_task', { id: 'q2' })
+    })
+  })
+
+  describe('dryRunTask', () => {
+    it('should request a saved dry-run plan from the backend', async () => {
+      const { invoke } = await import('@tauri-apps/api/core')
+      vi.mocked(invoke).mockResolvedValueOnce({
+        id: 'p1',
+        taskName: 't1',
+        taskType: 'sync',
+        createdAt: 1,
+        entries: [{ path: 'a.txt', action: 'delete', size: 3 }],
+        totals: {
+          copy: { count: 0, bytes: 0 },
+          update: { count: 0, bytes: 0 },
+          delete: { count: 1, bytes: 3 },
+          skip: { count: 0, bytes: 0 },
+        },
+      })
+
+      const plan = await repository.dryRunTask('t1', true)
+
+      expect(invoke).toHaveBeenCalledWith('dry_run_task', { name: 't1', save: true })
+      expect(plan.totals.delete.count).toBe(1)
+    })
+  })
+
+  describe('task versions', () => {
+    it('should list and restore file versions', async () => {
+      const { invoke } = await import('@tauri-apps/api/core')
+      vi.mocked(invoke).mockResolvedValueOnce([
+        {
+          version: '20240101-120000',
+          createdAt: 1704110400000,
+          path: 'backup/.netmount-versions/20240101-120000/a.txt',
+          size: 3,
+          modTime: '2024-01-01T11:00:00Z',
+        },
+      ])
+
+      const versions = await repository.listTaskVersions('t1', 'a.txt')
+      expect(invoke).toHaveBeenCalledWith('list_task_versions', { name: 't1', path: 'a.txt' })
+      expect(versions[0]!.version).toBe('20240101-120000')
+
+      vi.mocked(invoke).mockResolvedValueOnce(undefined)
+      await repository.restoreTaskVersion('t1', 'a.txt', '20240101-120000')
+      expect(invoke).toHaveBeenCalledWith('restore_task_version', {
+        name: 't1',
+        path: 'a.txt',
+        version: '20240101-120000',
+      })
+    })
+  })
+
+  describe('listTaskWatches', () => {
+    it('should report degraded watches from the backend', async () => {
+      const { invoke } = await import('@tauri-apps/api/core')
+      vi.mocked(invoke).mockResolvedValueOnce([
+        {
+          taskName: 't1',
+          path: '/home/user/Pictures',
+          backend: 'poll',
+          degraded: 'limit',
+          pending: 2,
+        },
+      ])
+
+      const watches = await repository.listTaskWatches()
+
+      expect(invoke).toHaveBeenCalledWith('list_task_watches')
+      expect(watches[0]!.backend).toBe('poll')
+      expect(watches[0]!.pending).toBe(2)
+    })
+  })
+
+  describe('task checkpoints', () => {
+    it('should return resumed progress and discard checkpoints', async () => {
+      const { invoke } = await import('@tauri-apps/api/core')
+      vi.mocked(invoke).mockResolvedValueOnce({
+        taskName: 't1',
+        createdAt: 1,
+        updatedAt: 2,
+        totalFiles: 10,
+        totalBytes: 1000,
+        completedFiles: 4,
+        completedBytes: 400,
+      })
+
+      const checkpoint = await repository.getTaskCheckpoint('t1')
+      expect(invoke).toHaveBeenCalledWith('get_task_checkpoint', { name: 't1' })
+      expect(checkpoint?.completedFiles).toBe(4)
+
+      vi.mocked(invoke).mockResolvedValueOnce(true)
+      await expect(repository.discardTaskCheckpoint('t1')).resolves.toBe(true)
+      expect(invoke).toHaveBeenCalledWith('discard_task_checkpoint', { name: 't1' })
+    })
+  })
+})
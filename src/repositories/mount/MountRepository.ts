@@ -10,9 +10,9 @@ import { RepositoryError, ErrorCode } from '../interfaces/IRepository'
 import { logger } from '../../services/LoggerService'
 import { nmConfig, saveNmConfig } from '../../services/ConfigService'
 import { rcloneInfo } from '../../services/rclone'
-import type { MountEntity, MountStatus, VfsOptions, MountOptions } from '../../type/mount/mount'
+import type { MountEntity, MountStatus, VfsOptions, MountOptions, MountHealth, MountPathSuggestion, MountPointCheck, MountPointCheckOptions } from '../../type/mount/mount'
 import type { MountListItem } from '../../type/config'
-import { generateMountId, parseMountId, normalizeMountPath, performMount, performUnmount, refreshMountList, isMounted, getMountConfig, forgetAllVfsCache } from './mountHelpers'
+import { generateMountId, parseMountId, normalizeMountPath, performMount, performUnmount, refreshMountList, isMounted, getMountConfig, forgetAllVfsCache, getMountHealth, checkMountHealth, resetMountHealth, validateMountPoint, suggestMountPath } from './mountHelpers'
 
 const mountLogger = logger.withContext('MountRepository')
 
@@ -272,6 +272,41 @@ export class MountRepository extends BaseRepository<MountEntity> {
     await forgetAllVfsCache()
   }
 
+  /**
+   * 获取挂载健康状态
+   */
+  async getMountHealth(): Promise<MountHealth[]> {
+    return getMountHealth()
+  }
+
+  /**
+   * 立即检查挂载健康状态并恢复失效的挂载
+   */
+  async checkMountHealth(): Promise<MountHealth[]> {
+    return checkMountHealth()
+  }
+
+  /**
+   * 清零挂载点的重新挂载次数
+   */
+  async resetMountHealth(mountPath: string): Promise<boolean> {
+    return resetMountHealth(mountPath)
+  }
+
+  /**
+   * 挂载前检查挂载点
+   */
+  async validateMountPoint(path: string, opts?: MountPointCheckOptions): Promise<MountPointCheck> {
+    return validateMountPoint(path, opts)
+  }
+
+  /**
+   * 为存储推荐空闲的挂载目录
+   */
+  async suggestMountPath(storageName: string, create?: boolean): Promise<MountPathSuggestion> {
+    return suggestMountPath(storageName, create)
+  }
+
   /**
    * 编辑挂载配置
    */
@@ -1,3 +1,4 @@
+import { invoke } from '@tauri-apps/api/core'
 import { logger } from '../../services/LoggerService'
 import { nmConfig } from '../../services/ConfigService'
 import { rcloneInfo } from '../../services/rclone'
@@ -7,6 +8,13 @@ import { rclone_api_post } from '../../utils/rclone/request'
 import { fs_exist_dir, fs_make_dir } from '../../utils'
 import { convertStoragePath } from '../../services/storage/StorageManager'
 import type { MountListItem } from '../../type/config'
+import type {
+  MountHealth,
+  MountPathSuggestion,
+  MountPointCheck,
+  MountReady,
+  MountPointCheckOptions,
+} from '../../type/mount/mount'
 import type { MountList } from '../../type/rclone/rcloneInfo'
 import { isMountListResponse } from '../../type/rclone/api'
 
@@ -130,11 +138,21 @@ export async function performMount(mountInfo: MountListItem): Promise<void> {
   }
 
   try {
-    await rclone_api_post('/mount/mount', {
-      fs: convertStoragePath(mountInfo.storageName) || mountInfo.storageName,
-      mountPoint: mountPath,
-      ...mountInfo.parameters,
-    })
+    if (rcloneInfo.endpoint.isLocal) {
+      // 本地挂载由后端等待文件系统真正可访问后才返回，避免随后打开目录或启动任务时竞争
+      const ready = await mountAndWait(mountInfo)
+      mountLogger.info('Mount ready', {
+        mountPath: ready.mountPath,
+        mountMs: ready.mountMs,
+        readyMs: ready.readyMs,
+      })
+    } else {
+      await rclone_api_post('/mount/mount', {
+        fs: convertStoragePath(mountInfo.storageName) || mountInfo.storageName,
+        mountPoint: mountPath,
+        ...mountInfo.parameters,
+      })
+    }
   } catch (e) {
     const isMacOS = rcloneInfo.version.os.toLowerCase().includes('darwin')
     const errorMsg = e instanceof Error ? e.message : String(e)
@@ -295,3 +313,52 @@ export async function performUnmount(mountPath: string): Promise<void> {
     }
   }
 }
+
+/**
+ * 获取后端监测到的挂载健康状态
+ */
+export async function getMountHealth(): Promise<MountHealth[]> {
+  return invoke<MountHealth[]>('get_mount_health')
+}
+
+/**
+ * 立即检查全部挂载点，失效的挂载会被重新挂载
+ */
+export async function checkMountHealth(): Promise<MountHealth[]> {
+  return invoke<MountHealth[]>('check_mount_health')
+}
+
+/**
+ * 清零重新挂载次数，允许监测再次尝试恢复
+ */
+export async function resetMountHealth(mountPath: string): Promise<boolean> {
+  return invoke<boolean>('reset_mount_health', { mountPath })
+}
+
+/**
+ * 挂载前检查挂载点（重复、嵌套、已挂载、非空、权限等）
+ */
+export async function validateMountPoint(
+  path: string,
+  opts?: MountPointCheckOptions
+): Promise<MountPointCheck> {
+  return invoke<MountPointCheck>('validate_mount_point', { path, opts })
+}
+
+/**
+ * 通过后端挂载并等待挂载点可访问（仅本地 rclone）
+ * @param timeoutMs - 等待就绪的最长时间，默认 15 秒
+ */
+export async function mountAndWait(mountInfo: MountListItem, timeoutMs?: number): Promise<MountReady> {
+  return invoke<MountReady>('mount_and_wait', { mount: mountInfo, timeoutMs })
+}
+
+/**
+ * 为存储推荐空闲的挂载目录（默认 ~/NetMount/<存储名>，可通过 settings.mountBase 配置）
+ */
+export async function suggestMountPath(
+  storageName: string,
+  create?: boolean
+): Promise<MountPathSuggestion> {
+  return invoke<MountPathSuggestion>('suggest_mount_path', { storageName, create })
+}
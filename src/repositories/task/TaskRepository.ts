@@ -9,7 +9,21 @@ import { BaseRepository } from '../base/BaseRepository'
 import { RepositoryError, ErrorCode } from '../interfaces/IRepository'
 import { logger } from '../../services/LoggerService'
 import { nmConfig, saveNmConfig } from '../../services/ConfigService'
-import type { TaskEntity, TaskStatus, TaskResult, TaskStats } from '../../type/task/task'
+import type {
+  TaskEntity,
+  TaskStatus,
+  TaskResult,
+  TaskStats,
+  TaskHistory,
+  TaskHistoryQuery,
+  TaskQueueSnapshot,
+  TaskPlan,
+  CheckReport,
+  BisyncStatus,
+  FileVersion,
+  TaskWatchStatus,
+  TaskCheckpoint,
+} from '../../type/task/task'
 import type { TaskListItem } from '../../type/config'
 import {
   TaskSchedulerLike,
@@ -22,6 +36,26 @@ import {
   getRunningTasks as _getRunningTasks,
   getTaskStats as _getTaskStats,
   startScheduler as _startScheduler,
+  getTaskHistory as _getTaskHistory,
+  readTaskRunLog as _readTaskRunLog,
+  getTaskQueue as _getTaskQueue,
+  moveQueuedTask as _moveQueuedTask,
+  cancelQueuedTask as _cancelQueuedTask,
+  dryRunTask as _dryRunTask,
+  listTaskPlans as _listTaskPlans,
+  getTaskPlan as _getTaskPlan,
+  approveTaskPlan as _approveTaskPlan,
+  getCheckReport as _getCheckReport,
+  exportCheckReport as _exportCheckReport,
+  getBisyncState as _getBisyncState,
+  cleanBisyncLocks as _cleanBisyncLocks,
+  previewBisyncResync as _previewBisyncResync,
+  bisyncResync as _bisyncResync,
+  listTaskVersions as _listTaskVersions,
+  restoreTaskVersion as _restoreTaskVersion,
+  listTaskWatches as _listTaskWatches,
+  getTaskCheckpoint as _getTaskCheckpoint,
+  discardTaskCheckpoint as _discardTaskCheckpoint,
 } from './taskHelpers'
 
 const taskLogger = logger.withContext('TaskRepository')
@@ -211,6 +245,86 @@ export class TaskRepository extends BaseRepository<TaskEntity> {
     return _startScheduler()
   }
 
+  async getTaskHistory(query?: TaskHistoryQuery): Promise<TaskHistory[]> {
+    return _getTaskHistory(query)
+  }
+
+  async readTaskRunLog(runId: string, opts?: { maxBytes?: number; search?: string }): Promise<string> {
+    return _readTaskRunLog(runId, opts)
+  }
+
+  async getTaskQueue(): Promise<TaskQueueSnapshot> {
+    return _getTaskQueue()
+  }
+
+  async moveQueuedTask(id: string, index: number): Promise<void> {
+    return _moveQueuedTask(id, index)
+  }
+
+  async cancelQueuedTask(id: string): Promise<void> {
+    return _cancelQueuedTask(id)
+  }
+
+  async dryRunTask(name: string, save = false): Promise<TaskPlan> {
+    return _dryRunTask(name, save)
+  }
+
+  async listTaskPlans(taskName?: string): Promise<TaskPlan[]> {
+    return _listTaskPlans(taskName)
+  }
+
+  async getTaskPlan(id: string): Promise<TaskPlan> {
+    return _getTaskPlan(id)
+  }
+
+  async approveTaskPlan(id: string): Promise<TaskPlan> {
+    return _approveTaskPlan(id)
+  }
+
+  async getCheckReport(runId: string): Promise<CheckReport> {
+    return _getCheckReport(runId)
+  }
+
+  async exportCheckReport(runId: string, outPath: string): Promise<string> {
+    return _exportCheckReport(runId, outPath)
+  }
+
+  async getBisyncState(name: string): Promise<BisyncStatus> {
+    return _getBisyncState(name)
+  }
+
+  async cleanBisyncLocks(name: string): Promise<number> {
+    return _cleanBisyncLocks(name)
+  }
+
+  async previewBisyncResync(name: string): Promise<TaskPlan> {
+    return _previewBisyncResync(name)
+  }
+
+  async bisyncResync(name: string, previewId: string): Promise<TaskHistory> {
+    return _bisyncResync(name, previewId)
+  }
+
+  async listTaskVersions(name: string, path: string): Promise<FileVersion[]> {
+    return _listTaskVersions(name, path)
+  }
+
+  async restoreTaskVersion(name: string, path: string, version: string): Promise<void> {
+    return _restoreTaskVersion(name, path, version)
+  }
+
+  async listTaskWatches(): Promise<TaskWatchStatus[]> {
+    return _listTaskWatches()
+  }
+
+  async getTaskCheckpoint(name: string): Promise<TaskCheckpoint | null> {
+    return _getTaskCheckpoint(name)
+  }
+
+  async discardTaskCheckpoint(name: string): Promise<boolean> {
+    return _discardTaskCheckpoint(name)
+  }
+
   // ==========================================
   // 公开 API（供 Controller 使用）
   // ==========================================
@@ -233,12 +347,14 @@ export class TaskRepository extends BaseRepository<TaskEntity> {
       nmConfig.task.push(taskInfo)
     }
 
+    // 先保存：任务由后端按 config.json 执行，调度前配置必须已写入
+    await saveNmConfig()
+
     if (taskInfo.run.mode !== 'start') {
       const scheduler = await getTaskScheduler()
       await scheduler.addTask(taskInfo)
     }
 
-    await saveNmConfig()
     return true
   }
 
@@ -1,8 +1,23 @@
+import { invoke } from '@tauri-apps/api/core'
 import { RepositoryError, ErrorCode } from '../interfaces/IRepository'
 import { logger } from '../../services/LoggerService'
 import { nmConfig } from '../../services/ConfigService'
 import { runTask } from '../../controller/task/runner'
-import type { TaskEntity, TaskStatus, TaskResult, TaskStats } from '../../type/task/task'
+import type {
+  TaskEntity,
+  TaskStatus,
+  TaskResult,
+  TaskStats,
+  TaskHistory,
+  TaskHistoryQuery,
+  TaskQueueSnapshot,
+  TaskPlan,
+  CheckReport,
+  BisyncStatus,
+  FileVersion,
+  TaskWatchStatus,
+  TaskCheckpoint,
+} from '../../type/task/task'
 import type { TaskListItem } from '../../type/config'
 
 const taskLogger = logger.withContext('TaskRepository')
@@ -30,9 +45,14 @@ export function taskListItemToEntity(task: TaskListItem): TaskEntity {
     target: task.target,
     parameters: task.parameters,
     enable: task.enable,
+    after: task.after,
+    priority: task.priority,
+    preHook: task.preHook,
+    postHook: task.postHook,
+    versioning: task.versioning,
     run: {
       ...task.run,
-      mode: task.run.mode as 'time' | 'interval' | 'start' | 'disposable',
+      mode: task.run.mode as 'time' | 'interval' | 'start' | 'disposable' | 'after' | 'onChange',
     },
     runInfo: task.runInfo,
     status: task.run.runId ? 'running' : 'pending',
@@ -121,3 +141,96 @@ export async function startScheduler(): Promise<void> {
   }
   taskLogger.info('Task scheduler started')
 }
+
+export async function getTaskHistory(query?: TaskHistoryQuery): Promise<TaskHistory[]> {
+  return invoke<TaskHistory[]>('get_task_history', { query })
+}
+
+export async function readTaskRunLog(
+  runId: string,
+  opts?: { maxBytes?: number; search?: string }
+): Promise<string> {
+  return invoke<string>('read_task_run_log', { runId, maxBytes: opts?.maxBytes, search: opts?.search })
+}
+
+export async function getTaskQueue(): Promise<TaskQueueSnapshot> {
+  return invoke<TaskQueueSnapshot>('get_task_queue')
+}
+
+export async function moveQueuedTask(id: string, index: number): Promise<void> {
+  await invoke('move_queued_task', { id, index })
+}
+
+export async function cancelQueuedTask(id: string): Promise<void> {
+  await invoke('cancel_queued_task', { id })
+}
+
+export async function dryRunTask(name: string, save = false): Promise<TaskPlan> {
+  return invoke<TaskPlan>('dry_run_task', { name, save })
+}
+
+export async function listTaskPlans(taskName?: string): Promise<TaskPlan[]> {
+  return invoke<TaskPlan[]>('list_task_plans', { taskName })
+}
+
+export async function getTaskPlan(id: string): Promise<TaskPlan> {
+  return invoke<TaskPlan>('get_task_plan', { id })
+}
+
+export async function approveTaskPlan(id: string): Promise<TaskPlan> {
+  return invoke<TaskPlan>('approve_task_plan', { id })
+}
+
+export async function getCheckReport(runId: string): Promise<CheckReport> {
+  return invoke<CheckReport>('get_check_report', { runId })
+}
+
+/** 导出校验报告，格式由扩展名（.csv / .json）决定 */
+export async function exportCheckReport(runId: string, outPath: string): Promise<string> {
+  return invoke<string>('export_check_report', { runId, outPath })
+}
+
+export async function getBisyncState(name: string): Promise<BisyncStatus> {
+  return invoke<BisyncStatus>('get_bisync_state', { name })
+}
+
+export async function cleanBisyncLocks(name: string): Promise<number> {
+  return invoke<number>('clean_bisync_locks', { name })
+}
+
+/** 预览 resync，返回的计划 ID 需传给 bisyncResync */
+export async function previewBisyncResync(name: string): Promise<TaskPlan> {
+  return invoke<TaskPlan>('preview_bisync_resync', { name })
+}
+
+export async function bisyncResync(name: string, previewId: string): Promise<TaskHistory> {
+  return invoke<TaskHistory>('bisync_resync', { name, previewId })
+}
+
+/** 列出目标中某个文件（相对任务目标路径）的历史版本 */
+export async function listTaskVersions(name: string, path: string): Promise<FileVersion[]> {
+  return invoke<FileVersion[]>('list_task_versions', { name, path })
+}
+
+export async function restoreTaskVersion(
+  name: string,
+  path: string,
+  version: string
+): Promise<void> {
+  return invoke<void>('restore_task_version', { name, path, version })
+}
+
+/** 查看 onChange 任务的监听状态 */
+export async function listTaskWatches(): Promise<TaskWatchStatus[]> {
+  return invoke<TaskWatchStatus[]>('list_task_watches')
+}
+
+/** 查看断点续传进度，没有检查点时返回 null */
+export async function getTaskCheckpoint(name: string): Promise<TaskCheckpoint | null> {
+  return invoke<TaskCheckpoint | null>('get_task_checkpoint', { name })
+}
+
+/** 丢弃检查点，下次运行从头开始 */
+export async function discardTaskCheckpoint(name: string): Promise<boolean> {
+  return invoke<boolean>('discard_task_checkpoint', { name })
+}
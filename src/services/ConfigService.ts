@@ -157,13 +157,6 @@ class ConfigService {
       const configData = await invoke<Partial<NMConfig>>('get_config')
       this.config = mergeObjects(this.config, configData)
       
-      // 解码框架密码（向后兼容明文密码）
-      if (this.config.framework?.rclone?.password) {
-        this.config.framework.rclone.password = decodePassword(this.config.framework.rclone.password)
-      }
-      if (this.config.framework?.openlist?.password) {
-        this.config.framework.openlist.password = decodePassword(this.config.framework.openlist.password)
-      }
       // 解码代理密码
       if (this.config.settings?.proxy?.password) {
         this.config.settings.proxy.password = decodePassword(this.config.settings.proxy.password)
@@ -181,14 +174,8 @@ class ConfigService {
    */
   async saveConfig(): Promise<void> {
     try {
-      // 创建配置副本，编码框架密码后再保存
+      // 创建配置副本，编码代理密码后再保存（框架凭据只保存在后端）
       const configToSave = JSON.parse(JSON.stringify(this.config)) as NMConfig
-      if (configToSave.framework?.rclone?.password) {
-        configToSave.framework.rclone.password = encodePassword(configToSave.framework.rclone.password)
-      }
-      if (configToSave.framework?.openlist?.password) {
-        configToSave.framework.openlist.password = encodePassword(configToSave.framework.openlist.password)
-      }
       // 编码代理密码
       if (configToSave.settings?.proxy?.password) {
         configToSave.settings.proxy.password = encodePassword(configToSave.settings.proxy.password)
@@ -31,11 +31,11 @@ export const roConfig = {
     task: {
       runMode: {
         defIndex: 0,
-        select: ['start', 'time', 'interval', 'disposable'] as const,
+        select: ['start', 'time', 'interval', 'disposable', 'after', 'onChange'] as const,
       },
       taskType: {
         defIndex: 3,
-        select: ['copy', 'move', 'delete', 'sync', 'bisync'] as const,
+        select: ['copy', 'move', 'delete', 'sync', 'bisync', 'check'] as const,
       },
       dateMultiplier: {
         defIndex: 0,
@@ -99,15 +99,14 @@ export const createDefaultConfig = (): NMConfig => ({
       transferDir: undefined,
     },
   },
+  // 框架密码由后端生成并保存，不下发到前端
   framework: {
     rclone: {
       user: randomString(32),
-      password: randomString(128),
       extraArgs: '',
     },
     openlist: {
       user: 'admin',
-      password: randomString(16),
       extraArgs: '',
     },
   },
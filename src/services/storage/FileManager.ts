@@ -1,7 +1,7 @@
 import { rclone_api_post } from '../../utils/rclone/request'
 import { FileInfo } from '../../type/rclone/rcloneInfo'
 import { RequestOptions } from '@arco-design/web-react/es/Upload'
-import { getRcloneApiHeaders } from '../../utils/rclone/request'
+import { relayUpload } from '../../utils/tauri/relay'
 import { rcloneInfo } from '../../services/rclone'
 import { searchStorage, convertStoragePath, formatPathRclone } from './StorageManager'
 import { isRcloneFileItem } from '../../utils/validators/rcloneValidators'
@@ -207,21 +207,19 @@ async function mkDir(storageName: string, path: string, refreshCallback?: Refres
 const uploadFileRequest = (option: RequestOptions, storageName: string, path: string) => {
   const { onProgress, onError, onSuccess, file } = option
 
-  const formData = new FormData()
-  formData.append('file', file)
+  const dir = convertStoragePath(storageName, path, true, true, undefined)
 
-  const xhr = new XMLHttpRequest()
-
-  xhr.upload.onprogress = ({ lengthComputable, loaded, total }) => {
-    if (lengthComputable) {
-      const progress = Math.round((loaded / total) * 100)
-      logger.debug(`Upload progress: ${progress}%`, 'FileManager', { loaded, total, progress })
+  // 经后端转发上传，rc 凭据不暴露给 webview
+  relayUpload(
+    file,
+    convertStoragePath(storageName, undefined, undefined, undefined, true),
+    dir ? `${dir}/${file.name}` : file.name,
+    progress => {
+      logger.debug(`Upload progress: ${progress}%`, 'FileManager', { progress })
       onProgress(progress)
     }
-  }
-
-  xhr.onload = async () => {
-    if (xhr.status === 200) {
+  )
+    .then(async () => {
       // 上传成功后刷新 VFS 缓存，避免重复上传
       try {
         const parentPath = path.includes('/') ? path.substring(0, path.lastIndexOf('/')) : '/'
@@ -232,20 +230,11 @@ const uploadFileRequest = (option: RequestOptions, storageName: string, path: st
         logger.warn('Failed to refresh VFS cache after upload', 'FileManager', { error: e })
       }
       onSuccess()
-    } else {
-      onError(xhr)
-    }
-  }
-
-  xhr.onerror = () => onError(xhr)
-
-  xhr.open(
-    'POST',
-    `${rcloneInfo.endpoint.url}/operations/uploadfile?fs=${convertStoragePath(storageName, undefined, undefined, undefined, true)}&remote=${convertStoragePath(storageName, path, true, true, undefined)}`,
-    true
-  )
-  xhr.setRequestHeader('Authorization', getRcloneApiHeaders().Authorization)
-  xhr.send(formData)
+    })
+    .catch(e => {
+      logger.error('Upload failed', e instanceof Error ? e : new Error(String(e)), 'FileManager')
+      onError(e instanceof Error ? e : new Error(String(e)))
+    })
 }
 
 export {
@@ -0,0 +1,53 @@
+/**
+ * rclone.conf Export Service
+ * 把 NetMount 的存储导出为独立的 rclone.conf，供脚本或服务器上的 rclone 使用
+ *
+ * OpenList 存储以及引用了 OpenList 存储的 remote 无法在 NetMount 之外使用，
+ * 不会写入文件，由后端在结果中列出。
+ */
+
+import { invoke } from '@tauri-apps/api/core'
+import { logger } from '../LoggerService'
+
+// 密钥的处理方式：保持混淆、还原为明文、移除
+type RcloneConfSecretPolicy = 'keep' | 'reveal' | 'strip'
+
+interface RcloneConfExportReport {
+  path: string
+  exported: string[]
+  unsupported: Array<{ name: string; reason: 'openlist' | 'dependsOnOpenlist' }>
+  // 被移除的选项，形如 `remote.key`
+  stripped: string[]
+  encrypted: boolean
+}
+
+/**
+ * 导出存储到 rclone.conf
+ * @param path - 输出文件路径
+ * @param names - 要导出的存储名称
+ * @param password - 非空时加密输出
+ */
+async function exportRcloneConf(
+  path: string,
+  names: string[],
+  secrets: RcloneConfSecretPolicy,
+  password?: string
+): Promise<RcloneConfExportReport> {
+  const report = await invoke<RcloneConfExportReport>('export_rclone_conf', {
+    options: {
+      path,
+      names,
+      secrets,
+      password: password || null,
+    },
+  })
+
+  logger.info(
+    `rclone.conf export completed: ${report.exported.length} exported, ${report.unsupported.length} unsupported`,
+    'RcloneConfExport'
+  )
+
+  return report
+}
+
+export { exportRcloneConf, type RcloneConfSecretPolicy, type RcloneConfExportReport }
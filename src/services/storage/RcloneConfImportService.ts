@@ -0,0 +1,98 @@
+/**
+ * rclone.conf Import Service
+ * 从用户已有的 rclone.conf 导入 remote
+ *
+ * 解析（含加密配置的解密）与导入都在后端完成，前端只拿到 remote 名称、类型与选项名，
+ * 选项的值（令牌、密码等）不会经过 webview。
+ */
+
+import { invoke } from '@tauri-apps/api/core'
+import { rcloneInfo } from '../rclone'
+import { reupStorage } from './StorageManager'
+import { logger } from '../LoggerService'
+
+// 名称冲突时的处理方式
+type RcloneConfConflictPolicy = 'rename' | 'skip'
+
+// 预览中的 remote
+interface RcloneConfRemote {
+  name: string
+  type: string
+  keys: string[]
+  conflict: boolean
+  renameTo: string
+  references: string[]
+}
+
+interface RcloneConfPreview {
+  path: string
+  encrypted: boolean
+  // 配置已加密，需要密码
+  locked: boolean
+  remotes: RcloneConfRemote[]
+}
+
+interface RcloneConfImportReport {
+  imported: Array<{ name: string; importedAs: string }>
+  skipped: string[]
+  failed: Array<{ name: string; error: string }>
+}
+
+// 已有的存储名称（含 OpenList 存储），导入时同样视为冲突
+function existingStorageNames(): string[] {
+  return rcloneInfo.storageList.map(storage => storage.name)
+}
+
+/**
+ * 预览 rclone.conf 中的 remote
+ * @param path - 配置文件路径，为空时使用 rclone 的默认位置
+ * @param password - 加密配置的密码
+ */
+async function previewRcloneConf(path?: string, password?: string): Promise<RcloneConfPreview> {
+  return await invoke<RcloneConfPreview>('preview_rclone_conf', {
+    path: path || null,
+    password: password || null,
+    reserved: existingStorageNames(),
+  })
+}
+
+/**
+ * 导入选中的 remote，完成后刷新存储列表
+ * @param names - 要导入的 remote 名称
+ */
+async function importRcloneConf(
+  names: string[],
+  onConflict: RcloneConfConflictPolicy,
+  path?: string,
+  password?: string
+): Promise<RcloneConfImportReport> {
+  const report = await invoke<RcloneConfImportReport>('import_rclone_conf', {
+    options: {
+      path: path || null,
+      password: password || null,
+      names,
+      onConflict,
+      reserved: existingStorageNames(),
+    },
+  })
+
+  if (report.imported.length > 0) {
+    await reupStorage()
+  }
+
+  logger.info(
+    `rclone.conf import completed: ${report.imported.length} imported, ${report.skipped.length} skipped, ${report.failed.length} failed`,
+    'RcloneConfImport'
+  )
+
+  return report
+}
+
+export {
+  previewRcloneConf,
+  importRcloneConf,
+  type RcloneConfConflictPolicy,
+  type RcloneConfRemote,
+  type RcloneConfPreview,
+  type RcloneConfImportReport,
+}
@@ -59,3 +59,20 @@ export {
   type AlistStorageItem,
   type ImportResult,
 } from './AlistImportService'
+
+// rclone.conf Import Service - Import remotes from an existing rclone.conf
+export {
+  previewRcloneConf,
+  importRcloneConf,
+  type RcloneConfConflictPolicy,
+  type RcloneConfRemote,
+  type RcloneConfPreview,
+  type RcloneConfImportReport,
+} from './RcloneConfImportService'
+
+// rclone.conf Export Service - Export storages as a standalone rclone.conf
+export {
+  exportRcloneConf,
+  type RcloneConfSecretPolicy,
+  type RcloneConfExportReport,
+} from './RcloneConfExportService'
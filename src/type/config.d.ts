@@ -25,6 +25,28 @@ interface NMConfig {
       lockOnSleep?: boolean     // 休眠时锁定
       idleTimeoutMinutes?: number // 空闲超时锁定（分钟），0或undefined表示禁用
     }
+    taskHistory?: {
+      maxRecords?: number // 最多保留的运行记录数
+      maxAgeDays?: number // 运行记录最长保留天数，0 表示不按时间清理
+    }
+    taskConcurrency?: {
+      maxConcurrent?: number // 最多同时运行的任务数，默认 3，0 表示不限制
+      perStorageDefault?: number // 单个存储默认并发上限，0 表示不限制
+      perStorage?: Record<string, number> // 按存储名单独设置的并发上限
+    }
+    taskRunLog?: {
+      maxAgeDays?: number // 运行日志最长保留天数，0 表示不按时间清理
+      maxTotalMb?: number // 运行日志总大小上限（MB），0 表示不限制
+    }
+    mountBase?: string // 推荐挂载目录的基础路径，默认 ~/NetMount，可使用 $XDG_RUNTIME_DIR 开头的路径
+    exitUploadWaitSecs?: number // 退出前等待写回缓存上传的最长时间（秒），默认 120，0 表示不等待
+    rcUnixSocket?: boolean // rclone rc 是否监听数据目录中的 Unix 套接字（仅 Linux），默认开启；关闭时只监听 127.0.0.1
+    rotateCredentials?: boolean // 每次启动时重新生成 rclone rc 凭据（只保存在内存中）并轮换 OpenList 管理员密码，默认关闭
+    mountHealth?: {
+      enabled?: boolean // 是否定期检查挂载点并重新挂载失效的挂载，默认开启
+      intervalSecs?: number // 检查间隔（秒），默认 30，最小 5
+      maxRetries?: number // 连续重新挂载的最大次数，默认 3
+    }
     proxy?: {
       type: 'no_proxy' | 'http' | 'socks5'  // 代理类型
       host?: string       // 代理主机地址
@@ -37,12 +59,10 @@ interface NMConfig {
   framework: {
     rclone: {
       user: string
-      password: string
       extraArgs?: string
     }
     openlist: {
       user: string
-      password: string
       extraArgs?: string
     }
   }
@@ -57,7 +77,7 @@ interface MountListItem {
 
 interface TaskListItem {
   name: string
-  taskType: 'copy' | 'move' | 'delete' | 'sync' | 'bisync' | string
+  taskType: 'copy' | 'move' | 'delete' | 'sync' | 'bisync' | 'check' | string
   source: {
     storageName: string
     path: string
@@ -68,9 +88,14 @@ interface TaskListItem {
   }
   parameters?: ParametersType
   enable: boolean
+  after?: TaskDependency[] //前置任务，满足条件时在其后执行
+  priority?: number //排队优先级，越大越先运行，默认 0
+  preHook?: TaskHook //任务运行前执行的本地命令
+  postHook?: TaskHook //任务运行后执行的本地命令
+  versioning?: TaskVersioning //覆盖或删除目标文件时保留旧版本
   run: {
     runId?: number //任务id,setTimeout或setInterval的返回值
-    mode: 'time' | 'interval' | 'start' | 'disposable' | string //start：软件启动时执行，time:定时执行，interval:间隔执行 , disposable:一次性执行(执行后删除任务)
+    mode: 'time' | 'interval' | 'start' | 'disposable' | 'after' | 'onChange' | string //start：软件启动时执行，time:定时执行，interval:间隔执行 , disposable:一次性执行(执行后删除任务)，after:仅由前置任务触发，onChange:本地源目录变更时执行
     time: {
       intervalDays: number //间隔天数
       h: number //小时
@@ -78,6 +103,7 @@ interface TaskListItem {
       s: number //秒
     }
     interval?: number //周期执行，单位ms
+    debounce?: number //onChange 模式合并变更的时间（秒），默认 5
   }
   runInfo: {
     error?: boolean
@@ -85,6 +111,25 @@ interface TaskListItem {
   }
 }
 
+interface TaskDependency {
+  task: string //前置任务名称
+  on?: 'success' | 'failure' | 'always' //触发条件，默认 success
+}
+
+interface TaskHook {
+  command: string
+  args?: string[]
+  timeout?: number //超时（秒），默认 300
+  abortOnFailure?: boolean //失败时前置钩子跳过任务、后置钩子将任务标记为失败
+}
+
+interface TaskVersioning {
+  enabled: boolean
+  mode?: 'dir' | 'suffix' //dir：移入目标下的 .netmount-versions/<时间戳>/，suffix：原地加时间戳后缀
+  keepCount?: number //最多保留份数，0 不限制
+  keepDays?: number //最长保留天数，0 不限制
+}
+
 interface OSInfo {
   arch: Arch | 'unknown'
   osType: OsType | 'unknown'
@@ -93,4 +138,4 @@ interface OSInfo {
   osVersion: string
 }
 
-export { NMConfig, MountListItem, TaskListItem, OSInfo }
+export { NMConfig, MountListItem, TaskListItem, TaskDependency, TaskHook, TaskVersioning, OSInfo }
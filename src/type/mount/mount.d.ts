@@ -80,4 +80,130 @@ export interface AutoMountConfig {
   retryDelay: number
   /** 是否在应用启动时自动挂载 */
   mountOnStartup: boolean
-}
\ No newline at end of file
+}
+/**
+ * 挂载健康状态（后端监测）
+ * - healthy: 正常
+ * - unresponsive: 访问超时，可能只是网络较慢
+ * - stale: 已失效，正在恢复
+ * - remounted: 已重新挂载
+ * - failed: 重新挂载次数用尽
+ */
+export type MountHealthState = 'healthy' | 'unresponsive' | 'stale' | 'remounted' | 'failed'
+
+/**
+ * 单个挂载点的健康状态
+ */
+export interface MountHealth {
+  /** 挂载路径（与配置中的 mountPath 一致） */
+  mountPath: string
+  /** 存储名称 */
+  storageName: string
+  state: MountHealthState
+  /** 连续重新挂载次数，检查正常后清零 */
+  retries: number
+  /** 最近一次失效或恢复失败的原因 */
+  lastError?: string
+  /** 检查时间（毫秒时间戳） */
+  checkedAt: number
+}
+
+/**
+ * FUSE 环境检查项（Linux）
+ * - device: /dev/fuse
+ * - kernelModule: fuse 内核模块
+ * - fusermount: fusermount3/fusermount 及其 setuid 位
+ * - allowOther: 请求 AllowOther 时 /etc/fuse.conf 中的 user_allow_other
+ */
+export interface FuseCheck {
+  id: 'device' | 'kernelModule' | 'fusermount' | 'allowOther' | string
+  status: 'ok' | 'warning' | 'error' | 'skipped'
+  message: string
+  /** 修复建议 */
+  hint?: string
+}
+
+/**
+ * FUSE 环境检查结果，非 Linux 系统始终就绪且没有检查项
+ */
+export interface FuseReadiness {
+  /** 没有 error 级别的检查项 */
+  ready: boolean
+  /** 找到的 fusermount 路径 */
+  fusermount?: string
+  checks: FuseCheck[]
+}
+
+/**
+ * 挂载点检查发现的问题
+ * code: duplicate | nested | alreadyMounted | insideMount | notFound | createFailed |
+ *       exists | notDirectory | notEmpty | notOwner | notWritable | parentNotWritable
+ */
+export interface MountPointProblem {
+  code: string
+  /** error 表示挂载会失败，warning 可以继续挂载 */
+  severity: 'error' | 'warning'
+  message: string
+  /** 冲突的挂载路径 */
+  conflict?: string
+}
+
+/**
+ * 后端挂载点检查结果
+ */
+export interface MountPointCheck {
+  /** 展开 ~ 后的路径 */
+  path: string
+  /** 没有 error 级别的问题 */
+  valid: boolean
+  /** 本次检查创建了目录 */
+  created: boolean
+  problems: MountPointProblem[]
+}
+
+/**
+ * 挂载点检查选项
+ */
+export interface MountPointCheckOptions {
+  /** 目录不存在时创建 */
+  create?: boolean
+  /** 挂载参数启用了 AllowNonEmpty */
+  allowNonEmpty?: boolean
+  /** 编辑挂载时原来的挂载路径，不与自身比较 */
+  ignoreMountPath?: string
+}
+
+/**
+ * 退出前等待写回缓存上传的进度（mount-exit-progress 事件）
+ */
+export interface MountExitProgress {
+  /** 正在上传与排队的文件数，0 表示已完成 */
+  pendingUploads: number
+  /** 距离放弃等待的剩余秒数 */
+  remainingSecs: number
+}
+
+/**
+ * 挂载就绪结果（mount_and_wait）
+ */
+export interface MountReady {
+  mountPath: string
+  /** mount/mount 请求耗时（毫秒） */
+  mountMs: number
+  /** 请求返回到挂载点可访问的耗时（毫秒） */
+  readyMs: number
+  /** 总耗时（毫秒） */
+  latencyMs: number
+}
+
+/**
+ * 推荐的挂载目录（suggest_mount_path）
+ */
+export interface MountPathSuggestion {
+  /** 保存到配置中的路径（基础目录以 ~ 开头时保留 ~） */
+  path: string
+  /** 展开后的本地路径 */
+  local: string
+  /** 本次创建了目录 */
+  created: boolean
+}
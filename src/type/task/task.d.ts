@@ -2,6 +2,7 @@
  * 任务相关类型定义
  */
 import { ParametersType } from '../defaults'
+import type { TaskDependency, TaskHook, TaskVersioning } from '../config'
 
 /**
  * 任务实体
@@ -32,12 +33,22 @@ export interface TaskEntity {
   parameters?: ParametersType
   /** 是否启用该任务 */
   enable: boolean
+  /** 前置任务 */
+  after?: TaskDependency[]
+  /** 排队优先级（越大越先运行） */
+  priority?: number
+  /** 运行前钩子（环境变量 NETMOUNT_TASK_* 提供任务上下文） */
+  preHook?: TaskHook
+  /** 运行后钩子 */
+  postHook?: TaskHook
+  /** 版本保留 */
+  versioning?: TaskVersioning
   /** 调度配置 */
   run: {
     /** 运行ID（任务正在运行时） */
     runId?: number
     /** 调度模式 */
-    mode: 'time' | 'interval' | 'start' | 'disposable'
+    mode: 'time' | 'interval' | 'start' | 'disposable' | 'after' | 'onChange'
     /** 时间配置 */
     time: {
       /** 间隔天数 */
@@ -51,6 +62,8 @@ export interface TaskEntity {
     }
     /** 间隔秒数（用于interval模式） */
     interval?: number
+    /** 合并变更的时间（秒，用于onChange模式） */
+    debounce?: number
   }
   /** 运行时信息 */
   runInfo?: {
@@ -72,9 +85,10 @@ export interface TaskEntity {
  * - sync: 同步（使目标与源一致）
  * - delete: 删除文件
  * - bisync: 双向同步
+ * - check: 校验源与目标是否一致
  */
 // eslint-disable-next-line @typescript-eslint/ban-types
-export type TaskType = 'copy' | 'move' | 'sync' | 'delete' | 'bisync' | (string & {})
+export type TaskType = 'copy' | 'move' | 'sync' | 'delete' | 'bisync' | 'check' | (string & {})
 
 /**
  * 任务状态
@@ -139,7 +153,7 @@ export interface TaskOptions {
  */
 export interface ScheduleConfig {
   /** 调度模式 */
-  mode: 'time' | 'interval' | 'start' | 'disposable'
+  mode: 'time' | 'interval' | 'start' | 'disposable' | 'after' | 'onChange'
   /** 时间配置 */
   time: {
     /** 间隔天数 */
@@ -212,14 +226,231 @@ export interface TaskHistory {
   type: TaskType
   /** 执行状态 */
   status: TaskStatus
-  /** 开始时间 */
-  startedAt: Date
-  /** 完成时间 */
-  completedAt: Date
+  /** 开始时间（Unix 毫秒） */
+  startedAt: number
+  /** 完成时间（Unix 毫秒） */
+  completedAt: number
+  /** rclone 异步任务 ID */
+  jobId?: number
+  /** 运行日志文件名（位于 log/task-runs/） */
+  logFile?: string
+  /** 所属任务链ID */
+  chainId?: string
   /** 执行结果 */
   result?: TaskResult
 }
 
+/**
+ * 任务历史查询条件
+ */
+export interface TaskHistoryQuery {
+  /** 任务名称 */
+  taskName?: string
+  /** 执行状态 */
+  status?: TaskStatus
+  /** 任务链ID */
+  chainId?: string
+  /** 开始时间下限（Unix 毫秒） */
+  from?: number
+  /** 开始时间上限（Unix 毫秒） */
+  to?: number
+  /** 最多返回条数 */
+  limit?: number
+}
+
+/**
+ * 任务链运行结果
+ * 运行任务时连同满足条件的下游任务一起执行
+ */
+export interface ChainRun {
+  /** 任务链ID */
+  id: string
+  /** 触发运行的任务名称 */
+  root: string
+  /** 整体状态（任一任务失败即为 failed；coalesced/cancelled 表示已在队列中或排队时被取消） */
+  status: 'success' | 'failed' | 'coalesced' | 'cancelled'
+  /** 开始时间（Unix 毫秒） */
+  startedAt: number
+  /** 完成时间（Unix 毫秒） */
+  completedAt: number
+  /** 各任务的运行记录，第一条为触发任务 */
+  runs: TaskHistory[]
+  /** 被跳过的下游任务 */
+  skipped: string[]
+}
+
+/**
+ * 校验任务报告
+ */
+export interface CheckReport {
+  /** 对应的运行ID */
+  runId: string
+  /** 任务名称 */
+  taskName: string
+  /** 生成时间（Unix 毫秒） */
+  createdAt: number
+  /** 校验方式 */
+  mode: 'check' | 'cryptcheck'
+  /** 是否下载内容比对 */
+  download: boolean
+  /** 一致的文件 */
+  match: string[]
+  /** 不同的文件 */
+  differ: string[]
+  /** 仅存在于目标中的文件 */
+  missingOnSrc: string[]
+  /** 仅存在于源中的文件 */
+  missingOnDst: string[]
+  /** 出错的文件 */
+  errors: string[]
+}
+
+/**
+ * 双向同步任务的 workdir 状态
+ */
+export interface BisyncStatus {
+  /** 任务名称 */
+  taskName: string
+  /** workdir 路径 */
+  workdir: string
+  /** 是否已有上次同步的列表 */
+  initialized: boolean
+  /** 锁文件 */
+  locks: { file: string; pid?: number; held: boolean }[]
+  /** 是否需要 resync */
+  needsResync: boolean
+  /** 最近一次错误 */
+  lastError?: string
+  /** 最近运行时间（Unix 毫秒） */
+  lastRunAt?: number
+  /** 最近 resync 时间（Unix 毫秒） */
+  lastResyncAt?: number
+}
+
+/**
+ * 断点续传进度（包含此前中断运行已完成的部分）
+ */
+export interface TaskCheckpoint {
+  /** 任务名称 */
+  taskName: string
+  /** 检查点创建时间（Unix 毫秒） */
+  createdAt: number
+  /** 最近更新时间（Unix 毫秒） */
+  updatedAt: number
+  /** 文件总数 */
+  totalFiles: number
+  /** 总字节数 */
+  totalBytes: number
+  /** 已完成文件数 */
+  completedFiles: number
+  /** 已完成字节数 */
+  completedBytes: number
+}
+
+/**
+ * 变更监听状态（onChange 模式）
+ */
+export interface TaskWatchStatus {
+  /** 任务名称 */
+  taskName: string
+  /** 监听的本地目录 */
+  path: string
+  /** inotify：系统通知；poll：轮询 */
+  backend: 'inotify' | 'poll'
+  /** 退回轮询的原因（如 inotify 监听数达到上限） */
+  degraded?: string
+  /** 等待触发的变更数 */
+  pending: number
+  /** 最近触发时间（Unix 毫秒） */
+  lastTriggeredAt?: number
+  /** 最近一次错误 */
+  lastError?: string
+}
+
+/**
+ * 文件的历史版本
+ */
+export interface FileVersion {
+  /** 版本时间戳（YYYYMMDD-HHMMSS，UTC） */
+  version: string
+  /** 创建时间（Unix 毫秒） */
+  createdAt: number
+  /** 版本文件在目标存储中的路径 */
+  path: string
+  /** 文件大小（字节） */
+  size: number
+  /** 修改时间 */
+  modTime: string
+}
+
+/**
+ * 运行计划中的操作
+ * - copy: 目标中不存在，将被复制
+ * - update: 目标中存在但不同，将被覆盖
+ * - delete: 仅存在于目标中，将被删除（sync）
+ * - skip: 两端相同，或仅存在于目标中但不会删除
+ */
+export type TaskPlanAction = 'copy' | 'update' | 'delete' | 'skip'
+
+/**
+ * 运行计划（dry-run 结果）
+ */
+export interface TaskPlan {
+  /** 计划ID */
+  id: string
+  /** 任务名称 */
+  taskName: string
+  /** 任务类型 */
+  taskType: TaskType
+  /** 生成时间（Unix 毫秒） */
+  createdAt: number
+  /** 文件清单（列表接口中为空） */
+  entries: {
+    path: string
+    action: TaskPlanAction
+    /** 文件大小（字节） */
+    size?: number
+  }[]
+  /** 各操作的文件数与字节数 */
+  totals: Record<TaskPlanAction, { count: number; bytes: number }>
+  /** dry-run 期间的错误 */
+  errors?: string[]
+  /** 确认时间（Unix 毫秒） */
+  approvedAt?: number
+  /** 生成计划时的 rclone 参数摘要 */
+  fingerprint?: string
+  /** 被运行使用的时间（Unix 毫秒），使用后不能再次运行 */
+  consumedAt?: number
+}
+
+/**
+ * 运行队列中的一项
+ */
+export interface TaskQueueEntry {
+  /** 排队项ID */
+  id: string
+  /** 任务名称 */
+  taskName: string
+  /** 任务涉及的存储 */
+  storages: string[]
+  /** 优先级（越大越先运行） */
+  priority: number
+  /** 入队时间（Unix 毫秒） */
+  enqueuedAt: number
+  /** 开始运行时间（Unix 毫秒，排队中为空） */
+  startedAt?: number
+}
+
+/**
+ * 运行队列快照
+ */
+export interface TaskQueueSnapshot {
+  /** 运行中的任务 */
+  running: TaskQueueEntry[]
+  /** 排队中的任务（按启动顺序） */
+  waiting: TaskQueueEntry[]
+}
+
 /**
  * 任务统计信息
  * 任务执行的整体统计
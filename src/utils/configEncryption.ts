@@ -0,0 +1,33 @@
+import { invoke } from '@tauri-apps/api/core'
+
+/** config.json 敏感字段的加密状态 */
+interface ConfigEncryption {
+  // 密钥来源：数据目录中的 config.key 或用户口令
+  keySource: 'keyFile' | 'passphrase'
+  // 口令模式下尚未输入口令，敏感字段仍是密文
+  locked: boolean
+}
+
+async function getConfigEncryption(): Promise<ConfigEncryption> {
+  return await invoke<ConfigEncryption>('get_config_encryption')
+}
+
+/**
+ * 输入口令，后端解密内存中的配置
+ * @param passphrase - 配置口令，错误时抛出异常
+ */
+async function unlockConfig(passphrase: string): Promise<void> {
+  await invoke('unlock_config', { passphrase })
+}
+
+/**
+ * 设置或修改配置口令，需先解锁
+ * @param passphrase - 新口令，为空时改回使用密钥文件
+ */
+async function setConfigPassphrase(passphrase: string): Promise<ConfigEncryption> {
+  return await invoke<ConfigEncryption>('set_config_passphrase', {
+    passphrase: passphrase || null,
+  })
+}
+
+export { getConfigEncryption, unlockConfig, setConfigPassphrase, type ConfigEncryption }
@@ -3,6 +3,7 @@ import * as shell from '@tauri-apps/plugin-shell'
 import { runCmd } from '../tauri/cmd'
 import { logger } from '../../services/LoggerService'
 import { invoke } from '@tauri-apps/api/core'
+import type { FuseReadiness } from '../../type/mount/mount'
 
 /**
  * 下载文件
@@ -26,6 +27,15 @@ export async function getWinFspInstallState(): Promise<boolean> {
   return (await invoke('get_winfsp_install_state')) as boolean
 }
 
+/**
+ * 检查 Linux FUSE 挂载环境
+ * @param allowOther - 是否有挂载启用了 AllowOther
+ * @returns 各检查项及修复建议
+ */
+export async function getFuseReadiness(allowOther?: boolean): Promise<FuseReadiness> {
+  return await invoke<FuseReadiness>('get_fuse_readiness', { allowOther })
+}
+
 /**
  * 安装 WinFsp
  * @returns 安装是否成功
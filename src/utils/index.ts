@@ -7,6 +7,7 @@ export { randomString, compareVersions } from './string/index'
 // File utilities
 export {
   getWinFspInstallState,
+  getFuseReadiness,
   installWinFsp,
   openWinFspInstaller,
   openUrlInBrowser,
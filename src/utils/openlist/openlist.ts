@@ -1,13 +1,12 @@
 import { invoke } from '@tauri-apps/api/core'
 import { openlistDataDir } from './paths'
 import { openlistInfo } from '../../services/openlist'
-import { createStorage } from '../../services/storage/StorageCreationService'
+import { reupStorage } from '../../services/storage/StorageManager'
 import { nmConfig } from '../../services/ConfigService'
-import { rclone_api_post } from '../rclone/request'
 import { mergeObjects } from '../index'
 import { openlist_api_get, openlist_api_post } from './request'
 import { logger } from '../../services/LoggerService'
-import { getOpenlistToken, setOpenlistPass } from './openlistAuth'
+import { loginOpenlist, setOpenlistPass } from './openlistAuth'
 
 type OpenlistConfig = typeof openlistInfo.openlistConfig
 type OpenlistConfigPartial = Partial<OpenlistConfig>
@@ -161,70 +160,19 @@ async function modifyOpenlistConfig(
 }
 
 async function addOpenlistInRclone() {
-  const webdavUrl = openlistInfo.endpoint.url + '/dav'
-  const username = nmConfig.framework.openlist.user
-  const password = nmConfig.framework.openlist.password
-  const storageName = openlistInfo.markInRclone
-
-  logger.info('OpenList WebDAV Configuration', 'OpenlistUtils', {
-    storageName,
-    webdavUrl,
-    username
+  logger.info('Adding OpenList WebDAV storage to rclone', 'OpenlistUtils', {
+    storageName: openlistInfo.markInRclone,
+    username: nmConfig.framework.openlist.user,
   })
-  logger.info('Note: WebDAV password is the same as Web UI login password', 'OpenlistUtils')
 
-  // 可选：探测 WebDAV 端点以提供诊断信息
-  try {
-    const probeRes = await fetch(webdavUrl, {
-      method: 'OPTIONS',
-      headers: {
-        Authorization: 'Basic ' + btoa(username + ':' + password),
-      },
-    })
-    logger.info('WebDAV probe HTTP status', 'OpenlistUtils', { status: probeRes.status })
-    if (probeRes.status === 401) {
-      logger.warn('WebDAV returned 401 - Please check if user has WebDAV Read/Management permissions enabled', 'OpenlistUtils')
-    } else if (probeRes.status === 403) {
-      logger.warn('WebDAV returned 403 - Please check if user has necessary file permissions', 'OpenlistUtils')
-    } else if (probeRes.ok || probeRes.status === 207) {
-      logger.info('WebDAV endpoint appears to be accessible', 'OpenlistUtils')
-    }
-  } catch (probeError) {
-    logger.warn('WebDAV probe failed (this is normal if server is still starting)', 'OpenlistUtils', { error: probeError })
-  }
+  // 后端删除旧配置（避免端口不一致问题）后重新创建，WebDAV 密码即 OpenList 管理员密码，由后端填入
+  await invoke('relay_add_openlist_remote')
+  await reupStorage()
   logger.info('WebDAV configuration complete', 'OpenlistUtils')
-
-  // 先删除可能存在的旧配置（避免端口不一致问题）
-  logger.info('Deleting old rclone storage config if exists...', 'OpenlistUtils')
-  try {
-    await rclone_api_post('/config/delete', { name: storageName }, true)
-    logger.info('Old storage config deleted (or did not exist)', 'OpenlistUtils')
-  } catch (e) {
-    // 配置可能不存在，忽略错误
-    logger.info('No old config to delete or delete failed', 'OpenlistUtils', { error: e })
-  }
-
-  // 短暂延迟确保删除生效
-  await new Promise(resolve => setTimeout(resolve, 500))
-
-  logger.info('Creating new rclone storage with updated WebDAV URL...', 'OpenlistUtils')
-  // 使用 opt.obscure = true 告诉 rclone 密码是明文需要混淆
-  await createStorage(
-    storageName,
-    'webdav',
-    {
-      url: webdavUrl,
-      vendor: 'other',
-      user: username,
-      pass: password,
-    },
-    {},
-    { obscure: true }
-  )
 }
 
 export {
-  getOpenlistToken,
+  loginOpenlist,
   modifyOpenlistConfig,
   setOpenlistPass,
   addOpenlistInRclone,
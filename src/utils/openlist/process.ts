@@ -6,7 +6,7 @@ import { logger } from '../../services/LoggerService'
 import { LOCALHOST_URLS } from '../../constants'
 import {
   ensureOpenlistWebdavPermissions,
-  getOpenlistToken,
+  loginOpenlist,
   modifyOpenlistConfig,
   setOpenlistPass,
 } from './openlist'
@@ -36,7 +36,7 @@ async function startOpenlist() {
 
   // 无条件重置 admin 密码，避免升级/迁移导致的密码不一致
   try {
-    await setOpenlistPass(nmConfig.framework.openlist.password)
+    await setOpenlistPass()
   } catch (e) {
     // 预启动阶段重置失败（例如 CLI 不可用），继续启动，稍后再尝试修复
     logger.warn('OpenList pre-start password reset failed, will retry after server starts', 'OpenList', { error: e })
@@ -72,21 +72,20 @@ async function startOpenlist() {
   openlistInfo.process.logFile = openlistLogFile()
   logger.info('openlist spawned from Rust', 'OpenList', { pid })
 
-  // 服务启动后再获取 token；若失败则尝试重置密码后重试一次
+  // 服务启动后再登录；若失败则尝试重置密码后重试一次
   try {
-    openlistInfo.endpoint.auth.token = await getOpenlistToken()
+    await loginOpenlist()
   } catch (e) {
-    logger.warn('OpenList token fetch failed, trying to reset password and retry', 'OpenList', { error: e })
-    await setOpenlistPass(nmConfig.framework.openlist.password)
-    openlistInfo.endpoint.auth.token = await getOpenlistToken()
+    logger.warn('OpenList login failed, trying to reset password and retry', 'OpenList', { error: e })
+    await setOpenlistPass()
+    await loginOpenlist()
   }
 
   // OpenList v4 默认可能未启用 WebDAV 权限，导致 rclone 访问 /dav 出现 403
   await ensureOpenlistWebdavPermissions(nmConfig.framework.openlist.user)
 
-  // OpenList 在用户更新后可能使旧 token 失效（PwdTS 变化），这里强制刷新一次 token
-  openlistInfo.endpoint.auth.token = ''
-  openlistInfo.endpoint.auth.token = await getOpenlistToken()
+  // OpenList 在用户更新后可能使旧 token 失效（PwdTS 变化），这里强制重新登录一次
+  await loginOpenlist()
 }
 
 async function stopOpenlist() {
@@ -1,6 +1,5 @@
-import { openlistInfo } from '../../services/openlist'
 import { logger } from '../../services/LoggerService'
-import { HTTP_HEADERS } from '../../constants'
+import { relayFetch } from '../tauri/relay'
 
 // API 响应接口
 interface ApiResponse {
@@ -11,7 +10,7 @@ interface ApiResponse {
 }
 
 /**
- * 构建完整 URL（包含查询参数）
+ * 构建带查询参数的接口路径
  */
 function buildFullPath(path: string, queryData?: object): string {
   const searchParams = new URLSearchParams()
@@ -23,7 +22,7 @@ function buildFullPath(path: string, queryData?: object): string {
     })
   }
   const queryString = searchParams.toString()
-  return `${openlistInfo.endpoint.url}${path}${queryString ? '?' + queryString : ''}`
+  return `${path}${queryString ? '?' + queryString : ''}`
 }
 
 /**
@@ -88,15 +87,7 @@ async function wrapApiCall<T>(
  */
 async function openlist_api_ping(): Promise<boolean> {
   try {
-    const url = openlistInfo.endpoint.url + '/ping'
-    const controller = new AbortController()
-    const timeoutId = setTimeout(() => controller.abort(), 2000) // 2秒超时
-
-    const res = await fetch(url, {
-      method: 'GET',
-      signal: controller.signal,
-    })
-    clearTimeout(timeoutId)
+    const res = await relayFetch('openlist', '/ping', { method: 'GET', timeoutMs: 2000 }) // 2秒超时
 
     if (res.ok) {
       return true
@@ -104,10 +95,7 @@ async function openlist_api_ping(): Promise<boolean> {
     logger.warn(`OpenList ping returned status: ${res.status}`, 'OpenListAPI')
     return false
   } catch (e) {
-    // 只在特定情况下记录错误，避免日志刷屏
-    if (e instanceof Error && e.name !== 'AbortError') {
-      logger.warn('OpenList ping failed', 'OpenListAPI', { message: e.message })
-    }
+    logger.debug('OpenList ping failed', 'OpenListAPI', { error: e })
     return false
   }
 }
@@ -120,15 +108,7 @@ async function openlist_api_get(path: string, queryData?: object): Promise<ApiRe
 
   return wrapApiCall(
     async () => {
-      const headers: Record<string, string> = {}
-      if (openlistInfo.endpoint.auth.token) {
-        headers['Authorization'] = openlistInfo.endpoint.auth.token
-      }
-      const res = await fetch(fullPath, {
-        method: 'GET',
-        redirect: 'follow',
-        headers,
-      })
+      const res = await relayFetch('openlist', fullPath, { method: 'GET' })
       return handleApiResponse(res, fullPath, 'GET')
     },
     fullPath,
@@ -148,16 +128,8 @@ async function openlist_api_post(
 
   return wrapApiCall(
     async () => {
-      const headers: Record<string, string> = {
-        'Content-Type': HTTP_HEADERS.CONTENT_TYPE_JSON,
-      }
-      if (openlistInfo.endpoint.auth.token) {
-        headers['Authorization'] = openlistInfo.endpoint.auth.token
-      }
-      const res = await fetch(fullPath, {
+      const res = await relayFetch('openlist', fullPath, {
         method: 'POST',
-        redirect: 'follow',
-        headers,
         body: bodyData ? JSON.stringify(bodyData) : null,
       })
       return handleApiResponse(res, fullPath, 'POST')
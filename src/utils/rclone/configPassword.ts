@@ -0,0 +1,42 @@
+import { invoke } from '@tauri-apps/api/core'
+
+/** NetMount 的 rclone 配置加密状态 */
+interface RcloneConfigEncryption {
+  encrypted: boolean
+  // 后端内存中已有可用的密码
+  unlocked: boolean
+}
+
+async function getRcloneConfigEncryption(): Promise<RcloneConfigEncryption> {
+  return await invoke<RcloneConfigEncryption>('get_rclone_config_encryption')
+}
+
+/**
+ * 输入配置密码，密码仅保存在后端内存中，启动 rclone 时通过 RCLONE_CONFIG_PASS 传入
+ * @param password - 配置密码，错误时抛出异常
+ */
+async function unlockRcloneConfig(password: string): Promise<void> {
+  await invoke('unlock_rclone_config', { password })
+}
+
+/**
+ * 设置、修改或移除配置密码，调用前需停止 rclone
+ * @param current - 当前密码（配置未加密或已解锁时可为空）
+ * @param password - 新密码，为空时移除加密
+ */
+async function setRcloneConfigPassword(
+  current: string,
+  password: string
+): Promise<RcloneConfigEncryption> {
+  return await invoke<RcloneConfigEncryption>('set_rclone_config_password', {
+    current: current || null,
+    password: password || null,
+  })
+}
+
+export {
+  getRcloneConfigEncryption,
+  unlockRcloneConfig,
+  setRcloneConfigPassword,
+  type RcloneConfigEncryption,
+}
@@ -1,17 +1,3 @@
-import { nmConfig } from '../../services/ConfigService'
-import { HTTP_HEADERS } from '../../constants'
-
-export function getRcloneApiHeaders() {
-  return {
-    Authorization: `Basic ${btoa(`${nmConfig.framework.rclone.user}:${nmConfig.framework.rclone.password}`)}`,
-    'Content-Type': HTTP_HEADERS.CONTENT_TYPE_JSON,
-  }
-}
-
-export function buildApiUrl(baseUrl: string, path: string): string {
-  return `${baseUrl}${path}`
-}
-
 export async function handleApiResponse(
   res: Response
 ): Promise<Record<string, unknown>> {
@@ -75,19 +75,21 @@ async function startRclone() {
   }
   rcloneInfo.process.logFile = logFile
 
+  // 后端启动时会把 --rc-addr 限制为 127.0.0.1，Linux 下默认改为数据目录中的 Unix 套接字
   const args: string[] = [
     'rcd',
     `--rc-addr=:${rcloneInfo.endpoint.localhost.port.toString()}`,
-    `--rc-user=${nmConfig.framework.rclone.user}`,
-    `--rc-pass=${nmConfig.framework.rclone.password}`,
     '--rc-allow-origin=' + window.location.origin || '*',
     `--config=${rcloneConfigFile()}`,
+    // 加密配置的密码由后端通过 RCLONE_CONFIG_PASS 传入，不要等待终端输入
+    '--ask-password=false',
     '--cache-dir=' + rcloneInfo.localArgs.path.tempDir,
     '--temp-dir=' + rcloneTempDir,
     `--log-file=${logFile}`,
     '--log-level=INFO',
   ]
 
+  // rc 认证信息由后端通过 RCLONE_RC_USER / RCLONE_RC_PASS 环境变量传入，不出现在命令行参数中
   if (nmConfig.framework.rclone.user === '') {
     args.push('--rc-no-auth')
   }
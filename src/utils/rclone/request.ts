@@ -1,7 +1,7 @@
 import { Message } from '@arco-design/web-react'
-import { rcloneInfo } from '../../services/rclone'
 import { logger } from '../../services/LoggerService'
-import { buildApiUrl, getRcloneApiHeaders, handleApiResponse } from './httpClient'
+import { relayFetch } from '../tauri/relay'
+import { handleApiResponse } from './httpClient'
 import {
   JobStatusResponse,
   isAsyncJobResponse,
@@ -56,11 +56,7 @@ async function printError(error: Error | Response): Promise<void> {
 
 async function rclone_api_noop(): Promise<boolean> {
   try {
-    const url = buildApiUrl(rcloneInfo.endpoint.url, '/rc/noop')
-    const res = await fetch(url, {
-      method: 'POST',
-      headers: { Authorization: getRcloneApiHeaders().Authorization },
-    })
+    const res = await relayFetch('rclone', '/rc/noop', { method: 'POST' })
     return res.ok
   } catch (e) {
     logger.debug('Rclone ping failed', 'Rclone', { error: e })
@@ -73,12 +69,9 @@ async function rclone_api_post(
   bodyData: object = {},
   ignoreError?: boolean
 ): Promise<RcloneApiResponse | undefined> {
-  const fullPath = buildApiUrl(rcloneInfo.endpoint.url, path)
-
   try {
-    const res = await fetch(fullPath, {
+    const res = await relayFetch('rclone', path, {
       method: 'POST',
-      headers: getRcloneApiHeaders(),
       body: JSON.stringify(bodyData),
     })
 
@@ -96,13 +89,8 @@ async function rclone_api_get(
   path: string,
   ignoreError?: boolean
 ): Promise<RcloneApiResponse | undefined> {
-  const fullPath = buildApiUrl(rcloneInfo.endpoint.url, path)
-
   try {
-    const res = await fetch(fullPath, {
-      method: 'GET',
-      headers: getRcloneApiHeaders(),
-    })
+    const res = await relayFetch('rclone', path, { method: 'GET' })
 
     const data = await handleApiResponse(res)
     return data
@@ -119,12 +107,9 @@ async function rclone_api_post_async(
   bodyData: object = {},
   ignoreError?: boolean
 ): Promise<number | undefined> {
-  const fullPath = buildApiUrl(rcloneInfo.endpoint.url, path)
-
   try {
-    const res = await fetch(fullPath, {
+    const res = await relayFetch('rclone', path, {
       method: 'POST',
-      headers: getRcloneApiHeaders(),
       body: JSON.stringify({ ...bodyData, _async: true }),
     })
 
@@ -148,12 +133,9 @@ async function rclone_api_job_status(
   jobid: number,
   ignoreError?: boolean
 ): Promise<JobStatusResponse | undefined> {
-  const fullPath = buildApiUrl(rcloneInfo.endpoint.url, '/job/status')
-
   try {
-    const res = await fetch(fullPath, {
+    const res = await relayFetch('rclone', '/job/status', {
       method: 'POST',
-      headers: getRcloneApiHeaders(),
       body: JSON.stringify({ jobid }),
     })
 
@@ -247,7 +229,6 @@ async function rclone_api_exec_async(
 export {
   rclone_api_post,
   rclone_api_get,
-  getRcloneApiHeaders,
   rclone_api_noop,
   rclone_api_post_async,
   rclone_api_job_status,
@@ -9,12 +9,6 @@ type WaitReadyOptions = {
   logEveryMs?: number
 }
 
-type RunSidecarOnceResult = {
-  code: number
-  stdout: string
-  stderr: string
-}
-
 function shortSidecarName(nameOrBinary: string): string {
   return nameOrBinary.includes('/') ? nameOrBinary.split('/').pop() || nameOrBinary : nameOrBinary
 }
@@ -23,19 +17,6 @@ async function spawnSidecar(binary: string, args: string[], cwd?: string): Promi
   return await invoke<number>('spawn_sidecar', { name: binary, args, cwd })
 }
 
-async function runSidecarOnce(
-  binary: string,
-  args: string[],
-  opts?: { timeoutMs?: number; cwd?: string }
-): Promise<RunSidecarOnceResult> {
-  return await invoke<RunSidecarOnceResult>('run_sidecar_once', {
-    name: binary,
-    args,
-    timeout_ms: opts?.timeoutMs,
-    cwd: opts?.cwd,
-  })
-}
-
 async function killSidecar(nameOrBinary: string): Promise<boolean> {
   const name = shortSidecarName(nameOrBinary)
   return (await invoke('kill_sidecar', { name })) as boolean
@@ -66,4 +47,4 @@ async function waitForReady(check: () => Promise<boolean>, opts: WaitReadyOption
   }
 }
 
-export { spawnSidecar, runSidecarOnce, killSidecar, waitForReady, shortSidecarName }
+export { spawnSidecar, killSidecar, waitForReady, shortSidecarName }
@@ -0,0 +1,74 @@
+import { invoke } from '@tauri-apps/api/core'
+
+/**
+ * rclone / OpenList 请求中转
+ *
+ * 框架凭据只保存在后端，webview 不直接访问 rclone/OpenList，
+ * 而是由后端注入认证信息后转发请求。
+ */
+
+type RelayFramework = 'rclone' | 'openlist'
+
+interface RelayResponse {
+  status: number
+  body: string
+}
+
+interface RelayInit {
+  method?: string
+  body?: string | null
+  timeoutMs?: number
+}
+
+// 上传分块大小
+const UPLOAD_CHUNK_SIZE = 8 * 1024 * 1024
+
+/**
+ * 通过后端转发请求，返回与 fetch 相同的 Response
+ * @param framework - 目标服务
+ * @param path - 以 / 开头的接口路径（可带查询参数）
+ */
+async function relayFetch(
+  framework: RelayFramework,
+  path: string,
+  init: RelayInit = {}
+): Promise<Response> {
+  const res = await invoke<RelayResponse>('relay_request', {
+    framework,
+    method: init.method || 'GET',
+    path,
+    body: init.body ?? null,
+    timeoutMs: init.timeoutMs,
+  })
+  // 204/304 等状态不允许带响应体
+  const body = res.body && ![204, 304].includes(res.status) ? res.body : null
+  return new Response(body, { status: res.status })
+}
+
+/**
+ * 通过后端上传文件到 rclone 存储
+ * @param fs - rclone 文件系统（如 `remote:`）
+ * @param remote - 目标文件路径
+ * @param onProgress - 上传进度（0-100）
+ */
+async function relayUpload(
+  file: Blob,
+  fs: string,
+  remote: string,
+  onProgress?: (percent: number) => void
+): Promise<void> {
+  const id = await invoke<string>('relay_upload_begin', { fs, remote })
+  try {
+    for (let offset = 0; offset < file.size; offset += UPLOAD_CHUNK_SIZE) {
+      const chunk = new Uint8Array(await file.slice(offset, offset + UPLOAD_CHUNK_SIZE).arrayBuffer())
+      await invoke('relay_upload_chunk', chunk, { headers: { 'upload-id': id } })
+      onProgress?.(Math.round((Math.min(offset + UPLOAD_CHUNK_SIZE, file.size) / file.size) * 100))
+    }
+    await invoke('relay_upload_finish', { id })
+  } catch (e) {
+    await invoke('relay_upload_abort', { id }).catch(() => {})
+    throw e
+  }
+}
+
+export { relayFetch, relayUpload, type RelayFramework, type RelayInit }
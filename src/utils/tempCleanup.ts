@@ -97,10 +97,9 @@ async function cleanupDirectory(dirPath: string, minAge: string): Promise<void>
       return
     }
 
-    await invoke('run_sidecar_once', {
-      name: 'binaries/rclone',
-      args: ['delete', dirPath, '--min-age', minAge],
-      timeout_ms: 30000,
+    await invoke('rclone_cleanup', {
+      cleanup: { action: 'delete', path: dirPath, minAge },
+      timeoutMs: 30000,
     }).catch(() => {
       // 忽略错误，清理失败不影响主流程
     })
@@ -120,10 +119,9 @@ async function purgeDirectory(dirPath: string, timeoutMs: number = 30000): Promi
       return
     }
 
-    await invoke('run_sidecar_once', {
-      name: 'binaries/rclone',
-      args: ['purge', dirPath],
-      timeout_ms: timeoutMs,
+    await invoke('rclone_cleanup', {
+      cleanup: { action: 'purge', path: dirPath },
+      timeoutMs,
     }).catch(() => {
       // 忽略错误
     })
@@ -143,10 +141,9 @@ async function cleanupOldLogs(logDir: string, minAge: string): Promise<void> {
     }
 
     // 只清理 .log.1, .log.2 等轮转日志，保留当前日志
-    await invoke('run_sidecar_once', {
-      name: 'binaries/rclone',
-      args: ['delete', logDir, '--include', '*.log.*', '--min-age', minAge],
-      timeout_ms: 30000,
+    await invoke('rclone_cleanup', {
+      cleanup: { action: 'delete', path: logDir, minAge, include: '*.log.*' },
+      timeoutMs: 30000,
     }).catch(() => {
       // 忽略错误
     })
@@ -173,10 +170,9 @@ export async function cleanupVfsCacheOnUnmount(storageName: string): Promise<voi
     // 2. 尝试清理该存储在 VFS 缓存中的残留文件
     // rclone VFS 缓存路径格式: <cache-dir>/vfs/<remote>/<path>
     // 使用 rclone cleanup 命令清理缓存中的残留文件
-    await invoke('run_sidecar_once', {
-      name: 'binaries/rclone',
-      args: ['cleanup', storageName + ':'],
-      timeout_ms: 15000,
+    await invoke('rclone_cleanup', {
+      cleanup: { action: 'cleanup', storage: storageName },
+      timeoutMs: 15000,
     }).catch(() => {
       // cleanup 可能因远程不可用而失败，忽略
     })